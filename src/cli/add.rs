@@ -7,6 +7,8 @@
 //! meh add "@products/alpha/api/timeout" "Timeout is 30s"
 //! meh add "Timeout is 30s" --path "@products/alpha/api/timeout"
 //! meh add "Timeout is 30s" --tags api,config
+//! meh add "Crash on login" --path @bugs/login --prop severity=high --prop component=auth
+//! meh add "Staging DB is down" --path @ops/staging --expires 3d
 //! ```
 //!
 //! # Architecture
@@ -16,9 +18,12 @@ use anyhow::{bail, Result};
 use clap::Args;
 use std::fs;
 
-use super::utils::find_meh_dir;
-use crate::core::fact::Fact;
+use super::utils::{embed_written, find_meh_dir};
+use crate::config::{Config, DedupePolicy};
+use crate::core::expiry::parse_expiry;
+use crate::core::fact::{parse_property, Fact, Status};
 use crate::core::path::Path;
+use crate::core::schema::{self, SchemaAction};
 use crate::core::storage::Storage;
 
 #[derive(Args, Debug)]
@@ -34,6 +39,18 @@ pub struct AddArgs {
     #[arg(short, long, value_delimiter = ',')]
     pub tags: Option<Vec<String>>,
 
+    /// Property as key=value (repeatable)
+    #[arg(long = "prop", value_name = "KEY=VALUE", value_parser = parse_property)]
+    pub props: Vec<(String, String)>,
+
+    /// Expire after a duration (30m, 12h, 7d, 2w) or at a date (2026-07-01)
+    #[arg(long, value_name = "WHEN")]
+    pub expires: Option<String>,
+
+    /// Ask for a review after a duration or at a date
+    #[arg(long, value_name = "WHEN")]
+    pub review_by: Option<String>,
+
     /// Source to add to (default: local)
     #[arg(short, long, default_value = "local")]
     pub source: String,
@@ -45,6 +62,10 @@ pub struct AddArgs {
     /// Read content from file
     #[arg(short = 'f', long)]
     pub file: Option<String>,
+
+    /// Add even if a fact with the same content exists (ignores core.dedupe)
+    #[arg(long)]
+    pub force: bool,
 }
 
 pub fn run(args: AddArgs) -> Result<()> {
@@ -82,19 +103,95 @@ pub fn run(args: AddArgs) -> Result<()> {
     // 5. Create Fact struct with builder pattern
     let mut fact = Fact::new(path.to_string(), title, content);
     fact.tags = args.tags.unwrap_or_default();
+    fact.properties = args.props.into_iter().collect();
+    let now = chrono::Utc::now();
+    fact.valid_until = args
+        .expires
+        .as_deref()
+        .map(|s| parse_expiry(s, now))
+        .transpose()?;
+    fact.review_by = args
+        .review_by
+        .as_deref()
+        .map(|s| parse_expiry(s, now))
+        .transpose()?;
     fact.author_type = crate::core::fact::AuthorType::Human;
     fact.author_id = "cli".to_string();
     fact.generate_summary(150);
 
     let meh_id = fact.meh_id();
 
-    // 6. Insert into storage
-    let storage = Storage::open(&db_path)?;
+    // 6. Check the schemas for this path
+    let config = Config::load()?;
+    let violation = schema::check(&config.schemas, &fact);
+    if let Some(violation) = &violation {
+        match violation.action {
+            SchemaAction::Reject => bail!("{}", violation.explanation()),
+            SchemaAction::Review => fact.status = Status::PendingReview,
+        }
+    }
+
+    // 7. Check for identical content (same path first, then KB-wide)
+    let storage = Storage::open(&db_path)?.with_namespace(config.namespace()?);
+    let duplicate = if args.force {
+        None
+    } else {
+        storage.find_duplicates(&fact)?.into_iter().next()
+    };
+
+    if let Some(existing) = duplicate {
+        let location = if existing.path == fact.path {
+            "at this path".to_string()
+        } else {
+            format!("at {}", existing.path)
+        };
+
+        match config.core.dedupe {
+            DedupePolicy::Refuse => bail!(
+                "Identical fact already exists {}: meh-{}\n   Use --force to add it anyway.",
+                location,
+                existing.id
+            ),
+            DedupePolicy::Vote => {
+                let mut vote = Fact::vote(&existing, "+1", "re-added identical content");
+                vote.author_type = crate::core::fact::AuthorType::Human;
+                vote.author_id = "cli".to_string();
+                storage.insert_vote(&vote)?;
+
+                println!(
+                    "🗳️  Identical fact already exists {}: meh-{}",
+                    location, existing.id
+                );
+                println!("   Recorded a +1 vote instead: meh-{}", vote.id);
+                return Ok(());
+            }
+            DedupePolicy::Warn => {
+                eprintln!(
+                    "⚠️  Identical fact already exists {}: meh-{}",
+                    location, existing.id
+                );
+            }
+        }
+    }
+
+    // 8. Insert into storage
     storage.insert(&fact)?;
+    embed_written(&storage, &[&fact]);
 
-    println!("✅ Fact added: {}", meh_id);
+    if violation.is_some() {
+        println!("⏳ Fact added for review: {}", meh_id);
+    } else {
+        println!("✅ Fact added: {}", meh_id);
+    }
     println!("   Path: {}", path);
     println!("   Title: {}", fact.title);
+    if let Some(until) = fact.valid_until {
+        println!("   Expires: {}", until);
+    }
+    if let Some(violation) = violation {
+        eprintln!("⚠️  {}", violation.explanation());
+        eprintln!("   Approve it with `meh pending -i`");
+    }
 
     Ok(())
 }
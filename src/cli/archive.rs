@@ -0,0 +1,102 @@
+//! Archive CLI commands
+//!
+//! Facts archived by `meh gc` (with `core.gc_mode = "archive"`) stay in
+//! data.db but are hidden from search and browsing.
+//!
+//! # Usage
+//! ```bash
+//! meh archive list                     # Most recently archived first
+//! meh archive list --path @products    # Only under a path
+//! meh archive restore meh-01HQ3K2JN5   # Bring a fact back
+//! ```
+
+use anyhow::{bail, Result};
+use clap::{Args, Subcommand};
+use ulid::Ulid;
+
+use crate::config::Config;
+use crate::core::storage::Storage;
+
+#[derive(Args, Debug)]
+pub struct ArchiveArgs {
+    #[command(subcommand)]
+    pub command: ArchiveCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ArchiveCommands {
+    /// List archived facts
+    List {
+        /// Only facts under this path prefix
+        #[arg(short, long)]
+        path: Option<String>,
+
+        /// Maximum results
+        #[arg(short, long, default_value = "50")]
+        limit: i64,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Restore an archived fact (superseded facts stay superseded)
+    Restore {
+        /// Fact ID (meh-xxx)
+        id: String,
+    },
+}
+
+pub fn execute(args: ArchiveArgs, config: &Config) -> Result<()> {
+    let db_path = config.data_dir();
+    if !db_path.exists() {
+        println!("No database found. Nothing archived.");
+        return Ok(());
+    }
+    let storage = Storage::open(&db_path)?.with_namespace(config.namespace()?);
+
+    match args.command {
+        ArchiveCommands::List { path, limit, json } => {
+            list_archived(&storage, path.as_deref(), limit, json)
+        }
+        ArchiveCommands::Restore { id } => restore(&storage, &id),
+    }
+}
+
+fn list_archived(storage: &Storage, path: Option<&str>, limit: i64, json: bool) -> Result<()> {
+    let facts = storage.get_archived(path, limit)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&facts)?);
+        return Ok(());
+    }
+
+    if facts.is_empty() {
+        println!("📦 Archive is empty.");
+        return Ok(());
+    }
+
+    println!("📦 {} archived fact(s):\n", facts.len());
+    for fact in &facts {
+        println!("  meh-{}  {}", fact.id, fact.path);
+        println!(
+            "      {} (archived {})",
+            fact.title,
+            fact.updated_at.format("%Y-%m-%d")
+        );
+    }
+
+    Ok(())
+}
+
+fn restore(storage: &Storage, id: &str) -> Result<()> {
+    let ulid = match Ulid::from_string(id.trim_start_matches("meh-")) {
+        Ok(ulid) => ulid,
+        Err(_) => bail!("Invalid fact ID: {}", id),
+    };
+
+    let status = storage.restore_archived(&ulid)?;
+    println!("✅ Restored meh-{} as {}", ulid, status.as_str());
+
+    Ok(())
+}
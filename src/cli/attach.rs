@@ -0,0 +1,167 @@
+//! `meh attach` / `meh attachment` commands
+//!
+//! Attach files (logs, stack traces, screenshots, diagrams) to facts and
+//! get them back out.
+//!
+//! # Usage
+//! ```bash
+//! meh attach meh-01HQ3K2JN5 ./crash.log
+//! meh attach @bugs/login-crash ./flow.png --name login-flow.png
+//! meh attachment list meh-01HQ3K2JN5
+//! meh attachment get meh-01HQ3K2JN5 crash.log              # to stdout
+//! meh attachment get meh-01HQ3K2JN5 flow.png -o flow.png   # to a file
+//! meh attachment rm meh-01HQ3K2JN5 crash.log
+//! ```
+
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::{Args, Subcommand};
+
+use super::utils::{find_fact, open_storage};
+use crate::config::Config;
+use crate::core::attachment::{self, format_size};
+
+#[derive(Args, Debug)]
+pub struct AttachArgs {
+    /// ID or path of the fact
+    pub target: String,
+
+    /// File to attach
+    pub file: PathBuf,
+
+    /// Attachment name (default: file name)
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// MIME type (default: guessed from extension)
+    #[arg(long)]
+    pub mime: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct AttachmentArgs {
+    #[command(subcommand)]
+    pub command: AttachmentCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AttachmentCommands {
+    /// List attachments of a fact
+    List {
+        /// ID or path of the fact
+        target: String,
+    },
+
+    /// Extract an attachment
+    Get {
+        /// ID or path of the fact
+        target: String,
+
+        /// Attachment name
+        name: String,
+
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Remove an attachment
+    Rm {
+        /// ID or path of the fact
+        target: String,
+
+        /// Attachment name
+        name: String,
+    },
+}
+
+pub fn run_attach(args: AttachArgs, config: &Config) -> Result<()> {
+    let storage = open_storage()?;
+    let fact = find_fact(&storage, &args.target)?;
+
+    let data =
+        fs::read(&args.file).with_context(|| format!("Failed to read {}", args.file.display()))?;
+    attachment::check_size(&data, config.core.attachment_max_kb)?;
+
+    let name = match args.name {
+        Some(name) => name,
+        None => args
+            .file
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(str::to_string)
+            .context("Cannot derive attachment name from path; use --name")?,
+    };
+    let mime = args
+        .mime
+        .unwrap_or_else(|| attachment::guess_mime(&args.file, &data));
+
+    let added = storage.add_attachment(&fact.id, &name, &mime, &data)?;
+
+    println!("📎 Attached {} to meh-{}", added.name, fact.id);
+    println!("   Type: {}", added.mime_type);
+    println!("   Size: {}", format_size(added.size));
+
+    Ok(())
+}
+
+pub fn run_attachment(args: AttachmentArgs) -> Result<()> {
+    let storage = open_storage()?;
+
+    match args.command {
+        AttachmentCommands::List { target } => {
+            let fact = find_fact(&storage, &target)?;
+            let attachments = storage.list_attachments(&fact.id)?;
+
+            if attachments.is_empty() {
+                println!("No attachments on meh-{}.", fact.id);
+                return Ok(());
+            }
+
+            println!("📎 Attachments of meh-{}:\n", fact.id);
+            for a in &attachments {
+                println!(
+                    "  {:<30} {:<26} {:>10}",
+                    a.name,
+                    a.mime_type,
+                    format_size(a.size)
+                );
+            }
+        }
+        AttachmentCommands::Get {
+            target,
+            name,
+            output,
+        } => {
+            let fact = find_fact(&storage, &target)?;
+            let Some((_, data)) = storage.get_attachment(&fact.id, &name)? else {
+                bail!("No attachment '{}' on meh-{}", name, fact.id);
+            };
+
+            match output {
+                Some(path) => {
+                    fs::write(&path, &data)
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                    eprintln!(
+                        "✅ Wrote {} ({})",
+                        path.display(),
+                        format_size(data.len() as u64)
+                    );
+                }
+                None => std::io::stdout().write_all(&data)?,
+            }
+        }
+        AttachmentCommands::Rm { target, name } => {
+            let fact = find_fact(&storage, &target)?;
+            if !storage.remove_attachment(&fact.id, &name)? {
+                bail!("No attachment '{}' on meh-{}", name, fact.id);
+            }
+            println!("🗑️  Removed {} from meh-{}", name, fact.id);
+        }
+    }
+
+    Ok(())
+}
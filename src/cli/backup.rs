@@ -0,0 +1,126 @@
+//! `meh backup` / `meh restore` commands
+//!
+//! Consistent snapshots of `data.db`, `notifications.db` and
+//! `pending_queue.db`, safe to take while the MCP server is running.
+//!
+//! # Usage
+//! ```bash
+//! meh backup ~/backups/meh                     # Snapshot directory
+//! meh backup ~/backups/meh --compress --keep 7 # .tar.gz, keep 7 newest (cron)
+//! meh restore ~/backups/meh/meh-backup-20260101-120000-123456.tar.gz
+//! ```
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::config::Config;
+use crate::core::backup::{self, Manifest};
+
+#[derive(Args, Debug)]
+pub struct BackupArgs {
+    /// Directory to write the snapshot into
+    pub dest: PathBuf,
+
+    /// Write a .tar.gz archive instead of a directory
+    #[arg(short = 'z', long)]
+    pub compress: bool,
+
+    /// Keep only the N newest snapshots in the destination
+    #[arg(long, value_name = "N")]
+    pub keep: Option<usize>,
+}
+
+#[derive(Args, Debug)]
+pub struct RestoreArgs {
+    /// Snapshot to restore (directory or .tar.gz)
+    pub archive: PathBuf,
+
+    /// Skip confirmation prompt
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+}
+
+pub fn run_backup(args: BackupArgs, config: &Config) -> Result<()> {
+    let db_path = config.data_dir();
+
+    // 1. Snapshot all databases
+    let (path, manifest) = backup::create_backup(&db_path, &args.dest, args.compress)?;
+
+    println!("💾 Backup created: {}", path.display());
+    print_manifest(&manifest);
+
+    // 2. Rotate old snapshots
+    if let Some(keep) = args.keep {
+        let removed = backup::rotate_backups(&args.dest, keep.max(1))?;
+        if !removed.is_empty() {
+            println!("\n🗑️  Removed {} old backup(s):", removed.len());
+            for path in removed {
+                println!("   {}", path.display());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn run_restore(args: RestoreArgs, config: &Config) -> Result<()> {
+    let db_path = config.data_dir();
+
+    // 1. Unpack and validate (live databases untouched so far)
+    let staged = backup::prepare_restore(&args.archive, &db_path)?;
+
+    println!("✅ Backup is valid: {}", args.archive.display());
+    print_manifest(&staged.manifest);
+    println!("\n📁 Restore into: {}", db_path.display());
+
+    // 2. Confirm unless -y flag
+    if !args.yes {
+        println!("⚠️  Stop any running `meh serve` first.");
+        print!("Replace the current databases with this backup? [y/N] ");
+        use std::io::{self, Write};
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    // 3. Swap in the snapshot
+    let restored = staged.manifest.facts.total;
+    staged.apply()?;
+
+    println!("♻️  Restored {} fact(s).", restored);
+
+    Ok(())
+}
+
+fn print_manifest(manifest: &Manifest) {
+    println!(
+        "   Created: {} (meh {})",
+        manifest.created_at.format("%Y-%m-%d %H:%M:%S UTC"),
+        manifest.meh_version
+    );
+    println!(
+        "   Facts: {} total, {} active, {} deprecated, {} superseded, {} pending review",
+        manifest.facts.total,
+        manifest.facts.active,
+        manifest.facts.deprecated,
+        manifest.facts.superseded,
+        manifest.facts.pending_review
+    );
+    for db in &manifest.databases {
+        println!(
+            "   {:<18} v{}  {:>8} KB  sha256:{}",
+            db.name,
+            db.schema_version,
+            db.size_bytes.div_ceil(1024),
+            &db.sha256[..12]
+        );
+    }
+}
@@ -20,8 +20,8 @@ use anyhow::Result;
 use clap::Args;
 use std::collections::HashMap;
 
-use super::utils::find_meh_dir;
-use crate::core::storage::Storage;
+use super::utils::{find_meh_dir, open_storage_at};
+use crate::core::fact::FactType;
 
 #[derive(Args, Debug)]
 pub struct LsArgs {
@@ -50,7 +50,7 @@ pub fn run_ls(args: LsArgs) -> Result<()> {
     // 1. Find .meh directory
     let meh_dir = find_meh_dir()?;
     let db_path = meh_dir.join("data.db");
-    let storage = Storage::open(&db_path)?;
+    let storage = open_storage_at(&db_path)?;
 
     // 2. Get children at the given path
     let prefix = args.path.trim_end_matches('/');
@@ -98,7 +98,7 @@ pub fn run_tree(args: TreeArgs) -> Result<()> {
     // 1. Find .meh directory
     let meh_dir = find_meh_dir()?;
     let db_path = meh_dir.join("data.db");
-    let storage = Storage::open(&db_path)?;
+    let storage = open_storage_at(&db_path)?;
 
     // 2. Get all facts with prefix and build tree
     let prefix = args.path.trim_end_matches('/');
@@ -109,17 +109,22 @@ pub fn run_tree(args: TreeArgs) -> Result<()> {
         return Ok(());
     }
 
-    // Build tree structure
+    // Build tree structure; warnings mark the path of the fact they warn about
     let mut tree = PathTree::new();
+    let mut total = 0;
     for fact in &facts {
-        tree.add_path(&fact.path);
+        if fact.fact_type == FactType::Warning {
+            tree.add_warning(&fact.path);
+        } else {
+            tree.add_path(&fact.path);
+            total += 1;
+        }
     }
 
     // Print tree
     println!("📂 {}", if prefix.is_empty() { "@" } else { prefix });
     tree.print(prefix, args.depth, "", true);
 
-    let total: usize = facts.len();
     println!("\n{} facts total", total);
 
     Ok(())
@@ -129,6 +134,7 @@ pub fn run_tree(args: TreeArgs) -> Result<()> {
 struct PathTree {
     children: HashMap<String, PathTree>,
     is_leaf: bool,
+    warnings: usize,
 }
 
 impl PathTree {
@@ -136,29 +142,28 @@ impl PathTree {
         Self {
             children: HashMap::new(),
             is_leaf: false,
+            warnings: 0,
         }
     }
 
     fn add_path(&mut self, path: &str) {
-        let segments: Vec<&str> = path
-            .trim_start_matches('@')
-            .trim_start_matches('/')
-            .split('/')
-            .filter(|s| !s.is_empty())
-            .collect();
-
-        self.add_segments(&segments, 0);
+        self.node(path).is_leaf = true;
     }
 
-    fn add_segments(&mut self, segments: &[&str], idx: usize) {
-        if idx >= segments.len() {
-            self.is_leaf = true;
-            return;
-        }
+    fn add_warning(&mut self, path: &str) {
+        self.node(path).warnings += 1;
+    }
 
-        let segment = segments[idx].to_string();
-        let child = self.children.entry(segment).or_insert_with(PathTree::new);
-        child.add_segments(segments, idx + 1);
+    fn node(&mut self, path: &str) -> &mut PathTree {
+        path.trim_start_matches('@')
+            .trim_start_matches('/')
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .fold(self, |node, segment| {
+                node.children
+                    .entry(segment.to_string())
+                    .or_insert_with(PathTree::new)
+            })
     }
 
     fn print(&self, _prefix: &str, max_depth: usize, indent: &str, is_last: bool) {
@@ -183,7 +188,12 @@ impl PathTree {
                 "├── "
             };
             let suffix = if child.children.is_empty() { "" } else { "/" };
-            println!("{}{}{}{}", indent, branch, key, suffix);
+            let warnings = if child.warnings > 0 {
+                format!(" ⚠️ {}", child.warnings)
+            } else {
+                String::new()
+            };
+            println!("{}{}{}{}{}", indent, branch, key, suffix, warnings);
 
             let new_indent = format!("{}{}", indent, if is_last_child { "    " } else { "│   " });
             child.print_level(max_depth, current_depth + 1, &new_indent, is_last_child);
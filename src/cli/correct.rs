@@ -16,9 +16,8 @@ use anyhow::Result;
 use clap::Args;
 use std::fs;
 
-use super::utils::{find_fact, find_meh_dir};
+use super::utils::{embed_written, find_fact, find_meh_dir, open_storage_at};
 use crate::core::fact::Fact;
-use crate::core::storage::Storage;
 
 #[derive(Args, Debug)]
 pub struct CorrectArgs {
@@ -41,7 +40,7 @@ pub fn run(args: CorrectArgs) -> Result<()> {
     // 1. Find .meh directory
     let meh_dir = find_meh_dir()?;
     let db_path = meh_dir.join("data.db");
-    let storage = Storage::open(&db_path)?;
+    let storage = open_storage_at(&db_path)?;
 
     // 2. Find original fact
     let original = find_fact(&storage, &args.target)?;
@@ -60,11 +59,12 @@ pub fn run(args: CorrectArgs) -> Result<()> {
     correction.generate_summary(150);
     let meh_id = correction.meh_id();
 
-    // 5. Insert new fact
-    storage.insert(&correction)?;
-
-    // 6. Mark original as superseded
-    storage.mark_superseded(&original.id)?;
+    // 5. Insert new fact and mark original as superseded (atomically)
+    storage.transaction(|tx| {
+        tx.insert(&correction)?;
+        tx.mark_superseded(&original.id)
+    })?;
+    embed_written(&storage, &[&correction]);
 
     println!("✅ Correction created: {}", meh_id);
     println!("   Supersedes: meh-{}", original.id);
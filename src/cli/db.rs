@@ -0,0 +1,349 @@
+//! Database maintenance commands
+//!
+//! # Usage
+//! ```bash
+//! meh db version              # Show schema version of each database
+//! meh db migrate              # Apply pending schema migrations
+//! meh db migrate --dry-run    # Show what would be applied
+//! meh db reindex              # Rebuild the full-text search index
+//! meh db reindex --embeddings # Also re-embed every fact (semantic search)
+//! meh db check                # Integrity and consistency report
+//! meh db check --fix          # Migrate, then apply safe repairs
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use rusqlite::Connection;
+
+use crate::config::Config;
+use crate::core::migrations::{self, Migration};
+use crate::core::storage::Storage;
+use crate::core::{embed, notifications, pending_queue, storage};
+
+#[derive(Args, Debug)]
+pub struct DbArgs {
+    #[command(subcommand)]
+    pub command: DbCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DbCommands {
+    /// Apply pending schema migrations
+    Migrate {
+        /// Show pending migrations without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Show schema version of each database
+    Version,
+
+    /// Rebuild the full-text search index (recovery)
+    Reindex(ReindexArgs),
+
+    /// Check database integrity and fact consistency (read-only without --fix)
+    Check {
+        /// Apply pending migrations, then safe repairs (FTS rebuild, stale
+        /// superseded originals)
+        #[arg(long)]
+        fix: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// `meh db reindex` / `meh reindex`
+#[derive(Args, Debug)]
+pub struct ReindexArgs {
+    /// Also recompute the embeddings used by `--semantic` search
+    #[arg(long)]
+    pub embeddings: bool,
+}
+
+/// A database managed by meh, with its migration list
+struct Database {
+    name: &'static str,
+    path: PathBuf,
+    migrations: &'static [Migration],
+}
+
+/// All databases that live next to `data.db`
+fn databases(config: &Config) -> Vec<Database> {
+    let data_db = config.data_dir();
+    let dir = data_db
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from(".meh"));
+
+    vec![
+        Database {
+            name: "data.db",
+            path: data_db,
+            migrations: storage::MIGRATIONS,
+        },
+        Database {
+            name: "notifications.db",
+            path: dir.join("notifications.db"),
+            migrations: notifications::MIGRATIONS,
+        },
+        Database {
+            name: "pending_queue.db",
+            path: dir.join("pending_queue.db"),
+            migrations: pending_queue::MIGRATIONS,
+        },
+    ]
+}
+
+fn open_existing(db: &Database) -> Result<Option<Connection>> {
+    if !db.path.exists() {
+        return Ok(None);
+    }
+    let conn = Connection::open(&db.path)
+        .with_context(|| format!("Failed to open {}", db.path.display()))?;
+    conn.busy_timeout(std::time::Duration::from_secs(5))?;
+    Ok(Some(conn))
+}
+
+pub fn execute(args: DbArgs, config: &Config) -> Result<()> {
+    match args.command {
+        DbCommands::Migrate { dry_run } => run_migrate(config, dry_run),
+        DbCommands::Version => run_version(config),
+        DbCommands::Reindex(args) => run_reindex(args, config),
+        DbCommands::Check { fix, json } => run_check(config, fix, json),
+    }
+}
+
+fn run_version(config: &Config) -> Result<()> {
+    println!("🗄️  Schema versions\n");
+
+    for db in databases(config) {
+        let Some(conn) = open_existing(&db)? else {
+            println!("  {:<18} (not created)", db.name);
+            continue;
+        };
+
+        let current = migrations::current_version(&conn)?;
+        let latest = migrations::latest_version(db.migrations);
+
+        let status = if current > latest {
+            "⚠️  newer than this meh".to_string()
+        } else if current < latest {
+            format!("{} pending", latest - current)
+        } else {
+            "up to date".to_string()
+        };
+
+        println!("  {:<18} v{} / v{} ({})", db.name, current, latest, status);
+    }
+
+    Ok(())
+}
+
+fn run_migrate(config: &Config, dry_run: bool) -> Result<()> {
+    let mut total = 0;
+
+    for db in databases(config) {
+        let Some(conn) = open_existing(&db)? else {
+            continue;
+        };
+
+        migrations::check_compatible(&conn, db.name, db.migrations)?;
+
+        if dry_run {
+            let pending = migrations::pending(&conn, db.migrations)?;
+            for m in &pending {
+                println!("  {} v{}: {}", db.name, m.version, m.description);
+            }
+            total += pending.len();
+        } else {
+            let applied = migrations::migrate(&conn, db.name, db.migrations)?;
+            for version in &applied {
+                let description = db
+                    .migrations
+                    .iter()
+                    .find(|m| m.version == *version)
+                    .map(|m| m.description)
+                    .unwrap_or_default();
+                println!("  ✅ {} v{}: {}", db.name, version, description);
+            }
+            total += applied.len();
+        }
+    }
+
+    if !dry_run {
+        sync_fuzzy_indexes(config)?;
+    }
+
+    if total == 0 {
+        println!("✨ All databases are up to date.");
+    } else if dry_run {
+        println!("\n🔍 {} migration(s) pending (dry run)", total);
+    } else {
+        println!("\n✅ Applied {} migration(s)", total);
+    }
+
+    Ok(())
+}
+
+/// Create or drop the trigram index of the default database and each local
+/// KB to follow its fuzzy setting (see `Config::fuzzy_for`); opening a KB
+/// does the same, this reports it
+fn sync_fuzzy_indexes(config: &Config) -> Result<()> {
+    let mut paths = vec![config.data_dir()];
+    for kb in config.kbs.kb.iter().filter(|kb| kb.kb_type != "remote") {
+        if let Some(path) = &kb.path {
+            paths.push(PathBuf::from(path));
+        }
+    }
+    paths.dedup();
+
+    for db_path in paths.iter().filter(|p| p.exists()) {
+        let storage = Storage::open(db_path)?;
+        let fuzzy = config.fuzzy_for(db_path);
+        if storage.has_fuzzy_index()? != fuzzy {
+            storage.with_fuzzy(fuzzy)?;
+            let action = if fuzzy { "Built" } else { "Dropped" };
+            println!("✅ {} fuzzy search index of {}", action, db_path.display());
+        }
+    }
+    Ok(())
+}
+
+pub fn run_reindex(args: ReindexArgs, config: &Config) -> Result<()> {
+    let db_path = config.data_dir();
+    if !db_path.exists() {
+        println!("No database found. Nothing to reindex.");
+        return Ok(());
+    }
+
+    sync_fuzzy_indexes(config)?;
+
+    let storage = Storage::open(&db_path)?;
+    let started = std::time::Instant::now();
+    storage.reindex()?;
+
+    println!(
+        "✅ Rebuilt search index in {:.2}s",
+        started.elapsed().as_secs_f64()
+    );
+
+    if args.embeddings {
+        let embedder = embed::from_config(&config.search.embeddings)?;
+        let started = std::time::Instant::now();
+        storage.clear_embeddings()?;
+        let count = embed::backfill(&storage, embedder.as_ref())?;
+        println!(
+            "✅ Embedded {} fact(s) with {} in {:.2}s",
+            count,
+            embedder.model(),
+            started.elapsed().as_secs_f64()
+        );
+    }
+
+    Ok(())
+}
+
+fn run_check(config: &Config, fix: bool, json: bool) -> Result<()> {
+    let db_path = config.data_dir();
+    if !db_path.exists() {
+        println!("No database found. Nothing to check.");
+        return Ok(());
+    }
+
+    // Only --fix writes: a plain check reports on the database as it is,
+    // even one that cannot be migrated
+    let storage = Storage::open_unmigrated(&db_path)?;
+    if fix {
+        storage.migrate()?;
+    }
+    let report = storage.check()?;
+
+    let repaired = if fix && report.has_fixable() {
+        Some(storage.repair(&report)?)
+    } else {
+        None
+    };
+
+    if json {
+        let issues: Vec<_> = report
+            .issues
+            .iter()
+            .map(|i| {
+                serde_json::json!({
+                    "kind": i.kind.as_str(),
+                    "severity": if i.kind.is_error() { "error" } else { "warning" },
+                    "fact_id": i.fact_id,
+                    "detail": i.detail,
+                    "fixable": i.kind.is_fixable(),
+                })
+            })
+            .collect();
+        let out = serde_json::json!({
+            "facts_checked": report.facts_checked,
+            "issues": issues,
+            "repaired": repaired.unwrap_or(0),
+        });
+        println!("{}", serde_json::to_string_pretty(&out)?);
+        return Ok(());
+    }
+
+    println!(
+        "🩺 Checked {} fact(s) in {}\n",
+        report.facts_checked,
+        db_path.display()
+    );
+
+    if report.issues.is_empty() {
+        println!("✅ No problems found.");
+        return Ok(());
+    }
+
+    for issue in &report.issues {
+        let icon = if issue.kind.is_error() {
+            "❌"
+        } else {
+            "⚠️ "
+        };
+        let fixable = if issue.kind.is_fixable() {
+            " [fixable]"
+        } else {
+            ""
+        };
+        match &issue.fact_id {
+            Some(id) => println!(
+                "  {} {} meh-{}: {}{}",
+                icon,
+                issue.kind.as_str(),
+                id,
+                issue.detail,
+                fixable
+            ),
+            None => println!(
+                "  {} {}: {}{}",
+                icon,
+                issue.kind.as_str(),
+                issue.detail,
+                fixable
+            ),
+        }
+    }
+
+    let errors = report.issues.iter().filter(|i| i.kind.is_error()).count();
+    println!(
+        "\n{} error(s), {} warning(s)",
+        errors,
+        report.issues.len() - errors
+    );
+
+    match repaired {
+        Some(n) => println!("🔧 Repaired {} issue(s).", n),
+        None if report.has_fixable() => println!("ℹ️  Run with --fix to apply safe repairs."),
+        None => {}
+    }
+
+    Ok(())
+}
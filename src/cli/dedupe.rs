@@ -0,0 +1,91 @@
+//! `meh dedupe` command
+//!
+//! Finds groups of live facts whose content is identical once whitespace,
+//! case and markdown formatting are ignored.
+//!
+//! # Usage
+//! ```bash
+//! meh dedupe          # List duplicate clusters
+//! meh dedupe --json   # Machine-readable output
+//! ```
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::config::Config;
+use crate::core::storage::Storage;
+
+#[derive(Args, Debug)]
+pub struct DedupeArgs {
+    /// Output as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+pub fn run(args: DedupeArgs, config: &Config) -> Result<()> {
+    let db_path = config.data_dir();
+    if !db_path.exists() {
+        println!("No database found. Nothing to check.");
+        return Ok(());
+    }
+
+    let storage = Storage::open(&db_path)?.with_namespace(config.namespace()?);
+    let clusters = storage.duplicate_clusters()?;
+
+    if args.json {
+        let json: Vec<serde_json::Value> = clusters
+            .iter()
+            .map(|c| {
+                serde_json::json!({
+                    "content_hash": c.content_hash,
+                    "facts": c.facts.iter().map(|f| serde_json::json!({
+                        "id": format!("meh-{}", f.id),
+                        "path": f.path,
+                        "title": f.title,
+                        "status": f.status.as_str(),
+                        "created_at": f.created_at.to_rfc3339(),
+                    })).collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    if clusters.is_empty() {
+        println!("✨ No duplicate facts found.");
+        return Ok(());
+    }
+
+    let extra: usize = clusters.iter().map(|c| c.facts.len() - 1).sum();
+    println!(
+        "🔁 {} duplicate cluster(s), {} redundant fact(s):\n",
+        clusters.len(),
+        extra
+    );
+
+    for cluster in &clusters {
+        println!(
+            "  {} ({} facts)",
+            &cluster.content_hash[..12],
+            cluster.facts.len()
+        );
+        for (i, fact) in cluster.facts.iter().enumerate() {
+            // Oldest first: that is usually the one to keep
+            let marker = if i == 0 { "●" } else { "○" };
+            println!(
+                "    {} meh-{}  {}  {}",
+                marker,
+                fact.id,
+                fact.created_at.format("%Y-%m-%d"),
+                fact.path
+            );
+        }
+        println!();
+    }
+
+    println!("ℹ️  ● oldest fact. Retire the others with `meh deprecate <id>`");
+    println!("   or consolidate a cluster with `meh merge <ids>... --into <path>`.");
+
+    Ok(())
+}
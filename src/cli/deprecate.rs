@@ -14,8 +14,7 @@
 use anyhow::Result;
 use clap::Args;
 
-use super::utils::{find_fact, find_meh_dir};
-use crate::core::storage::Storage;
+use super::utils::{find_fact, find_meh_dir, open_storage_at};
 
 #[derive(Args, Debug)]
 pub struct DeprecateArgs {
@@ -35,7 +34,7 @@ pub fn run(args: DeprecateArgs) -> Result<()> {
     // 1. Find .meh directory
     let meh_dir = find_meh_dir()?;
     let db_path = meh_dir.join("data.db");
-    let storage = Storage::open(&db_path)?;
+    let storage = open_storage_at(&db_path)?;
 
     // 2. Find fact to deprecate
     let fact = find_fact(&storage, &args.target)?;
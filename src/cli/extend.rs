@@ -16,9 +16,8 @@ use anyhow::Result;
 use clap::Args;
 use std::fs;
 
-use super::utils::{find_fact, find_meh_dir};
+use super::utils::{embed_written, find_fact, find_meh_dir, open_storage_at};
 use crate::core::fact::Fact;
-use crate::core::storage::Storage;
 
 #[derive(Args, Debug)]
 pub struct ExtendArgs {
@@ -37,7 +36,7 @@ pub fn run(args: ExtendArgs) -> Result<()> {
     // 1. Find .meh directory
     let meh_dir = find_meh_dir()?;
     let db_path = meh_dir.join("data.db");
-    let storage = Storage::open(&db_path)?;
+    let storage = open_storage_at(&db_path)?;
 
     // 2. Find original fact
     let original = find_fact(&storage, &args.target)?;
@@ -58,6 +57,7 @@ pub fn run(args: ExtendArgs) -> Result<()> {
 
     // 5. Insert new fact
     storage.insert(&extension)?;
+    embed_written(&storage, &[&extension]);
 
     println!("✅ Extension created: {}", meh_id);
     println!("   Extends: meh-{}", original.id);
@@ -0,0 +1,114 @@
+//! `meh find` command
+//!
+//! Lists facts whose path matches a glob: `*` is one segment, `**` any
+//! number of segments.
+//!
+//! # Usage
+//! ```bash
+//! meh find '@products/*/api/**'
+//! meh find '**/timeout' --status active,deprecated
+//! meh find '@products/**' --tag api -f compact
+//! meh find '@team/*/onboarding' --server http://localhost:3000 --kb my-kb
+//! ```
+//!
+//! # Architecture
+//! - Local: the glob's literal prefix is a range on the path index
+//!   (see `Storage::find_by_pattern`)
+//! - Remote: walks the tree with browse calls (see `RemoteKb::find`)
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::core::fact::{Fact, Status};
+use crate::core::kb::KnowledgeBase;
+
+#[derive(Args, Debug)]
+pub struct FindArgs {
+    /// Path pattern, e.g. '@products/*/api/**' (quote it for the shell)
+    pub pattern: String,
+
+    /// Statuses to include (comma-separated: active, deprecated,
+    /// superseded, archived, pending_review)
+    #[arg(long, value_delimiter = ',', default_value = "active")]
+    pub status: Vec<Status>,
+
+    /// Required tags (comma-separated or repeated, AND logic)
+    #[arg(short, long = "tag", value_delimiter = ',')]
+    pub tags: Vec<String>,
+
+    /// Maximum results
+    #[arg(short, long, default_value = "100")]
+    pub limit: usize,
+
+    /// Output format (pretty, json, compact)
+    #[arg(short, long, default_value = "pretty")]
+    pub format: String,
+
+    /// Use remote server instead of local database
+    #[arg(long, env = "MEH_SERVER_URL")]
+    pub server: Option<String>,
+
+    /// Knowledge base slug (for remote operations)
+    #[arg(long, env = "MEH_KB")]
+    pub kb: Option<String>,
+}
+
+pub async fn run(args: FindArgs) -> Result<()> {
+    let config = crate::config::Config::load()?;
+    let kb = KnowledgeBase::from_args(args.server.as_deref(), args.kb.as_deref(), &config)?;
+
+    // One more than asked for tells whether there are more
+    let fetch = args.limit.saturating_add(1);
+    let mut facts = match &kb {
+        KnowledgeBase::Local(local) => {
+            local.find(&args.pattern, &args.status, &args.tags, fetch)?
+        }
+        KnowledgeBase::Remote(remote) => {
+            remote
+                .find(&args.pattern, &args.status, &args.tags, fetch)
+                .await?
+        }
+    };
+    let limited = facts.len() > args.limit;
+    facts.truncate(args.limit);
+
+    match args.format.as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(&facts)?),
+        "compact" => {
+            for fact in &facts {
+                println!(
+                    "{}\t{}\t{}\t{:.2}",
+                    fact.path,
+                    fact.status.as_str(),
+                    fact.title,
+                    fact.trust_score
+                );
+            }
+        }
+        _ => print_pretty(&facts, &args.pattern, limited.then_some(args.limit)),
+    }
+
+    Ok(())
+}
+
+/// `limited_to`: the limit, if more facts match than it let through
+fn print_pretty(facts: &[Fact], pattern: &str, limited_to: Option<usize>) {
+    if facts.is_empty() {
+        println!("No facts match: {}", pattern);
+        return;
+    }
+
+    println!("\n🔎 {} fact(s) match {}:\n", facts.len(), pattern);
+    for fact in facts {
+        let status = if fact.status == Status::Active {
+            String::new()
+        } else {
+            format!("  [{}]", fact.status.as_str())
+        };
+        println!("  {}{}", fact.path, status);
+        println!("     {} | Trust: {:.2}", fact.title, fact.trust_score);
+    }
+    if let Some(limit) = limited_to {
+        println!("\n(limited to {}; use --limit for more)", limit);
+    }
+}
@@ -1,10 +1,15 @@
 //! Garbage Collection CLI command
+//!
+//! Also runs the expiry pass: facts past `valid_until` are deprecated and
+//! passed review dates are reported (see `core::expiry`).
 
 use anyhow::Result;
 use clap::Args;
 
 use crate::config::Config;
-use crate::core::storage::{GcReason, Storage};
+use crate::core::expiry::{self, ExpiryReport};
+use crate::core::notifications::NotificationStorage;
+use crate::core::storage::{GcMode, GcReason, Storage};
 
 #[derive(Args, Debug)]
 pub struct GcArgs {
@@ -12,10 +17,14 @@ pub struct GcArgs {
     #[arg(long)]
     pub retention_days: Option<u32>,
 
-    /// Show what would be deleted without actually deleting
+    /// Show what would be archived/deleted without changing anything
     #[arg(long)]
     pub dry_run: bool,
 
+    /// Permanently delete instead of archiving (overrides core.gc_mode)
+    #[arg(long)]
+    pub purge: bool,
+
     /// Skip confirmation prompt
     #[arg(short = 'y', long)]
     pub yes: bool,
@@ -23,6 +32,15 @@ pub struct GcArgs {
 
 pub fn run(args: GcArgs, config: &Config) -> Result<()> {
     let retention_days = args.retention_days.unwrap_or(config.core.gc_retention_days);
+    let mode = if args.purge {
+        GcMode::Purge
+    } else {
+        config.core.gc_mode
+    };
+    let verb = match mode {
+        GcMode::Archive => "Archive",
+        GcMode::Purge => "Delete",
+    };
 
     let db_path = config.data_dir();
 
@@ -31,42 +49,70 @@ pub fn run(args: GcArgs, config: &Config) -> Result<()> {
         return Ok(());
     }
 
-    let storage = Storage::open(&db_path)?;
+    let storage = Storage::open(&db_path)?.with_namespace(config.namespace()?);
 
-    // First do a dry run to show candidates
-    let preview = storage.garbage_collect(retention_days, true)?;
+    // First do dry runs to show what would change: expired time-bound
+    // facts (they reach GC once past the retention period) and candidates
+    let now = chrono::Utc::now();
+    let expiry_preview = expiry::process(&storage, None, now, true)?;
+    print_expiry(&expiry_preview);
+
+    let preview = storage.garbage_collect(retention_days, mode, true)?;
 
     if preview.candidates.is_empty() {
         println!(
             "✨ No deprecated/superseded facts older than {} days.",
             retention_days
         );
-        return Ok(());
+    } else {
+        println!(
+            "🧹 Found {} fact(s) to clean up (older than {} days):\n",
+            preview.candidates.len(),
+            retention_days
+        );
+
+        for candidate in &preview.candidates {
+            let reason = match candidate.reason {
+                GcReason::Deprecated => "deprecated",
+                GcReason::Superseded => "superseded",
+                GcReason::Archived => "archived",
+            };
+            println!("  {} {} ({})", candidate.id, candidate.path, reason);
+        }
+        println!();
     }
 
-    println!(
-        "🧹 Found {} fact(s) to clean up (older than {} days):\n",
-        preview.candidates.len(),
-        retention_days
-    );
-
-    for candidate in &preview.candidates {
-        let reason = match candidate.reason {
-            GcReason::Deprecated => "deprecated",
-            GcReason::Superseded => "superseded",
-        };
-        println!("  {} {} ({})", candidate.id, candidate.path, reason);
+    let has_expiry_work =
+        !expiry_preview.expired.is_empty() || !expiry_preview.review_due.is_empty();
+    if preview.candidates.is_empty() && !has_expiry_work {
+        return Ok(());
     }
-    println!();
 
     if args.dry_run {
-        println!("ℹ️  Dry run - no facts were deleted.");
+        println!("ℹ️  Dry run - no facts were changed.");
         return Ok(());
     }
 
     // Confirm unless -y flag
     if !args.yes {
-        print!("Delete {} fact(s)? [y/N] ", preview.candidates.len());
+        let mut actions = Vec::new();
+        if !expiry_preview.expired.is_empty() {
+            actions.push(format!(
+                "deprecate {} expired fact(s)",
+                expiry_preview.expired.len()
+            ));
+        }
+        if !preview.candidates.is_empty() {
+            actions.push(format!(
+                "{} {} fact(s)",
+                verb.to_lowercase(),
+                preview.candidates.len()
+            ));
+        }
+        if actions.is_empty() {
+            actions.push("send review reminders".to_string());
+        }
+        print!("Proceed to {}? [y/N] ", actions.join(" and "));
         use std::io::{self, Write};
         io::stdout().flush()?;
 
@@ -79,10 +125,46 @@ pub fn run(args: GcArgs, config: &Config) -> Result<()> {
         }
     }
 
-    // Actually delete
-    let result = storage.garbage_collect(retention_days, false)?;
+    let notifications_db = db_path
+        .parent()
+        .map(|p| p.join("notifications.db"))
+        .unwrap_or_else(|| db_path.with_extension("notifications.db"));
+    let notifications = NotificationStorage::open(&notifications_db).ok();
+    let expiry = expiry::process(&storage, notifications.as_ref(), now, false)?;
+    if !expiry.expired.is_empty() {
+        println!("⏰ Deprecated {} expired fact(s).", expiry.expired.len());
+    }
 
-    println!("🗑️  Deleted {} fact(s).", result.deleted_count);
+    if preview.candidates.is_empty() {
+        return Ok(());
+    }
+
+    let result = storage.garbage_collect(retention_days, mode, false)?;
+
+    match mode {
+        GcMode::Archive => println!(
+            "📦 Archived {} fact(s). See them with `meh archive list`.",
+            result.archived_count
+        ),
+        GcMode::Purge => println!("🗑️  Deleted {} fact(s).", result.deleted_count),
+    }
 
     Ok(())
 }
+
+fn print_expiry(report: &ExpiryReport) {
+    if !report.expired.is_empty() {
+        println!("⏰ {} expired fact(s) to deprecate:", report.expired.len());
+        for fact in &report.expired {
+            println!("  {} {}", fact.id, fact.path);
+        }
+        println!();
+    }
+    if !report.review_due.is_empty() {
+        println!("🔎 {} fact(s) due for review:", report.review_due.len());
+        for fact in &report.review_due {
+            println!("  {} {}", fact.id, fact.path);
+        }
+        println!();
+    }
+}
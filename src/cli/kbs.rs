@@ -510,6 +510,8 @@ async fn add_kb_interactive() -> Result<()> {
         server: server_name,
         slug,
         write,
+        fuzzy: false,
+        trust: 1.0,
     };
 
     config.kbs.kb.push(kb_config);
@@ -0,0 +1,146 @@
+//! `meh links` command
+//!
+//! Shows the wiki-style references between facts: `[[@path/to/fact]]` and
+//! `meh-<ULID>` mentions in content are recorded as links when a fact is
+//! added.
+//!
+//! # Usage
+//! ```bash
+//! meh links @ops/runbook          # Outgoing references and backlinks
+//! meh links --broken              # References to missing/deprecated facts
+//! meh links --broken --json
+//! ```
+
+use anyhow::{bail, Result};
+use clap::Args;
+
+use super::utils::{find_fact, open_storage};
+use crate::core::fact::{reference_label, Fact, Status};
+use crate::core::storage::BrokenReference;
+
+#[derive(Args, Debug)]
+pub struct LinksArgs {
+    /// ID or path of the fact
+    pub target: Option<String>,
+
+    /// List references to facts that don't exist or are deprecated
+    #[arg(long, conflicts_with = "target")]
+    pub broken: bool,
+
+    /// Output as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+pub fn run(args: LinksArgs) -> Result<()> {
+    let storage = open_storage()?;
+
+    if args.broken {
+        let broken = storage.broken_references()?;
+        if args.json {
+            print_broken_json(&broken)?;
+        } else {
+            print_broken(&broken);
+        }
+        return Ok(());
+    }
+
+    let Some(target) = args.target else {
+        bail!("Give a fact ID or path, or use --broken");
+    };
+
+    let fact = find_fact(&storage, &target)?;
+    let references = storage.references_from(&fact.id)?;
+    let backlinks = storage.backlinks(&fact)?;
+
+    if args.json {
+        let json = serde_json::json!({
+            "id": format!("meh-{}", fact.id),
+            "path": fact.path,
+            "references": references.iter().map(|(target, resolved)| serde_json::json!({
+                "target": reference_label(target),
+                "id": resolved.as_ref().map(|f| format!("meh-{}", f.id)),
+                "status": resolved.as_ref().map(|f| f.status.as_str()),
+            })).collect::<Vec<_>>(),
+            "referenced_by": backlinks.iter().map(|f| serde_json::json!({
+                "id": format!("meh-{}", f.id),
+                "path": f.path,
+                "title": f.title,
+            })).collect::<Vec<_>>(),
+        });
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    println!("🔗 Links of {} (meh-{})\n", fact.path, fact.id);
+    print_links(&references, &backlinks);
+
+    Ok(())
+}
+
+/// Outgoing references and backlinks (shared with `meh show`)
+pub fn print_links(references: &[(String, Option<Fact>)], backlinks: &[Fact]) {
+    if references.is_empty() && backlinks.is_empty() {
+        println!("   No links.");
+        return;
+    }
+
+    if !references.is_empty() {
+        println!("   References:");
+        for (target, resolved) in references {
+            match resolved {
+                Some(f) if f.status == Status::Active => {
+                    println!("     → {}  ({})", reference_label(target), f.title)
+                }
+                Some(f) => println!(
+                    "     → {}  ({}, {})",
+                    reference_label(target),
+                    f.title,
+                    f.status.as_str()
+                ),
+                None => println!("     → {}  ❌ not found", reference_label(target)),
+            }
+        }
+    }
+
+    if !backlinks.is_empty() {
+        println!("   Referenced by:");
+        for f in backlinks {
+            println!("     ← {}  ({}, meh-{})", f.path, f.title, f.id);
+        }
+    }
+}
+
+fn print_broken(broken: &[BrokenReference]) {
+    if broken.is_empty() {
+        println!("✨ No broken references.");
+        return;
+    }
+
+    println!("💔 {} broken reference(s):\n", broken.len());
+    for b in broken {
+        println!(
+            "  {} → {}  ({})",
+            b.source_path,
+            reference_label(&b.target),
+            b.reason.as_str()
+        );
+        println!("     in meh-{}", b.source_id);
+    }
+}
+
+fn print_broken_json(broken: &[BrokenReference]) -> Result<()> {
+    let json: Vec<serde_json::Value> = broken
+        .iter()
+        .map(|b| {
+            serde_json::json!({
+                "source_id": format!("meh-{}", b.source_id),
+                "source_path": b.source_path,
+                "target": reference_label(&b.target),
+                "reason": b.reason.as_str(),
+            })
+        })
+        .collect();
+    println!("{}", serde_json::to_string_pretty(&json)?);
+    Ok(())
+}
@@ -0,0 +1,169 @@
+//! `meh merge` command
+//!
+//! Consolidates several overlapping facts into one. The merged fact links
+//! back to every source and supersedes all of them, so old IDs resolve to it.
+//!
+//! # Usage
+//! ```bash
+//! meh merge meh-01HQ3K2JN5 meh-01HQ3K4XYZ --into @ops/deploy   # opens $EDITOR
+//! meh merge meh-01HQ3K2JN5 @ops/release --into @ops/deploy --content "Tag, then make deploy"
+//! meh merge meh-01HQ3K2JN5 meh-01HQ3K4XYZ --into @ops/deploy -f deploy.md
+//! ```
+
+use anyhow::{bail, Context, Result};
+use clap::Args;
+use std::fs;
+use std::path::Path;
+
+use super::utils::{embed_written, find_fact, find_meh_dir, open_storage_at};
+use crate::config::Config;
+use crate::core::fact::{AuthorType, Fact, Status};
+use crate::core::path::Path as FactPath;
+use crate::core::schema::{self, SchemaAction};
+
+#[derive(Args, Debug)]
+pub struct MergeArgs {
+    /// IDs or paths of the facts to merge (at least two)
+    #[arg(required = true, num_args = 2..)]
+    pub targets: Vec<String>,
+
+    /// Path of the merged fact
+    #[arg(long)]
+    pub into: String,
+
+    /// Merged content (default: edit the sources in $EDITOR)
+    #[arg(long)]
+    pub content: Option<String>,
+
+    /// Read content from file
+    #[arg(short = 'f', long)]
+    pub file: Option<String>,
+
+    /// Title of the merged fact (default: first line of the content)
+    #[arg(short, long)]
+    pub title: Option<String>,
+}
+
+pub fn run(args: MergeArgs) -> Result<()> {
+    let meh_dir = find_meh_dir()?;
+    let db_path = meh_dir.join("data.db");
+    let storage = open_storage_at(&db_path)?;
+    let into = FactPath::parse(&args.into)?;
+
+    let mut sources: Vec<Fact> = Vec::new();
+    for target in &args.targets {
+        let fact = find_fact(&storage, target)?;
+        if fact.status != Status::Active {
+            bail!(
+                "meh-{} is {}; only active facts can be merged",
+                fact.id,
+                fact.status.as_str()
+            );
+        }
+        if !sources.iter().any(|s| s.id == fact.id) {
+            sources.push(fact);
+        }
+    }
+    if sources.len() < 2 {
+        bail!("Merge needs at least two distinct facts");
+    }
+
+    let content = if let Some(file_path) = &args.file {
+        fs::read_to_string(file_path)?
+    } else if let Some(content) = &args.content {
+        content.clone()
+    } else {
+        edit_content(&meh_dir, &sources)?
+    };
+    if content.trim().is_empty() {
+        bail!("Merged content is empty, aborting");
+    }
+
+    let mut merged = Fact::merge(&sources, into.to_string(), content);
+    if let Some(title) = args.title {
+        merged.title = title;
+    }
+    merged.author_type = AuthorType::Human;
+    merged.author_id = "cli".to_string();
+    merged.generate_summary(150);
+
+    // Check the schemas for the merged path
+    let config = Config::load()?;
+    let violation = schema::check(&config.schemas, &merged);
+    if let Some(violation) = &violation {
+        match violation.action {
+            SchemaAction::Reject => bail!("{}", violation.explanation()),
+            SchemaAction::Review => merged.status = Status::PendingReview,
+        }
+    }
+
+    // Pending merges supersede their sources on approval
+    storage.insert_merge(&merged)?;
+    embed_written(&storage, &[&merged]);
+
+    if violation.is_some() {
+        println!("⏳ Merged fact added for review: {}", merged.meh_id());
+    } else {
+        println!("✅ Merged fact created: {}", merged.meh_id());
+    }
+    println!("   Path: {}", merged.path);
+    println!("   Title: {}", merged.title);
+    for source in &sources {
+        println!("   Supersedes: meh-{} ({})", source.id, source.path);
+    }
+    if let Some(violation) = violation {
+        eprintln!("⚠️  {}", violation.explanation());
+        eprintln!("   Approve it with `meh pending -i`");
+    }
+
+    Ok(())
+}
+
+/// Let the user write the merged content, starting from the sources
+///
+/// Whole-line HTML comments are instructions and are stripped afterwards.
+fn edit_content(meh_dir: &Path, sources: &[Fact]) -> Result<String> {
+    let mut draft = String::from(
+        "<!-- Write the merged fact. Comment lines are ignored; empty content aborts. -->\n",
+    );
+    for source in sources {
+        draft.push_str(&format!("\n<!-- meh-{} {} -->\n", source.id, source.path));
+        draft.push_str(source.content.trim_end());
+        draft.push('\n');
+    }
+
+    let draft_path = meh_dir.join("MERGE_EDITMSG");
+    fs::write(&draft_path, draft)?;
+
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| {
+            if cfg!(windows) {
+                "notepad".to_string()
+            } else {
+                "vi".to_string()
+            }
+        });
+    let status = std::process::Command::new(&editor)
+        .arg(&draft_path)
+        .status()
+        .with_context(|| format!("Failed to open editor: {}", editor))?;
+    let edited = fs::read_to_string(&draft_path);
+    let _ = fs::remove_file(&draft_path);
+    if !status.success() {
+        bail!("Editor exited with {}, aborting", status);
+    }
+
+    Ok(strip_comments(&edited?))
+}
+
+fn strip_comments(text: &str) -> String {
+    let lines: Vec<&str> = text
+        .lines()
+        .filter(|line| {
+            let line = line.trim();
+            !(line.starts_with("<!--") && line.ends_with("-->"))
+        })
+        .collect();
+    lines.join("\n").trim().to_string()
+}
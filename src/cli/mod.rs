@@ -5,22 +5,34 @@
 use clap::{Parser, Subcommand};
 
 pub mod add;
+pub mod archive;
+pub mod attach;
+pub mod backup;
 pub mod browse;
 pub mod config;
 pub mod context;
 pub mod correct;
+pub mod db;
+pub mod dedupe;
 pub mod deprecate;
 pub mod extend;
+pub mod find;
 pub mod gc;
 pub mod init;
 pub mod kbs;
+pub mod links;
+pub mod merge;
+pub mod namespace;
 pub mod notifications;
 pub mod pending;
+pub mod related;
 pub mod remote_ops;
 pub mod search;
 pub mod show;
 pub mod stats;
+pub mod template;
 pub mod utils;
+pub mod warn;
 
 /// meh - AI Knowledge Management CLI
 ///
@@ -46,6 +58,10 @@ pub struct Cli {
     #[arg(long, global = true, env = "MEH_KB")]
     pub kb: Option<String>,
 
+    /// Namespace to work in (default: core.namespace from config)
+    #[arg(long, global = true, env = "MEH_NAMESPACE")]
+    pub namespace: Option<String>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -64,6 +80,9 @@ pub enum Commands {
     /// Search facts
     Search(search::SearchArgs),
 
+    /// Find facts by path pattern (e.g. '@products/*/api/**')
+    Find(find::FindArgs),
+
     /// List paths (like ls)
     Ls(browse::LsArgs),
 
@@ -73,15 +92,45 @@ pub enum Commands {
     /// Correct a fact (creates superseding fact)
     Correct(correct::CorrectArgs),
 
+    /// Merge several facts into one consolidated fact
+    Merge(merge::MergeArgs),
+
     /// Extend a fact (adds related information)
     Extend(extend::ExtendArgs),
 
+    /// Attach a warning (caveat) to a fact
+    Warn(warn::WarnArgs),
+
+    /// Show references between facts ([[@path]] / meh-ID mentions)
+    Links(links::LinksArgs),
+
+    /// List facts related to a fact (links, tags, path, similar text)
+    Related(related::RelatedArgs),
+
     /// Deprecate a fact
     Deprecate(deprecate::DeprecateArgs),
 
+    /// Attach a file to a fact
+    Attach(attach::AttachArgs),
+
+    /// List, extract or remove attachments
+    Attachment(attach::AttachmentArgs),
+
     /// Garbage collect old deprecated/superseded facts
     Gc(gc::GcArgs),
 
+    /// List or restore facts archived by gc
+    Archive(archive::ArchiveArgs),
+
+    /// Find facts with duplicate content
+    Dedupe(dedupe::DedupeArgs),
+
+    /// List namespaces or move facts between them
+    Namespace(namespace::NamespaceArgs),
+
+    /// Show per-path fact schemas and templates
+    Template(template::TemplateArgs),
+
     /// Get or set configuration
     Config(config::ConfigArgs),
 
@@ -102,6 +151,18 @@ pub enum Commands {
 
     /// Manage remote knowledge bases
     Kbs(kbs::KbsArgs),
+
+    /// Database maintenance (schema migrations)
+    Db(db::DbArgs),
+
+    /// Rebuild search indexes (same as `meh db reindex`)
+    Reindex(db::ReindexArgs),
+
+    /// Snapshot all databases (safe while the MCP server runs)
+    Backup(backup::BackupArgs),
+
+    /// Restore databases from a snapshot
+    Restore(backup::RestoreArgs),
 }
 
 pub mod serve;
@@ -0,0 +1,125 @@
+//! Namespace CLI commands
+//!
+//! One data.db can hold several isolated namespaces (e.g. one per
+//! sub-project of a monorepo). Every command works in the active namespace:
+//! `--namespace`, `MEH_NAMESPACE` or `core.namespace` in config.
+//!
+//! # Usage
+//! ```bash
+//! meh namespace list                        # Namespaces with fact counts
+//! meh namespace move @billing billing       # Move facts under a path
+//! meh --namespace billing search "invoice"  # Work in another namespace
+//! meh search "deploy" --all-namespaces      # Opt-in cross-namespace search
+//! ```
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use crate::config::Config;
+use crate::core::fact::{namespace_label, parse_namespace};
+use crate::core::storage::Storage;
+
+#[derive(Args, Debug)]
+pub struct NamespaceArgs {
+    #[command(subcommand)]
+    pub command: NamespaceCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum NamespaceCommands {
+    /// List namespaces and their fact counts
+    List {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Move facts at or under a path from the active namespace to another
+    Move {
+        /// Path (facts at it and below it are moved)
+        path: String,
+
+        /// Target namespace ("default" for the default namespace)
+        to: String,
+    },
+}
+
+pub fn execute(args: NamespaceArgs, config: &Config) -> Result<()> {
+    let db_path = config.data_dir();
+    if !db_path.exists() {
+        println!("No database found. Run 'meh init' first.");
+        return Ok(());
+    }
+    let storage = Storage::open(&db_path)?.with_namespace(config.namespace()?);
+
+    match args.command {
+        NamespaceCommands::List { json } => list(&storage, json),
+        NamespaceCommands::Move { path, to } => move_facts(&storage, &path, &to),
+    }
+}
+
+fn list(storage: &Storage, json: bool) -> Result<()> {
+    let namespaces = storage.namespaces()?;
+
+    if json {
+        let json: Vec<_> = namespaces
+            .iter()
+            .map(|ns| {
+                serde_json::json!({
+                    "namespace": namespace_label(&ns.name),
+                    "active": ns.name == storage.namespace(),
+                    "total_facts": ns.total_facts,
+                    "active_facts": ns.active_facts,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    if namespaces.is_empty() {
+        println!("No facts yet.");
+        return Ok(());
+    }
+
+    println!("🗂️  Namespaces:\n");
+    for ns in &namespaces {
+        let marker = if ns.name == storage.namespace() {
+            "▶ "
+        } else {
+            "  "
+        };
+        println!(
+            "{}{:<24} {} active / {} total",
+            marker,
+            namespace_label(&ns.name),
+            ns.active_facts,
+            ns.total_facts
+        );
+    }
+
+    Ok(())
+}
+
+fn move_facts(storage: &Storage, path: &str, to: &str) -> Result<()> {
+    let to = parse_namespace(to)?;
+    let moved = storage.move_to_namespace(path, &to)?;
+
+    if moved == 0 {
+        println!(
+            "No facts at or under {} in namespace '{}'.",
+            path,
+            namespace_label(storage.namespace())
+        );
+    } else {
+        println!(
+            "✅ Moved {} fact(s) under {} from '{}' to '{}'",
+            moved,
+            path,
+            namespace_label(storage.namespace()),
+            namespace_label(&to)
+        );
+    }
+
+    Ok(())
+}
@@ -65,7 +65,7 @@ pub fn execute(args: PendingArgs, config: &Config) -> Result<()> {
 
     // Open storage if exists
     let storage = if db_path.exists() {
-        Some(Storage::open(&db_path)?)
+        Some(Storage::open(&db_path)?.with_namespace(config.namespace()?))
     } else {
         None
     };
@@ -255,9 +255,6 @@ fn run_interactive_review(
                 match &items[idx] {
                     PendingItem::Local(fact) => {
                         if let Some(storage) = storage {
-                            if let Some(ref supersedes_id) = fact.supersedes {
-                                storage.mark_superseded(supersedes_id)?;
-                            }
                             storage.approve_fact(&fact.id)?;
                         }
                     }
@@ -311,9 +308,6 @@ fn run_interactive_review(
                     match item {
                         PendingItem::Local(fact) => {
                             if let Some(storage) = storage {
-                                if let Some(ref supersedes_id) = fact.supersedes {
-                                    let _ = storage.mark_superseded(supersedes_id);
-                                }
                                 let _ = storage.approve_fact(&fact.id);
                             }
                         }
@@ -452,11 +446,7 @@ fn approve_one(
         let id = parse_meh_id(id_str)?;
 
         if let Some(fact) = storage.get_by_id(&id)? {
-            // If it's a correction, also mark original as superseded
-            if let Some(ref supersedes_id) = fact.supersedes {
-                storage.mark_superseded(supersedes_id)?;
-            }
-
+            // Corrections also supersede their original (same transaction)
             storage.approve_fact(&id)?;
             println!("✅ Approved: {} {}", format_meh_id(&id), fact.path);
         } else {
@@ -545,10 +535,6 @@ fn approve_all(
     // Approve local facts
     if let Some(storage) = storage {
         for fact in &local_pending {
-            // Handle corrections - mark original as superseded
-            if let Some(ref supersedes_id) = fact.supersedes {
-                storage.mark_superseded(supersedes_id)?;
-            }
             storage.approve_fact(&fact.id)?;
             println!("✅ {}", fact.path);
         }
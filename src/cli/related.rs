@@ -0,0 +1,65 @@
+//! `meh related` command
+//!
+//! "More like this": other facts ranked by links, shared tags, path
+//! proximity and similar text (see `core::related`).
+//!
+//! # Usage
+//! ```bash
+//! meh related @api/auth/tokens
+//! meh related meh-01HQ... --limit 5 --json
+//! ```
+
+use anyhow::Result;
+use clap::Args;
+
+use super::utils::{find_fact, open_storage};
+use crate::core::related::related;
+
+#[derive(Args, Debug)]
+pub struct RelatedArgs {
+    /// ID or path of the fact
+    pub target: String,
+
+    /// Maximum results
+    #[arg(short, long, default_value = "10")]
+    pub limit: usize,
+
+    /// Output as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+pub fn run(args: RelatedArgs) -> Result<()> {
+    let storage = open_storage()?;
+    let fact = find_fact(&storage, &args.target)?;
+    let related = related(&storage, &fact, args.limit, false)?;
+
+    if args.json {
+        let json = serde_json::json!({
+            "id": format!("meh-{}", fact.id),
+            "path": fact.path,
+            "related": related.iter().map(|r| serde_json::json!({
+                "id": format!("meh-{}", r.fact.id),
+                "path": r.fact.path,
+                "title": r.fact.title,
+                "score": r.score,
+                "reasons": r.reasons,
+            })).collect::<Vec<_>>(),
+        });
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    if related.is_empty() {
+        println!("No related facts for {}", fact.path);
+        return Ok(());
+    }
+
+    println!("🧭 Related to {} (meh-{})\n", fact.path, fact.id);
+    for r in &related {
+        println!("  {}  (score {:.2})", r.fact.path, r.score);
+        println!("     {} | {}", r.fact.title, r.reasons.join("; "));
+    }
+
+    Ok(())
+}
@@ -7,27 +7,44 @@
 //! meh search "api timeout"
 //! meh search "timeout" --path "@products/"
 //! meh search --tags critical,api "error"
-//! meh search "@products/*/api/timeout"   # Wildcard path search
+//! meh search "crash" --prop severity=high
+//! meh search "staging" --include-expired
+//! meh search "timeout" --not-tags legacy --include-deprecated
+//! meh search "deploy" --token-budget 2000   # Stop once the budget is used
+//! meh search "deploy" --all-namespaces   # Every namespace of the database
+//! meh search 'timeout tag:api path:@products/** -deprecated "connection reset"'
+//! meh search "deploy trust>0.6 author:human"
+//! meh search "postgress" --fuzzy   # Typo-tolerant (KB needs fuzzy = true)
+//! meh search "why do logins fail" --semantic   # Keywords + embeddings
 //! meh search "query" --server http://localhost:3000 --kb my-kb  # Remote
+//! meh search "timeout" --all-kbs   # Every KB in search_order, one ranking
 //! ```
 //!
 //! # Architecture
 //! - Uses FTS5 with BM25 ranking (local)
 //! - Or HTTP API (remote)
 //! - Uses KnowledgeBase abstraction for unified access
-//! - Returns L2 Summary level by default
+//! - Returns L2 Summary level by default, with the words around the match
+//!   highlighted (`[search] snippet_words`)
 //! - See `../../plan/ANALYSIS_AUTO_CONTEXT_SEARCH.md`
 
+use std::collections::{HashMap, HashSet};
+
 use anyhow::Result;
 use clap::Args;
+use colored::Colorize;
 
 use super::show::DetailLevel;
-use crate::core::fact::Fact;
-use crate::core::kb::{KnowledgeBase, KnowledgeBaseBackend};
+use crate::core::embed;
+use crate::core::fact::{namespace_label, parse_property, Fact};
+use crate::core::federated::{self, FederatedHit};
+use crate::core::kb::KnowledgeBase;
+use crate::core::search::{self, render_snippet, SearchQuery};
 
 #[derive(Args, Debug)]
 pub struct SearchArgs {
-    /// Search query (text or path pattern with wildcards)
+    /// Search query: words, "phrases", +required, -excluded, tag:x,
+    /// path:@a/**, trust>0.6, author:human, prop:key=value
     pub query: String,
 
     /// Filter by path prefix
@@ -38,6 +55,18 @@ pub struct SearchArgs {
     #[arg(short, long, value_delimiter = ',')]
     pub tags: Option<Vec<String>>,
 
+    /// Filter by property (key=value, repeatable, AND logic)
+    #[arg(long = "prop", value_name = "KEY=VALUE", value_parser = parse_property)]
+    pub props: Vec<(String, String)>,
+
+    /// Include facts past their expiry date
+    #[arg(long)]
+    pub include_expired: bool,
+
+    /// Search every namespace, not just the active one (local KBs only)
+    #[arg(long)]
+    pub all_namespaces: bool,
+
     /// Exclude tags (comma-separated)
     #[arg(long, value_delimiter = ',')]
     pub not_tags: Option<Vec<String>>,
@@ -50,10 +79,29 @@ pub struct SearchArgs {
     #[arg(long)]
     pub min_trust: Option<f32>,
 
-    /// Only show active (non-deprecated) facts
+    /// Only show active (non-deprecated) facts (the default)
     #[arg(long)]
     pub active_only: bool,
 
+    /// Include deprecated facts
+    #[arg(long, conflicts_with = "active_only")]
+    pub include_deprecated: bool,
+
+    /// Also match misspelled or partial words (KB needs `fuzzy = true`;
+    /// used automatically when few facts match)
+    #[arg(long)]
+    pub fuzzy: bool,
+
+    /// Rank by meaning as well as keywords (embedder from
+    /// `[search.embeddings]`, local KBs only)
+    #[arg(long)]
+    pub semantic: bool,
+
+    /// Search every KB in `search_order` at once and merge the results
+    /// into one ranking (per-KB timeout: `federated_timeout_secs`)
+    #[arg(long)]
+    pub all_kbs: bool,
+
     /// Maximum results
     #[arg(short, long, default_value = "20")]
     pub limit: usize,
@@ -80,52 +128,193 @@ pub struct SearchArgs {
 }
 
 pub async fn run(args: SearchArgs) -> Result<()> {
-    // Load config and create KnowledgeBase (local or remote)
     let config = crate::config::Config::load()?;
+
+    // Flags add to what the query itself says (`tag:`, `path:`, ...)
+    let mut query = SearchQuery::parse(&args.query)?.with_limit(args.limit);
+    query.tags.extend(args.tags.clone().unwrap_or_default());
+    query
+        .not_tags
+        .extend(args.not_tags.clone().unwrap_or_default());
+    query.properties.extend(args.props.clone());
+    if args.path.is_some() {
+        query.path_prefix = args.path.clone();
+    }
+    if args.min_trust.is_some() {
+        query.min_trust = args.min_trust;
+    }
+    query.include_deprecated = args.include_deprecated;
+    query.include_expired = args.include_expired;
+    query.all_namespaces = args.all_namespaces;
+    query.token_budget = args.token_budget;
+    query.fuzzy = args.fuzzy;
+    query.snippet_words = Some(config.search.snippet_words);
+    if args.semantic {
+        query.embedder = Some(embed::from_config(&config.search.embeddings)?);
+    }
+
+    if args.all_kbs {
+        return run_all_kbs(&args, config, query).await;
+    }
+
+    // Create KnowledgeBase (local or remote)
     let kb = KnowledgeBase::from_args(args.server.as_deref(), args.kb.as_deref(), &config)?;
 
-    // Execute search using unified abstraction
-    let facts = kb.search(&args.query, args.limit).await?;
-
-    // Apply additional filters (not supported by all backends yet)
-    let facts: Vec<_> = facts
-        .into_iter()
-        .filter(|f| {
-            // Path prefix filter
-            if let Some(prefix) = &args.path {
-                if !f.path.starts_with(prefix.trim_end_matches('/')) {
-                    return false;
+    // Local KBs filter in SQL; remote KBs filter what the server returns
+    let mut fuzzy = HashSet::new();
+    let mut snippets = HashMap::new();
+    let facts: Vec<Fact> = match &kb {
+        KnowledgeBase::Local(local) => local
+            .search_query(&query)?
+            .into_iter()
+            .map(|r| {
+                if r.fuzzy {
+                    fuzzy.insert(r.fact.id);
                 }
-            }
-            // Tags filter
-            if let Some(required_tags) = &args.tags {
-                for tag in required_tags {
-                    if !f.tags.contains(tag) {
-                        return false;
-                    }
-                }
-            }
-            // Min trust filter
-            if let Some(min) = args.min_trust {
-                if f.trust_score < min {
-                    return false;
+                if let Some(snippet) = r.snippet {
+                    snippets.insert(r.fact.id, snippet);
                 }
+                r.fact
+            })
+            .collect(),
+        KnowledgeBase::Remote(remote) => remote.search_query(&query).await?,
+    };
+
+    // Warnings are shown under their target (kept locally); each one
+    // lowers the shown trust
+    let mut facts = search::fold_warnings(facts);
+    let mut warnings = HashMap::new();
+    if let KnowledgeBase::Local(local) = &kb {
+        let trust = config.trust.calculator();
+        for fact in &mut facts {
+            let attached = local.warnings(&fact.id)?;
+            if !attached.is_empty() {
+                fact.trust_score = trust.apply_warning_penalty(fact.trust_score, attached.len());
+                warnings.insert(fact.id, attached);
             }
-            true
-        })
-        .collect();
+        }
+    }
+
+    let ids: Vec<_> = facts.iter().map(|f| f.id).collect();
+    kb.record_usage_best_effort(&ids).await;
 
     // Output results
     match args.format.as_str() {
         "json" => print_json(&facts)?,
         "compact" => print_compact(&facts),
-        _ => print_pretty(&facts, &args.level),
+        _ => print_pretty(
+            &facts,
+            &args.level,
+            args.all_namespaces,
+            &fuzzy,
+            &snippets,
+            &warnings,
+        ),
     }
 
     Ok(())
 }
 
-fn print_pretty(facts: &[Fact], level: &DetailLevel) {
+/// `--all-kbs`: the query (its limit per KB) against every KB in
+/// `search_order`, merged into one ranked list
+async fn run_all_kbs(
+    args: &SearchArgs,
+    config: crate::config::Config,
+    query: SearchQuery,
+) -> Result<()> {
+    let kbs = config.kbs.search_order.clone();
+    if kbs.is_empty() {
+        anyhow::bail!("No KBs configured in search_order");
+    }
+
+    let limit = args.limit;
+    let namespace = config.namespace()?;
+    let results = tokio::task::spawn_blocking(move || {
+        federated::search(&config, &kbs, &query, limit, &namespace)
+    })
+    .await?;
+
+    match args.format.as_str() {
+        "json" => {
+            let hits: Vec<_> = results
+                .hits
+                .iter()
+                .map(|h| {
+                    serde_json::json!({
+                        "kb": h.kb,
+                        "score": h.score,
+                        "also_in": h.also_in,
+                        "fact": h.fact,
+                    })
+                })
+                .collect();
+            let json = serde_json::json!({ "results": hits, "errors": results.errors });
+            println!("{}", serde_json::to_string_pretty(&json)?);
+        }
+        "compact" => {
+            for hit in &results.hits {
+                println!(
+                    "{}\t{}\t{}\t{:.2}",
+                    hit.kb, hit.fact.path, hit.fact.title, hit.score
+                );
+            }
+        }
+        _ => print_federated(&results.hits, &args.level),
+    }
+
+    // Failing KBs do not fail the search; the others still answered
+    for e in &results.errors {
+        eprintln!("⚠️  {}", e);
+    }
+    Ok(())
+}
+
+fn print_federated(hits: &[FederatedHit], level: &DetailLevel) {
+    if hits.is_empty() {
+        println!("No results found.");
+        return;
+    }
+
+    println!("\n📚 Found {} result(s):\n", hits.len());
+
+    for (i, hit) in hits.iter().enumerate() {
+        let fact = &hit.fact;
+        let mut kb = format!("[{}]", hit.kb);
+        if !hit.also_in.is_empty() {
+            kb.push_str(&format!(" (also in {})", hit.also_in.join(", ")));
+        }
+        println!("{}. {} {}", i + 1, kb, fact.path);
+        match level {
+            DetailLevel::Catalog => {}
+            DetailLevel::Index => {
+                println!("   Score: {:.2} | {}", hit.score, fact.title);
+            }
+            DetailLevel::Summary | DetailLevel::Full => {
+                println!(
+                    "   Score: {:.2} | Trust: {:.2} | {}",
+                    hit.score, fact.trust_score, fact.title
+                );
+                if let Some(snippet) = &hit.snippet {
+                    let line = render_snippet(snippet, |w| w.yellow().bold().to_string());
+                    println!("   {}\n", line);
+                } else {
+                    let summary = fact.summary.as_deref().unwrap_or(&fact.content);
+                    let preview: String = summary.chars().take(100).collect();
+                    println!("   {}\n", preview);
+                }
+            }
+        }
+    }
+}
+
+fn print_pretty(
+    facts: &[Fact],
+    level: &DetailLevel,
+    show_namespace: bool,
+    fuzzy: &HashSet<ulid::Ulid>,
+    snippets: &HashMap<ulid::Ulid, String>,
+    warnings: &HashMap<ulid::Ulid, Vec<Fact>>,
+) {
     if facts.is_empty() {
         println!("No results found.");
         return;
@@ -134,16 +323,28 @@ fn print_pretty(facts: &[Fact], level: &DetailLevel) {
     println!("\n📚 Found {} result(s):\n", facts.len());
 
     for (i, fact) in facts.iter().enumerate() {
+        let mut path = if show_namespace {
+            format!("[{}] {}", namespace_label(&fact.namespace), fact.path)
+        } else {
+            fact.path.clone()
+        };
+        if fuzzy.contains(&fact.id) {
+            path.push_str("  ≈ fuzzy");
+        }
         match level {
             DetailLevel::Catalog => {
-                println!("{}. {}", i + 1, fact.path);
+                println!("{}. {}", i + 1, path);
             }
             DetailLevel::Index => {
-                println!("{}. {}", i + 1, fact.path);
+                println!("{}. {}", i + 1, path);
                 println!("   Trust: {:.2} | {}", fact.trust_score, fact.title);
             }
             DetailLevel::Summary | DetailLevel::Full => {
-                println!("{}. {}", i + 1, fact.path);
+                if fact.is_expired(chrono::Utc::now()) {
+                    println!("{}. {}  ⏰ expired", i + 1, path);
+                } else {
+                    println!("{}. {}", i + 1, path);
+                }
                 println!(
                     "   Trust: {:.2} | Tags: {}",
                     fact.trust_score,
@@ -153,7 +354,17 @@ fn print_pretty(facts: &[Fact], level: &DetailLevel) {
                         fact.tags.join(", ")
                     }
                 );
-                if let Some(summary) = &fact.summary {
+                for warning in warnings.get(&fact.id).into_iter().flatten() {
+                    println!(
+                        "   {} {}",
+                        "⚠️  WARNING:".yellow().bold(),
+                        warning.content.trim().yellow()
+                    );
+                }
+                if let Some(snippet) = snippets.get(&fact.id) {
+                    let line = render_snippet(snippet, |w| w.yellow().bold().to_string());
+                    println!("   {}\n", line);
+                } else if let Some(summary) = &fact.summary {
                     println!("   {}\n", summary);
                 } else {
                     // Use first 100 chars as summary
@@ -8,9 +8,12 @@
 //! meh show meh-01HQ3K2JN5
 //! meh show @products/alpha/api/timeout --level summary
 //! meh show meh-01HQ3K2JN5 --with-history
+//! meh show meh-01HQ3K2JN5 --attachments
 //! meh show @products/alpha --server http://localhost:3000 --kb my-kb
 //! ```
 //!
+//! Warnings attached with `meh warn` are printed in yellow after the fact.
+//!
 //! # Detail Levels (from DECISIONS_UNIFIED.md)
 //! - L0 Catalog: just path
 //! - L1 Index: path + title + trust
@@ -19,7 +22,10 @@
 
 use anyhow::{bail, Result};
 use clap::{Args, ValueEnum};
+use colored::Colorize;
 
+use super::links::print_links;
+use crate::core::attachment::{format_size, Attachment};
 use crate::core::fact::Fact;
 use crate::core::kb::{KnowledgeBase, KnowledgeBaseBackend};
 
@@ -49,6 +55,10 @@ pub struct ShowArgs {
     #[arg(long)]
     pub with_history: bool,
 
+    /// List attached files
+    #[arg(long)]
+    pub attachments: bool,
+
     /// Output format
     #[arg(short, long, default_value = "pretty")]
     pub format: String,
@@ -70,28 +80,56 @@ pub async fn run(args: ShowArgs) -> Result<()> {
     // Get fact by path or ID using unified abstraction
     let fact = kb.get_fact(&args.target).await?;
 
-    let fact = match fact {
+    let mut fact = match fact {
         Some(f) => f,
         None => bail!("Fact not found: {}", args.target),
     };
 
+    // Warnings are kept locally; each one lowers the shown trust
+    let warnings = match &kb {
+        KnowledgeBase::Local(local) => local.warnings(&fact.id)?,
+        KnowledgeBase::Remote(_) => Vec::new(),
+    };
+    fact.trust_score = config
+        .trust
+        .calculator()
+        .apply_warning_penalty(fact.trust_score, warnings.len());
+
+    kb.record_usage_best_effort(&[fact.id]).await;
+
     // Format output based on level
     match args.format.as_str() {
         "json" => print_json(&fact, &args.level)?,
-        _ => print_pretty(&fact, &args.level),
+        _ => {
+            print_pretty(&fact, &args.level);
+            print_warnings(&warnings);
+            if let KnowledgeBase::Local(local) = &kb {
+                let references = local.references(&fact.id)?;
+                let backlinks = local.backlinks(&fact)?;
+                if !references.is_empty() || !backlinks.is_empty() {
+                    println!("\n🔗 Links:");
+                    print_links(&references, &backlinks);
+                }
+            }
+        }
+    }
+
+    if args.attachments {
+        match &kb {
+            KnowledgeBase::Local(local) => print_attachments(&local.attachments(&fact.id)?),
+            KnowledgeBase::Remote(_) => {
+                println!("\n⚠️  Attachments not available for remote knowledge bases.");
+            }
+        }
     }
 
     // Note: --with-history only works for local KB
     if args.with_history {
-        if matches!(kb, KnowledgeBase::Remote(_)) {
-            println!("\n⚠️  History chain not available for remote knowledge bases.");
-        } else {
-            // For local, we'd need to access storage directly
-            // This is a limitation of the current abstraction
-            println!(
-                "\n⚠️  Use local access for history: meh show {} --with-history",
-                args.target
-            );
+        match &kb {
+            KnowledgeBase::Local(local) => print_history(&local.history_chain(&fact.id)?, &fact),
+            KnowledgeBase::Remote(_) => {
+                println!("\n⚠️  History chain not available for remote knowledge bases.");
+            }
         }
     }
 
@@ -124,10 +162,46 @@ fn print_pretty(fact: &Fact, level: &DetailLevel) {
             println!("Title:   {}", fact.title);
             println!("Trust:   {}", format_trust(fact.trust_score));
             println!("Status:  {:?}", fact.status);
+            if !fact.namespace.is_empty() {
+                println!("NS:      {}", fact.namespace);
+            }
             if !fact.tags.is_empty() {
                 println!("Tags:    {}", fact.tags.join(", "));
             }
+            if !fact.properties.is_empty() {
+                let props: Vec<String> = fact
+                    .properties
+                    .iter()
+                    .map(|(k, v)| format!("{}={}", k, v))
+                    .collect();
+                println!("Props:   {}", props.join(", "));
+            }
             println!("Created: {}", fact.created_at);
+            let now = chrono::Utc::now();
+            if let Some(until) = fact.valid_until {
+                let flag = if fact.is_expired(now) {
+                    "  ⏰ expired"
+                } else {
+                    ""
+                };
+                println!("Expires: {}{}", until, flag);
+            }
+            if let Some(by) = fact.review_by {
+                let flag = if fact.is_review_due(now) {
+                    "  🔎 review due"
+                } else {
+                    ""
+                };
+                println!("Review:  {}{}", by, flag);
+            }
+            if !fact.merged_from.is_empty() {
+                let sources: Vec<String> = fact
+                    .merged_from
+                    .iter()
+                    .map(|id| format!("meh-{}", id))
+                    .collect();
+                println!("Merged:  {}", sources.join(", "));
+            }
             println!();
             println!("{}", fact.content);
             println!("─────────────────────────────────────────");
@@ -135,6 +209,61 @@ fn print_pretty(fact: &Fact, level: &DetailLevel) {
     }
 }
 
+fn print_warnings(warnings: &[Fact]) {
+    for warning in warnings {
+        println!(
+            "{} {}",
+            "⚠️  WARNING:".yellow().bold(),
+            warning.content.trim().yellow()
+        );
+        println!(
+            "   {}",
+            format!(
+                "meh-{} · {}",
+                warning.id,
+                warning.created_at.format("%Y-%m-%d")
+            )
+            .dimmed()
+        );
+    }
+}
+
+fn print_attachments(attachments: &[Attachment]) {
+    if attachments.is_empty() {
+        println!("\n📎 No attachments.");
+        return;
+    }
+
+    println!("\n📎 Attachments ({}):", attachments.len());
+    for a in attachments {
+        println!("   {}  ({}, {})", a.name, a.mime_type, format_size(a.size));
+    }
+    println!(
+        "   Extract with: meh attachment get meh-{} <name>",
+        attachments[0].fact_id
+    );
+}
+
+/// Print the supersede chain (archived versions included)
+fn print_history(chain: &[Fact], current: &Fact) {
+    if chain.len() <= 1 {
+        println!("\n📜 No history (this is the only version).");
+        return;
+    }
+
+    println!("\n📜 History ({} versions, oldest first):", chain.len());
+    for fact in chain {
+        let marker = if fact.id == current.id { "→" } else { " " };
+        println!(
+            " {} meh-{}  {}  [{}]",
+            marker,
+            fact.id,
+            fact.created_at.format("%Y-%m-%d %H:%M"),
+            fact.status.as_str()
+        );
+    }
+}
+
 fn print_json(fact: &Fact, _level: &DetailLevel) -> Result<()> {
     let json = serde_json::to_string_pretty(fact)?;
     println!("{}", json);
@@ -3,7 +3,8 @@
 use clap::Args;
 
 use crate::config::Config;
-use crate::core::storage::Storage;
+use crate::core::fact::namespace_label;
+use crate::core::storage::{FactUsage, Storage};
 
 /// Stats command arguments
 #[derive(Args, Debug)]
@@ -11,55 +12,66 @@ pub struct StatsArgs {
     /// Output as JSON
     #[arg(long)]
     pub json: bool,
+
+    /// List the most-read and never-read active facts
+    #[arg(long)]
+    pub usage: bool,
+
+    /// Number of facts per usage list
+    #[arg(long, default_value = "10")]
+    pub limit: i64,
 }
 
 /// Execute stats command
 pub fn execute(args: StatsArgs) -> anyhow::Result<()> {
     let config = Config::load()?;
     let db_path = config.data_dir();
-    let storage = Storage::open(&db_path)?;
+    let storage = Storage::open(&db_path)?.with_namespace(config.namespace()?);
+
+    if args.usage {
+        return print_usage(&storage, args.limit, args.json);
+    }
 
     let stats = storage.stats()?;
 
     if args.json {
         let json = serde_json::json!({
+            "namespace": namespace_label(storage.namespace()),
             "total_facts": stats.total_facts,
             "active_facts": stats.active_facts,
             "deprecated_facts": stats.deprecated_facts,
-            "superseded_facts": stats.total_facts - stats.active_facts - stats.deprecated_facts,
+            "superseded_facts": stats.superseded_facts,
+            "archived_facts": stats.archived_facts,
+            "pending_review_facts": stats.pending_review_facts,
         });
         println!("{}", serde_json::to_string_pretty(&json)?);
     } else {
         println!("📊 Knowledge Base Statistics\n");
+        if !storage.namespace().is_empty() {
+            println!("  Namespace:        {}", storage.namespace());
+        }
         println!("  Total facts:      {}", stats.total_facts);
-        println!(
-            "  ├── Active:       {} ({}%)",
-            stats.active_facts,
-            if stats.total_facts > 0 {
-                stats.active_facts * 100 / stats.total_facts
-            } else {
-                0
-            }
-        );
-        println!(
-            "  ├── Deprecated:   {} ({}%)",
-            stats.deprecated_facts,
-            if stats.total_facts > 0 {
-                stats.deprecated_facts * 100 / stats.total_facts
+        let rows = [
+            ("Active:", stats.active_facts),
+            ("Deprecated:", stats.deprecated_facts),
+            ("Superseded:", stats.superseded_facts),
+            ("Archived:", stats.archived_facts),
+            ("Pending review:", stats.pending_review_facts),
+        ];
+        for (i, (label, count)) in rows.iter().enumerate() {
+            let branch = if i + 1 == rows.len() {
+                "└──"
             } else {
-                0
-            }
-        );
-        let superseded = stats.total_facts - stats.active_facts - stats.deprecated_facts;
-        println!(
-            "  └── Superseded:   {} ({}%)",
-            superseded,
-            if stats.total_facts > 0 {
-                superseded * 100 / stats.total_facts
-            } else {
-                0
-            }
-        );
+                "├──"
+            };
+            println!(
+                "  {} {:<16}{} ({}%)",
+                branch,
+                label,
+                count,
+                (count * 100).checked_div(stats.total_facts).unwrap_or(0)
+            );
+        }
 
         // Get top paths
         if let Ok(paths) = storage.list_children_all("@") {
@@ -77,3 +89,59 @@ pub fn execute(args: StatsArgs) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Print the most-read and never-read facts
+fn print_usage(storage: &Storage, limit: i64, json: bool) -> anyhow::Result<()> {
+    let most = storage.most_accessed(limit)?;
+    let never = storage.never_accessed(limit)?;
+
+    if json {
+        let to_json = |usage: &[FactUsage]| -> Vec<serde_json::Value> {
+            usage
+                .iter()
+                .map(|u| {
+                    serde_json::json!({
+                        "id": format!("meh-{}", u.id),
+                        "path": u.path,
+                        "title": u.title,
+                        "access_count": u.access_count,
+                        "last_accessed_at": u.last_accessed_at,
+                    })
+                })
+                .collect()
+        };
+        let json = serde_json::json!({
+            "most_read": to_json(&most),
+            "never_read": to_json(&never),
+        });
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    println!("🔥 Most-read facts\n");
+    if most.is_empty() {
+        println!("  (no reads recorded yet)");
+    }
+    for u in &most {
+        println!(
+            "  {:>5}×  {}  {} (last {})",
+            u.access_count,
+            u.path,
+            u.title,
+            u.last_accessed_at
+                .as_deref()
+                .and_then(|s| s.get(..10))
+                .unwrap_or("-")
+        );
+    }
+
+    println!("\n💤 Never-read facts\n");
+    if never.is_empty() {
+        println!("  (every active fact has been read)");
+    }
+    for u in &never {
+        println!("  {}  {} (meh-{})", u.path, u.title, u.id);
+    }
+
+    Ok(())
+}
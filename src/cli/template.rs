@@ -0,0 +1,109 @@
+//! `meh template` commands
+//!
+//! Shows the per-path schemas from config (`[[schemas]]`) and the skeleton
+//! a fact under a path has to follow.
+//!
+//! # Usage
+//! ```bash
+//! meh template list                          # Configured schemas
+//! meh template show @project/bugs            # Skeleton to fill in
+//! meh template show @project/bugs > bug.md   # Requirements go to stderr
+//! ```
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use crate::config::Config;
+use crate::core::path::Path;
+use crate::core::schema::{self, FactSchema, SchemaAction};
+
+#[derive(Args, Debug)]
+pub struct TemplateArgs {
+    #[command(subcommand)]
+    pub command: TemplateCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TemplateCommands {
+    /// List configured schemas
+    List,
+
+    /// Print the skeleton for facts at a path
+    Show {
+        /// Path of the fact to write (e.g. @project/bugs)
+        path: String,
+    },
+}
+
+pub fn run(args: TemplateArgs, config: &Config) -> Result<()> {
+    match args.command {
+        TemplateCommands::List => list(&config.schemas),
+        TemplateCommands::Show { path } => show(&config.schemas, &path),
+    }
+}
+
+fn list(schemas: &[FactSchema]) -> Result<()> {
+    if schemas.is_empty() {
+        println!("No schemas configured. Add [[schemas]] to config.toml.");
+        return Ok(());
+    }
+
+    println!("📐 Schemas:\n");
+    for s in schemas {
+        let action = match s.on_violation {
+            SchemaAction::Reject => "reject",
+            SchemaAction::Review => "review",
+        };
+        println!("  {}  (on violation: {})", s.pattern, action);
+        for line in requirements(s) {
+            println!("    {}", line);
+        }
+    }
+
+    Ok(())
+}
+
+fn show(schemas: &[FactSchema], path: &str) -> Result<()> {
+    let path = Path::parse(path)?;
+    let applicable = schema::matching(schemas, &path.to_string());
+    if applicable.is_empty() {
+        eprintln!(
+            "ℹ️  No schema applies to {}; any content is accepted.",
+            path
+        );
+    } else {
+        for s in &applicable {
+            eprintln!("📐 {}", s.pattern);
+            for line in requirements(s) {
+                eprintln!("   {}", line);
+            }
+        }
+        eprintln!();
+    }
+
+    print!("{}", schema::template(&applicable));
+    Ok(())
+}
+
+/// What a schema requires besides its sections, one line each
+fn requirements(s: &FactSchema) -> Vec<String> {
+    let mut lines = Vec::new();
+    if !s.sections.is_empty() {
+        lines.push(format!("Sections:   {}", s.sections.join(", ")));
+    }
+    if !s.properties.is_empty() {
+        let props: Vec<String> = s
+            .properties
+            .iter()
+            .map(|k| format!("--prop {}=...", k))
+            .collect();
+        lines.push(format!("Properties: {}", props.join(" ")));
+    }
+    if !s.tags.is_empty() {
+        lines.push(format!("Tags:       {}", s.tags.join(", ")));
+    }
+    if let Some(max) = s.max_length {
+        lines.push(format!("Max length: {} characters", max));
+    }
+    lines
+}
@@ -3,12 +3,15 @@
 //! Common helper functions shared across CLI commands.
 //! This module reduces code duplication by centralizing:
 //! - Repository discovery (find_meh_dir)
+//! - Opening storage in the active namespace (open_storage)
 //! - Fact lookup by ID or path (find_fact)
 
 use anyhow::{bail, Result};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use ulid::Ulid;
 
+use crate::config::Config;
+use crate::core::embed;
 use crate::core::fact::Fact;
 use crate::core::storage::Storage;
 
@@ -45,7 +48,30 @@ pub fn get_db_path() -> Result<PathBuf> {
 ///
 /// Convenience function that finds .meh, opens storage, and returns it.
 pub fn open_storage() -> Result<Storage> {
-    Storage::open(&get_db_path()?)
+    open_storage_at(&get_db_path()?)
+}
+
+/// Open storage at a path, scoped to the active namespace
+/// (`--namespace`, `MEH_NAMESPACE` or `core.namespace`), with the KB's
+/// fuzzy setting
+pub fn open_storage_at(db_path: &Path) -> Result<Storage> {
+    let config = Config::load()?;
+    Storage::open(db_path)?
+        .with_namespace(config.namespace()?)
+        .with_fuzzy(config.fuzzy_for(db_path))
+}
+
+/// Embed facts just written for `--semantic` search; a failing embedder
+/// only warns, the write stands
+pub fn embed_written(storage: &Storage, facts: &[&Fact]) {
+    let result = Config::load()
+        .and_then(|config| embed::embed_written(storage, &config.search.embeddings, facts));
+    if let Err(e) = result {
+        eprintln!(
+            "⚠️  Not embedded for semantic search: {} (run `meh db reindex --embeddings`)",
+            e
+        );
+    }
 }
 
 /// Find a fact by ID or path
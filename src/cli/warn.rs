@@ -0,0 +1,55 @@
+//! `meh warn` command
+//!
+//! Attaches a warning (caveat) to an existing fact without replacing it.
+//!
+//! # Usage
+//! ```bash
+//! meh warn meh-01HQ3K2JN5 "Does not apply to the /health endpoint"
+//! meh warn @products/alpha/api/timeout "Overridden by LB idle timeout (60s)"
+//! ```
+//!
+//! # Difference from extend/correct
+//! - `correct` = "this replaces that" (supersedes)
+//! - `extend` = "this adds to that" (extends)
+//! - `warn` = "careful with that" (shown next to it, lowers its trust)
+
+use anyhow::Result;
+use clap::Args;
+
+use super::utils::{find_fact, find_meh_dir, open_storage_at};
+use crate::core::fact::{AuthorType, Fact};
+
+#[derive(Args, Debug)]
+pub struct WarnArgs {
+    /// ID or path of the fact to warn about
+    pub target: String,
+
+    /// The caveat
+    pub caveat: String,
+}
+
+pub fn run(args: WarnArgs) -> Result<()> {
+    // 1. Find .meh directory
+    let meh_dir = find_meh_dir()?;
+    let db_path = meh_dir.join("data.db");
+    let storage = open_storage_at(&db_path)?;
+
+    // 2. Find original fact
+    let original = find_fact(&storage, &args.target)?;
+
+    // 3. Create warning fact
+    let mut warning = Fact::warning(&original, &args.caveat);
+    warning.author_type = AuthorType::Human;
+    warning.author_id = "cli".to_string();
+    warning.generate_summary(150);
+    let meh_id = warning.meh_id();
+
+    // 4. Insert new fact
+    storage.insert(&warning)?;
+
+    println!("⚠️  Warning added: {}", meh_id);
+    println!("   On: meh-{}", original.id);
+    println!("   Path: {}", original.path);
+
+    Ok(())
+}
@@ -1,10 +1,29 @@
 //! Configuration module
 
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
+use crate::core::embed::EmbeddingConfig;
+use crate::core::fact::parse_namespace;
+use crate::core::schema::FactSchema;
+use crate::core::storage::GcMode;
+use crate::core::trust::TrustCalculator;
+
+/// Namespace given on the command line, set once at startup
+static NAMESPACE_OVERRIDE: OnceLock<String> = OnceLock::new();
+
+/// Work in `namespace` instead of the configured one for the rest of the
+/// process (`meh --namespace`, which clap also fills from `MEH_NAMESPACE`)
+pub fn set_namespace_override(namespace: &str) -> Result<()> {
+    let namespace = parse_namespace(namespace)?;
+    NAMESPACE_OVERRIDE
+        .set(namespace)
+        .map_err(|_| anyhow::anyhow!("Namespace override already set"))
+}
+
 /// Main configuration structure
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Config {
@@ -20,6 +39,10 @@ pub struct Config {
     #[serde(default)]
     pub trust: TrustConfig,
 
+    /// Per-path fact schemas (`[[schemas]]`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schemas: Vec<FactSchema>,
+
     /// Remote servers configuration
     #[serde(default)]
     pub servers: Vec<ServerEntry>,
@@ -69,6 +92,22 @@ pub struct CoreConfig {
     /// Auto-run GC on MCP server start
     #[serde(default = "default_gc_auto")]
     pub gc_auto: bool,
+
+    /// What GC does with old facts: archive (keep, hidden) or purge (delete)
+    #[serde(default)]
+    pub gc_mode: GcMode,
+
+    /// What adding a fact with duplicate content does: refuse, warn or vote
+    #[serde(default)]
+    pub dedupe: DedupePolicy,
+
+    /// Largest file `meh attach` accepts (KB)
+    #[serde(default = "default_attachment_max_kb")]
+    pub attachment_max_kb: u64,
+
+    /// Namespace to read and write ("" or "default" is the default namespace)
+    #[serde(default)]
+    pub namespace: String,
 }
 
 impl Default for CoreConfig {
@@ -79,6 +118,10 @@ impl Default for CoreConfig {
             cache_max_mb: default_cache_max_mb(),
             gc_retention_days: default_gc_retention_days(),
             gc_auto: default_gc_auto(),
+            gc_mode: GcMode::default(),
+            dedupe: DedupePolicy::default(),
+            attachment_max_kb: default_attachment_max_kb(),
+            namespace: String::new(),
         }
     }
 }
@@ -99,6 +142,23 @@ fn default_gc_auto() -> bool {
     true
 }
 
+fn default_attachment_max_kb() -> u64 {
+    1024
+}
+
+/// What adding a fact does when identical content already exists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DedupePolicy {
+    /// Reject the new fact
+    Refuse,
+    /// Add it anyway and point at the existing fact
+    #[default]
+    Warn,
+    /// Record a +1 vote on the existing fact instead
+    Vote,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchConfig {
     #[serde(default = "default_limit")]
@@ -109,6 +169,20 @@ pub struct SearchConfig {
 
     #[serde(default = "default_timeout_secs")]
     pub federated_timeout_secs: u64,
+
+    /// Words shown around the match in search results (max 64, 0 = show
+    /// summaries instead)
+    #[serde(default = "default_snippet_words")]
+    pub snippet_words: usize,
+
+    /// Typo-tolerant search for local databases without a `[[kbs.kb]]`
+    /// entry, e.g. the default data.db (KBs with one use their `fuzzy`)
+    #[serde(default)]
+    pub fuzzy: bool,
+
+    /// Embedder for semantic search (`[search.embeddings]`)
+    #[serde(default)]
+    pub embeddings: EmbeddingConfig,
 }
 
 impl Default for SearchConfig {
@@ -117,6 +191,9 @@ impl Default for SearchConfig {
             default_limit: default_limit(),
             token_budget: default_token_budget(),
             federated_timeout_secs: default_timeout_secs(),
+            snippet_words: default_snippet_words(),
+            fuzzy: false,
+            embeddings: EmbeddingConfig::default(),
         }
     }
 }
@@ -125,6 +202,10 @@ fn default_limit() -> usize {
     20
 }
 
+fn default_snippet_words() -> usize {
+    24
+}
+
 fn default_token_budget() -> usize {
     3000
 }
@@ -140,6 +221,10 @@ pub struct TrustConfig {
 
     #[serde(default = "default_decay_rate")]
     pub decay_rate: f32,
+
+    /// Trust subtracted for each warning attached to a fact
+    #[serde(default = "default_warning_penalty")]
+    pub warning_penalty: f32,
 }
 
 impl Default for TrustConfig {
@@ -147,10 +232,21 @@ impl Default for TrustConfig {
         Self {
             default_score: default_trust_score(),
             decay_rate: default_decay_rate(),
+            warning_penalty: default_warning_penalty(),
         }
     }
 }
 
+impl TrustConfig {
+    /// Trust calculator using these settings
+    pub fn calculator(&self) -> TrustCalculator {
+        TrustCalculator::with_config(crate::core::trust::TrustConfig {
+            warning_penalty: self.warning_penalty,
+            ..Default::default()
+        })
+    }
+}
+
 fn default_trust_score() -> f32 {
     0.5
 }
@@ -159,6 +255,10 @@ fn default_decay_rate() -> f32 {
     0.01
 }
 
+fn default_warning_penalty() -> f32 {
+    0.1
+}
+
 /// Server entry - defines a remote server with auth
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerEntry {
@@ -226,12 +326,24 @@ pub struct KbConfig {
     /// Write policy: allow, deny, ask
     #[serde(default = "default_write_policy")]
     pub write: WritePolicy,
+
+    /// Keep a trigram index for typo-tolerant search (sqlite KBs only)
+    #[serde(default)]
+    pub fuzzy: bool,
+
+    /// Weight (0-1) of this KB's results in federated search
+    #[serde(default = "default_kb_trust")]
+    pub trust: f32,
 }
 
 fn default_kb_type() -> String {
     "sqlite".to_string()
 }
 
+fn default_kb_trust() -> f32 {
+    1.0
+}
+
 /// Write policy for a knowledge base
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
@@ -426,6 +538,22 @@ impl Config {
         PathBuf::from(".meh").join("data.db")
     }
 
+    /// Get the active namespace
+    ///
+    /// Priority:
+    /// 1. `meh --namespace` (see `set_namespace_override`)
+    /// 2. MEH_NAMESPACE env var
+    /// 3. `core.namespace` from config
+    pub fn namespace(&self) -> Result<String> {
+        if let Some(namespace) = NAMESPACE_OVERRIDE.get() {
+            return Ok(namespace.clone());
+        }
+        match std::env::var("MEH_NAMESPACE") {
+            Ok(namespace) => parse_namespace(&namespace),
+            Err(_) => parse_namespace(&self.core.namespace),
+        }
+    }
+
     /// Get write policy for a knowledge base by name
     /// Returns Allow if KB not found (backward compatible)
     pub fn get_write_policy(&self, kb_name: &str) -> WritePolicy {
@@ -454,6 +582,25 @@ impl Config {
             .and_then(|server_name| self.get_server(server_name))
     }
 
+    /// Whether the local database at `db_path` keeps a trigram index for
+    /// fuzzy search: the `fuzzy` of its `[[kbs.kb]]` entry (one without
+    /// `path` is the default database), else `search.fuzzy`
+    pub fn fuzzy_for(&self, db_path: &Path) -> bool {
+        let same = |a: &Path, b: &Path| match (a.canonicalize(), b.canonicalize()) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => a == b,
+        };
+        self.kbs
+            .kb
+            .iter()
+            .filter(|kb| kb.kb_type != "remote")
+            .find(|kb| match &kb.path {
+                Some(path) => same(Path::new(path), db_path),
+                None => same(&self.data_dir(), db_path),
+            })
+            .map_or(self.search.fuzzy, |kb| kb.fuzzy)
+    }
+
     /// Get primary KB name
     pub fn primary_kb(&self) -> &str {
         &self.kbs.primary
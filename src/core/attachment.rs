@@ -0,0 +1,138 @@
+//! Attachments - binary files attached to facts
+//!
+//! Screenshots, logs, stack traces and diagrams that don't belong in the
+//! markdown `content` of a fact.
+//!
+//! # Key Points
+//! - Blobs are content-addressed (SHA-256) and stored once in `blobs`
+//! - `attachments` maps (fact, name) to a blob with its MIME type
+//! - Corrections inherit the attachments of the fact they supersede
+
+use std::path::Path;
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use ulid::Ulid;
+
+/// Metadata of a file attached to a fact
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attachment {
+    pub fact_id: Ulid,
+    /// File name, unique per fact
+    pub name: String,
+    pub mime_type: String,
+    /// SHA-256 of the data (hex)
+    pub hash: String,
+    /// Size in bytes
+    pub size: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Attachment {
+    /// Whether the data is readable text (safe to show inline)
+    pub fn is_text(&self) -> bool {
+        is_text_mime(&self.mime_type)
+    }
+}
+
+/// SHA-256 (hex) of attachment data
+pub fn blob_hash(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// Reject attachments larger than `max_kb` kilobytes
+pub fn check_size(data: &[u8], max_kb: u64) -> Result<()> {
+    let max_bytes = max_kb.saturating_mul(1024);
+    if data.len() as u64 > max_bytes {
+        bail!(
+            "Attachment is {} KB, limit is {} KB (core.attachment_max_kb)",
+            data.len().div_ceil(1024),
+            max_kb
+        );
+    }
+    Ok(())
+}
+
+/// Guess a MIME type from the file extension, falling back to the data
+pub fn guess_mime(path: &Path, data: &[u8]) -> String {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+
+    let mime = match ext.as_str() {
+        "txt" | "log" | "trace" => "text/plain",
+        "md" | "markdown" => "text/markdown",
+        "csv" => "text/csv",
+        "html" | "htm" => "text/html",
+        "json" => "application/json",
+        "toml" => "application/toml",
+        "yaml" | "yml" => "application/yaml",
+        "xml" => "application/xml",
+        "svg" => "image/svg+xml",
+        "mmd" | "mermaid" => "text/vnd.mermaid",
+        "dot" | "gv" => "text/vnd.graphviz",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "gz" => "application/gzip",
+        _ if std::str::from_utf8(data).is_ok() => "text/plain",
+        _ => "application/octet-stream",
+    };
+
+    mime.to_string()
+}
+
+/// Text-like MIME types (shown inline by MCP `get`)
+pub fn is_text_mime(mime: &str) -> bool {
+    mime.starts_with("text/")
+        || matches!(
+            mime,
+            "application/json"
+                | "application/toml"
+                | "application/yaml"
+                | "application/xml"
+                | "image/svg+xml"
+        )
+}
+
+/// Human-readable size (B / KB / MB)
+pub fn format_size(size: u64) -> String {
+    if size < 1024 {
+        format!("{} B", size)
+    } else if size < 1024 * 1024 {
+        format!("{:.1} KB", size as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", size as f64 / (1024.0 * 1024.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guess_mime() {
+        assert_eq!(guess_mime(Path::new("trace.log"), b""), "text/plain");
+        assert_eq!(guess_mime(Path::new("shot.PNG"), b""), "image/png");
+        assert_eq!(guess_mime(Path::new("notes"), b"hello"), "text/plain");
+        assert_eq!(
+            guess_mime(Path::new("dump"), &[0xff, 0xfe, 0x00]),
+            "application/octet-stream"
+        );
+        assert!(is_text_mime("application/json"));
+        assert!(!is_text_mime("image/png"));
+    }
+
+    #[test]
+    fn test_check_size() {
+        assert!(check_size(&[0; 1024], 1).is_ok());
+        assert!(check_size(&[0; 1025], 1).is_err());
+    }
+}
@@ -0,0 +1,562 @@
+//! Backup - Consistent snapshots of the meh databases
+//!
+//! Uses SQLite's online backup API, so snapshots are consistent even while
+//! an MCP server is writing in WAL mode.
+//!
+//! # Snapshot Layout
+//! ```text
+//! meh-backup-20260101-120000-123456/   (or .tar.gz with --compress)
+//!   manifest.json
+//!   data.db
+//!   notifications.db
+//!   pending_queue.db
+//! ```
+//!
+//! # Key Points
+//! - Snapshots are plain rollback-journal databases (no -wal/-shm files)
+//! - The manifest records fact counts and SHA-256 checksums
+//! - Restore validates checksums, integrity and schema version before
+//!   touching the live databases, then swaps all of them or none
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OpenFlags, MAIN_DB};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::migrations::{self, Migration};
+use super::{notifications, pending_queue, storage};
+
+/// Prefix of every snapshot created by `create_backup`
+pub const BACKUP_PREFIX: &str = "meh-backup-";
+
+const MANIFEST_FILE: &str = "manifest.json";
+const MANIFEST_FORMAT: u32 = 1;
+
+/// Snapshot manifest (`manifest.json`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Manifest format version
+    pub format: u32,
+    /// meh version that created the snapshot
+    pub meh_version: String,
+    pub created_at: DateTime<Utc>,
+    /// Fact counts in `data.db`
+    pub facts: FactCounts,
+    pub databases: Vec<DatabaseEntry>,
+}
+
+/// Fact counts by status
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FactCounts {
+    pub total: usize,
+    pub active: usize,
+    pub superseded: usize,
+    pub deprecated: usize,
+    pub pending_review: usize,
+}
+
+/// One database file inside a snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseEntry {
+    /// File name inside the snapshot (e.g., "data.db")
+    pub name: String,
+    pub schema_version: u32,
+    pub size_bytes: u64,
+    pub sha256: String,
+}
+
+/// Live database files: (snapshot name, live path, migrations)
+fn database_files(data_db: &Path) -> Vec<(&'static str, PathBuf, &'static [Migration])> {
+    let dir = data_db
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from(".meh"));
+
+    vec![
+        ("data.db", data_db.to_path_buf(), storage::MIGRATIONS),
+        (
+            "notifications.db",
+            dir.join("notifications.db"),
+            notifications::MIGRATIONS,
+        ),
+        (
+            "pending_queue.db",
+            dir.join("pending_queue.db"),
+            pending_queue::MIGRATIONS,
+        ),
+    ]
+}
+
+/// Create a snapshot of all databases in `dest_dir`
+///
+/// # Returns
+/// Path of the created snapshot (directory, or `.tar.gz` if `compress`)
+pub fn create_backup(
+    data_db: &Path,
+    dest_dir: &Path,
+    compress: bool,
+) -> Result<(PathBuf, Manifest)> {
+    if !data_db.exists() {
+        bail!("Database not found at {}", data_db.display());
+    }
+
+    fs::create_dir_all(dest_dir)
+        .with_context(|| format!("Failed to create {}", dest_dir.display()))?;
+
+    let created_at = Utc::now();
+    // Microseconds keep back-to-back snapshots apart
+    let name = format!(
+        "{}{}",
+        BACKUP_PREFIX,
+        created_at.format("%Y%m%d-%H%M%S-%6f")
+    );
+    let snapshot_dir = dest_dir.join(&name);
+    let archive_path = dest_dir.join(format!("{}.tar.gz", name));
+
+    if snapshot_dir.exists() || archive_path.exists() {
+        bail!("Backup {} already exists", name);
+    }
+
+    // Always snapshot into a directory first; compress afterwards
+    let staging = if compress {
+        dest_dir.join(format!(".{}.tmp", name))
+    } else {
+        snapshot_dir.clone()
+    };
+    fs::create_dir_all(&staging)?;
+
+    let result = write_snapshot(data_db, &staging, created_at);
+    let manifest = match result {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+    };
+
+    if !compress {
+        return Ok((snapshot_dir, manifest));
+    }
+
+    let packed = pack_archive(&staging, &archive_path, &manifest);
+    let _ = fs::remove_dir_all(&staging);
+    if let Err(e) = packed {
+        let _ = fs::remove_file(&archive_path);
+        return Err(e);
+    }
+
+    Ok((archive_path, manifest))
+}
+
+fn write_snapshot(data_db: &Path, dir: &Path, created_at: DateTime<Utc>) -> Result<Manifest> {
+    let mut databases = Vec::new();
+
+    for (name, live_path, _) in database_files(data_db) {
+        if !live_path.exists() {
+            continue;
+        }
+
+        let target = dir.join(name);
+        // Read-write open: a read-only connection can't attach to a WAL database
+        // whose -shm file doesn't exist yet
+        let source = Connection::open(&live_path)
+            .with_context(|| format!("Failed to open {}", live_path.display()))?;
+        source.busy_timeout(std::time::Duration::from_secs(5))?;
+        source
+            .backup(MAIN_DB, &target, None)
+            .with_context(|| format!("Failed to back up {}", name))?;
+
+        // Make the snapshot a self-contained single file
+        let snapshot = Connection::open(&target)?;
+        snapshot.pragma_update(None, "journal_mode", "DELETE")?;
+        let schema_version = migrations::current_version(&snapshot)?;
+        drop(snapshot);
+
+        databases.push(DatabaseEntry {
+            name: name.to_string(),
+            schema_version,
+            size_bytes: fs::metadata(&target)?.len(),
+            sha256: sha256_file(&target)?,
+        });
+    }
+
+    let facts = count_facts(&dir.join("data.db"))?;
+
+    let manifest = Manifest {
+        format: MANIFEST_FORMAT,
+        meh_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at,
+        facts,
+        databases,
+    };
+
+    fs::write(
+        dir.join(MANIFEST_FILE),
+        serde_json::to_string_pretty(&manifest)?,
+    )?;
+
+    Ok(manifest)
+}
+
+fn pack_archive(dir: &Path, archive_path: &Path, manifest: &Manifest) -> Result<()> {
+    let file = fs::File::create(archive_path)
+        .with_context(|| format!("Failed to create {}", archive_path.display()))?;
+    let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+
+    builder.append_path_with_name(dir.join(MANIFEST_FILE), MANIFEST_FILE)?;
+    for db in &manifest.databases {
+        builder.append_path_with_name(dir.join(&db.name), &db.name)?;
+    }
+
+    builder.into_inner()?.finish()?;
+    Ok(())
+}
+
+/// Count facts by status in a snapshot's `data.db`
+fn count_facts(path: &Path) -> Result<FactCounts> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let mut stmt = conn.prepare("SELECT status, COUNT(*) FROM facts GROUP BY status")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as usize))
+    })?;
+
+    let mut counts = FactCounts::default();
+    for row in rows {
+        let (status, count) = row?;
+        counts.total += count;
+        match status.as_str() {
+            "active" => counts.active += count,
+            "superseded" => counts.superseded += count,
+            "deprecated" => counts.deprecated += count,
+            "pending_review" => counts.pending_review += count,
+            _ => {}
+        }
+    }
+
+    Ok(counts)
+}
+
+fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Delete the oldest snapshots in `dest_dir`, keeping the newest `keep`
+///
+/// # Returns
+/// Paths that were removed
+pub fn rotate_backups(dest_dir: &Path, keep: usize) -> Result<Vec<PathBuf>> {
+    let mut snapshots: Vec<PathBuf> = fs::read_dir(dest_dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(BACKUP_PREFIX))
+        })
+        .collect();
+
+    // Timestamped names sort chronologically
+    snapshots.sort();
+
+    let excess = snapshots.len().saturating_sub(keep);
+    let removed: Vec<PathBuf> = snapshots.into_iter().take(excess).collect();
+
+    for path in &removed {
+        if path.is_dir() {
+            fs::remove_dir_all(path)?;
+        } else {
+            fs::remove_file(path)?;
+        }
+    }
+
+    Ok(removed)
+}
+
+/// A snapshot that has been unpacked and validated, ready to restore
+pub struct StagedRestore {
+    staging: PathBuf,
+    data_db: PathBuf,
+    pub manifest: Manifest,
+}
+
+/// Unpack and validate a snapshot without touching the live databases
+///
+/// Checks that every listed database is present, matches its checksum,
+/// passes `PRAGMA integrity_check` and is not newer than this binary.
+pub fn prepare_restore(archive: &Path, data_db: &Path) -> Result<StagedRestore> {
+    if !archive.exists() {
+        bail!("Backup not found at {}", archive.display());
+    }
+
+    let live_dir = data_db
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from(".meh"));
+    fs::create_dir_all(&live_dir)?;
+
+    // Stage next to the live databases so the final rename stays on one filesystem
+    let staging = live_dir.join(format!(".restore-{}", ulid::Ulid::new()));
+    fs::create_dir_all(&staging)?;
+
+    let manifest =
+        match unpack(archive, &staging).and_then(|()| validate_snapshot(&staging, data_db)) {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                let _ = fs::remove_dir_all(&staging);
+                return Err(e.context(format!("Invalid backup {}", archive.display())));
+            }
+        };
+
+    Ok(StagedRestore {
+        staging,
+        data_db: data_db.to_path_buf(),
+        manifest,
+    })
+}
+
+fn unpack(archive: &Path, staging: &Path) -> Result<()> {
+    if archive.is_dir() {
+        for entry in fs::read_dir(archive)? {
+            let path = entry?.path();
+            if path.is_file() {
+                fs::copy(&path, staging.join(path.file_name().unwrap()))?;
+            }
+        }
+        return Ok(());
+    }
+
+    let file = fs::File::open(archive)?;
+    let mut tar = tar::Archive::new(flate2::read::GzDecoder::new(file));
+    tar.unpack(staging).context("Failed to unpack archive")?;
+    Ok(())
+}
+
+fn validate_snapshot(staging: &Path, data_db: &Path) -> Result<Manifest> {
+    let manifest_json =
+        fs::read_to_string(staging.join(MANIFEST_FILE)).context("Missing manifest.json")?;
+    let manifest: Manifest = serde_json::from_str(&manifest_json).context("Unreadable manifest")?;
+
+    if manifest.format > MANIFEST_FORMAT {
+        bail!(
+            "Manifest format {} is newer than supported ({})",
+            manifest.format,
+            MANIFEST_FORMAT
+        );
+    }
+
+    if !manifest.databases.iter().any(|db| db.name == "data.db") {
+        bail!("Snapshot does not contain data.db");
+    }
+
+    let known = database_files(data_db);
+
+    for entry in &manifest.databases {
+        let Some((_, _, db_migrations)) = known.iter().find(|(name, _, _)| *name == entry.name)
+        else {
+            bail!("Unknown database in manifest: {}", entry.name);
+        };
+
+        let path = staging.join(&entry.name);
+        if !path.exists() {
+            bail!("{} is listed in the manifest but missing", entry.name);
+        }
+
+        let checksum = sha256_file(&path)?;
+        if checksum != entry.sha256 {
+            bail!("{}: checksum mismatch (corrupted snapshot)", entry.name);
+        }
+
+        let conn = Connection::open_with_flags(&path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let integrity: String = conn.query_row("PRAGMA integrity_check", [], |row| row.get(0))?;
+        if integrity != "ok" {
+            bail!("{}: integrity check failed: {}", entry.name, integrity);
+        }
+
+        migrations::check_compatible(&conn, &entry.name, db_migrations)?;
+    }
+
+    Ok(manifest)
+}
+
+impl StagedRestore {
+    /// Replace the live databases with the staged snapshot
+    ///
+    /// All live files (with their WAL/SHM files) are first moved aside into
+    /// the staging directory, then the snapshot files are moved in. If any
+    /// step fails, everything is moved back, so the live databases are
+    /// either all restored or all unchanged. Databases not present in the
+    /// snapshot are left untouched.
+    pub fn apply(self) -> Result<()> {
+        let targets: Vec<(&str, PathBuf)> = database_files(&self.data_db)
+            .into_iter()
+            .filter(|(name, _, _)| self.manifest.databases.iter().any(|db| db.name == *name))
+            .map(|(name, live_path, _)| (name, live_path))
+            .collect();
+
+        let aside = self.staging.join("previous");
+        fs::create_dir_all(&aside)?;
+
+        // (original location, where it was moved)
+        let mut moved: Vec<(PathBuf, PathBuf)> = Vec::new();
+        let mut placed: Vec<PathBuf> = Vec::new();
+
+        let result = (|| -> Result<()> {
+            // Stale WAL/SHM files would be replayed over the restored file
+            for (_, live_path) in &targets {
+                for suffix in ["", "-wal", "-shm"] {
+                    let mut file = live_path.clone().into_os_string();
+                    file.push(suffix);
+                    let file = PathBuf::from(file);
+                    if !file.exists() {
+                        continue;
+                    }
+                    let to = aside.join(file.file_name().unwrap_or_default());
+                    fs::rename(&file, &to)
+                        .with_context(|| format!("Failed to move {} aside", file.display()))?;
+                    moved.push((file, to));
+                }
+            }
+
+            for (name, live_path) in &targets {
+                fs::rename(self.staging.join(name), live_path)
+                    .with_context(|| format!("Failed to restore {}", live_path.display()))?;
+                placed.push(live_path.clone());
+            }
+
+            Ok(())
+        })();
+
+        if result.is_err() {
+            for path in &placed {
+                let _ = fs::remove_file(path);
+            }
+            for (original, to) in moved.iter().rev() {
+                let _ = fs::rename(to, original);
+            }
+        }
+
+        result
+    }
+}
+
+impl Drop for StagedRestore {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.staging);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::fact::Fact;
+    use crate::core::storage::Storage;
+    use tempfile::tempdir;
+
+    fn live_db(dir: &Path) -> Result<PathBuf> {
+        let data_db = dir.join(".meh").join("data.db");
+        fs::create_dir_all(data_db.parent().unwrap())?;
+        let storage = Storage::open(&data_db)?;
+        storage.insert(&Fact::new("@a", "A", "First"))?;
+        storage.insert(&Fact::new("@b", "B", "Second"))?;
+        Ok(data_db)
+    }
+
+    #[test]
+    fn test_backup_and_restore_roundtrip() -> Result<()> {
+        for compress in [false, true] {
+            let dir = tempdir()?;
+            let data_db = live_db(dir.path())?;
+
+            let (snapshot, manifest) =
+                create_backup(&data_db, &dir.path().join("backups"), compress)?;
+            assert_eq!(manifest.facts.total, 2);
+            assert_eq!(manifest.facts.active, 2);
+
+            // Change the live database after the snapshot
+            Storage::open(&data_db)?.insert(&Fact::new("@c", "C", "Third"))?;
+
+            let staged = prepare_restore(&snapshot, &data_db)?;
+            assert_eq!(staged.manifest.facts.total, 2);
+            staged.apply()?;
+
+            assert_eq!(Storage::open(&data_db)?.stats()?.total_facts, 2);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_restore_rejects_corrupted_snapshot() -> Result<()> {
+        let dir = tempdir()?;
+        let data_db = live_db(dir.path())?;
+
+        let (snapshot, _) = create_backup(&data_db, &dir.path().join("backups"), false)?;
+        fs::write(snapshot.join("data.db"), b"not a database")?;
+
+        let err = prepare_restore(&snapshot, &data_db).err().unwrap();
+        assert!(format!("{:#}", err).contains("checksum mismatch"));
+
+        // Live database untouched
+        assert_eq!(Storage::open(&data_db)?.stats()?.total_facts, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_failed_restore_leaves_all_databases_unchanged() -> Result<()> {
+        let dir = tempdir()?;
+        let data_db = live_db(dir.path())?;
+        let notifications_db = data_db.with_file_name("notifications.db");
+        Connection::open(&notifications_db)?.execute_batch("CREATE TABLE t (x INTEGER)")?;
+
+        let (snapshot, _) = create_backup(&data_db, &dir.path().join("backups"), false)?;
+        Storage::open(&data_db)?.insert(&Fact::new("@c", "C", "Third"))?;
+
+        // data.db is swapped first; notifications.db then fails
+        let staged = prepare_restore(&snapshot, &data_db)?;
+        fs::remove_file(staged.staging.join("notifications.db"))?;
+        assert!(staged.apply().is_err());
+
+        assert_eq!(Storage::open(&data_db)?.stats()?.total_facts, 3);
+        assert!(notifications_db.exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_backups_in_the_same_second_get_distinct_names() -> Result<()> {
+        let dir = tempdir()?;
+        let data_db = live_db(dir.path())?;
+        let backups = dir.path().join("backups");
+
+        let (first, _) = create_backup(&data_db, &backups, false)?;
+        let (second, _) = create_backup(&data_db, &backups, false)?;
+        assert_ne!(first, second);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rotate_keeps_newest() -> Result<()> {
+        let dir = tempdir()?;
+        for ts in ["20260101-000000", "20260102-000000", "20260103-000000"] {
+            fs::create_dir(dir.path().join(format!("{}{}", BACKUP_PREFIX, ts)))?;
+        }
+        fs::write(dir.path().join("unrelated.txt"), "keep me")?;
+
+        let removed = rotate_backups(dir.path(), 2)?;
+        assert_eq!(removed.len(), 1);
+        assert!(removed[0].ends_with("meh-backup-20260101-000000"));
+        assert!(dir.path().join("unrelated.txt").exists());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,428 @@
+//! Embeddings - vectors for semantic search
+//!
+//! Facts are embedded into vectors stored in `fact_embeddings`; a semantic
+//! search ranks facts by cosine similarity to the query vector and fuses
+//! that ranking with BM25 (reciprocal rank fusion, see `search`).
+//!
+//! # Embedders
+//! - `hashed` (default): built-in and offline. Hashes stemmed words and
+//!   their character trigrams, so word forms ("logins" / "login failure")
+//!   meet; it knows no synonyms.
+//! - `command`: runs a user-configured local program (e.g. a wrapper around
+//!   a sentence-transformer or Ollama) that reads a JSON array of texts on
+//!   stdin and prints a JSON array of vectors.
+//!
+//! Configured as `[search.embeddings]` in config.toml. Each vector records
+//! its embedder (`model`), so switching embedders re-embeds facts instead
+//! of comparing incompatible vectors.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use ulid::Ulid;
+
+use super::fact::Fact;
+use super::storage::Storage;
+
+/// Turns texts into vectors
+pub trait Embedder: Send + Sync + std::fmt::Debug {
+    /// Identifies the embedder and its settings (stored with each vector)
+    fn model(&self) -> String;
+
+    /// One vector per text, in order
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>>;
+
+    /// Cosine similarity below which a fact is not considered related
+    fn min_similarity(&self) -> f32;
+}
+
+/// `[search.embeddings]` settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingConfig {
+    /// Which embedder to use
+    #[serde(default)]
+    pub embedder: EmbedderKind,
+
+    /// Program and arguments for the `command` embedder
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub command: Vec<String>,
+
+    /// Vector size of the `hashed` embedder
+    #[serde(default = "default_dims")]
+    pub dims: usize,
+
+    /// Cosine similarity below which a fact is not considered related
+    /// (default depends on the embedder)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_similarity: Option<f32>,
+}
+
+impl Default for EmbeddingConfig {
+    fn default() -> Self {
+        Self {
+            embedder: EmbedderKind::default(),
+            command: Vec::new(),
+            dims: default_dims(),
+            min_similarity: None,
+        }
+    }
+}
+
+fn default_dims() -> usize {
+    512
+}
+
+/// Available embedders
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum EmbedderKind {
+    /// Built-in hashed bag of words and trigrams
+    #[default]
+    Hashed,
+    /// User-configured local program
+    Command,
+}
+
+/// Build the configured embedder
+pub fn from_config(config: &EmbeddingConfig) -> Result<Arc<dyn Embedder>> {
+    Ok(match config.embedder {
+        EmbedderKind::Hashed => {
+            let mut embedder = HashedEmbedder::new(config.dims)?;
+            if let Some(min) = config.min_similarity {
+                embedder.min_similarity = min;
+            }
+            Arc::new(embedder)
+        }
+        EmbedderKind::Command => {
+            let mut embedder = CommandEmbedder::new(config.command.clone())?;
+            if let Some(min) = config.min_similarity {
+                embedder.min_similarity = min;
+            }
+            Arc::new(embedder)
+        }
+    })
+}
+
+/// Built-in offline embedder: hashed, sublinear counts of stemmed words
+/// and their character trigrams
+#[derive(Debug)]
+pub struct HashedEmbedder {
+    dims: usize,
+    min_similarity: f32,
+}
+
+/// Weight of a trigram relative to its whole word
+const TRIGRAM_WEIGHT: f32 = 0.25;
+
+/// Words too common to say anything about a fact
+pub(crate) const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "can", "did", "do", "does", "for", "from",
+    "how", "i", "in", "is", "it", "no", "not", "of", "on", "or", "should", "that", "the", "there",
+    "this", "to", "was", "we", "were", "what", "when", "where", "which", "who", "why", "will",
+    "with", "would", "you",
+];
+
+impl HashedEmbedder {
+    pub fn new(dims: usize) -> Result<Self> {
+        if dims == 0 {
+            bail!("[search.embeddings] dims must be greater than 0");
+        }
+        // Texts sharing a word or two land around 0.15-0.5, unrelated
+        // ones below 0.1 (hash collisions)
+        Ok(Self {
+            dims,
+            min_similarity: 0.12,
+        })
+    }
+
+    fn embed_one(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0f32; self.dims];
+        let lower = text.to_lowercase();
+        for word in lower
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| w.len() > 1 && !STOPWORDS.contains(w))
+        {
+            let stem = stem(word);
+            vector[self.bucket(&format!("w:{}", stem))] += 1.0;
+
+            let padded: Vec<char> = format!("#{}#", stem).chars().collect();
+            for gram in padded.windows(3) {
+                let gram: String = gram.iter().collect();
+                vector[self.bucket(&format!("t:{}", gram))] += TRIGRAM_WEIGHT;
+            }
+        }
+
+        // Sublinear counts, so one repeated word does not dominate
+        for v in vector.iter_mut() {
+            *v = v.ln_1p();
+        }
+        normalize(&mut vector);
+        vector
+    }
+
+    fn bucket(&self, feature: &str) -> usize {
+        (fnv1a(feature.as_bytes()) % self.dims as u64) as usize
+    }
+}
+
+impl Embedder for HashedEmbedder {
+    fn model(&self) -> String {
+        format!("hashed-v1-{}", self.dims)
+    }
+
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        Ok(texts.iter().map(|t| self.embed_one(t)).collect())
+    }
+
+    fn min_similarity(&self) -> f32 {
+        self.min_similarity
+    }
+}
+
+/// Runs a local program: JSON array of texts on stdin, JSON array of
+/// vectors (arrays of numbers) on stdout
+#[derive(Debug)]
+pub struct CommandEmbedder {
+    command: Vec<String>,
+    min_similarity: f32,
+}
+
+impl CommandEmbedder {
+    pub fn new(command: Vec<String>) -> Result<Self> {
+        if command.is_empty() {
+            bail!(
+                "The command embedder needs a program: set `command = [\"my-embedder\", \"--arg\"]` in [search.embeddings]"
+            );
+        }
+        Ok(Self {
+            command,
+            min_similarity: 0.3,
+        })
+    }
+}
+
+impl Embedder for CommandEmbedder {
+    fn model(&self) -> String {
+        format!("command:{}", self.command.join(" "))
+    }
+
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let program = &self.command[0];
+        let mut child = Command::new(program)
+            .args(&self.command[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to run embedder '{}'", program))?;
+
+        // Written from another thread: a program that prints while it reads
+        // would otherwise fill the stdout pipe and block both sides
+        let input = serde_json::to_vec(texts)?;
+        let mut stdin = child.stdin.take().context("Embedder stdin unavailable")?;
+        let writer = std::thread::spawn(move || stdin.write_all(&input));
+
+        let output = child.wait_with_output()?;
+        let written = writer
+            .join()
+            .map_err(|_| anyhow::anyhow!("Embedder stdin writer panicked"))?;
+        if !output.status.success() {
+            bail!(
+                "Embedder '{}' failed ({}): {}",
+                program,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        written.with_context(|| format!("Failed to write to embedder '{}'", program))?;
+
+        let vectors: Vec<Vec<f32>> = serde_json::from_slice(&output.stdout).with_context(|| {
+            format!("Embedder '{}' must print a JSON array of vectors", program)
+        })?;
+        if vectors.len() != texts.len() {
+            bail!(
+                "Embedder '{}' returned {} vectors for {} texts",
+                program,
+                vectors.len(),
+                texts.len()
+            );
+        }
+
+        Ok(vectors
+            .into_iter()
+            .map(|mut v| {
+                normalize(&mut v);
+                v
+            })
+            .collect())
+    }
+
+    fn min_similarity(&self) -> f32 {
+        self.min_similarity
+    }
+}
+
+/// Text of a fact that gets embedded
+pub fn fact_text(fact: &Fact) -> String {
+    format!(
+        "{}\n{}\n{}\n{}",
+        fact.title,
+        fact.path.replace(['@', '/', '-', '_'], " "),
+        fact.tags.join(" "),
+        fact.content
+    )
+}
+
+/// Facts embedded per embedder call
+const BATCH_SIZE: usize = 64;
+
+/// Embed facts just written with the configured embedder
+///
+/// Searches only read vectors: writes embed what they add, and facts
+/// written earlier (or while the embedder failed) are embedded by
+/// `meh db reindex --embeddings`.
+pub fn embed_written(storage: &Storage, config: &EmbeddingConfig, facts: &[&Fact]) -> Result<()> {
+    let embedder = from_config(config)?;
+    let texts: Vec<String> = facts.iter().map(|f| fact_text(f)).collect();
+    let vectors = embedder.embed(&texts)?;
+    let rows: Vec<(Ulid, Vec<f32>)> = facts.iter().map(|f| f.id).zip(vectors).collect();
+    storage.set_embeddings(&embedder.model(), &rows)
+}
+
+/// Embed every searchable fact that has no vector from `embedder` yet,
+/// returning how many were embedded
+pub fn backfill(storage: &Storage, embedder: &dyn Embedder) -> Result<usize> {
+    let model = embedder.model();
+    let mut total = 0;
+    loop {
+        let facts = storage.facts_without_embedding(&model, BATCH_SIZE)?;
+        if facts.is_empty() {
+            return Ok(total);
+        }
+
+        let texts: Vec<String> = facts.iter().map(fact_text).collect();
+        let vectors = embedder.embed(&texts)?;
+        let rows: Vec<(Ulid, Vec<f32>)> = facts.iter().map(|f| f.id).zip(vectors).collect();
+        storage.set_embeddings(&model, &rows)?;
+        total += rows.len();
+    }
+}
+
+/// Cosine similarity of two normalized vectors (0 if sizes differ)
+pub fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Smoothing constant of reciprocal rank fusion (the usual 60)
+const RRF_K: f32 = 60.0;
+
+/// Fuse rankings (best first): each list adds `1 / (RRF_K + rank)` to an
+/// item's score. Returns items by fused score, best first.
+pub fn reciprocal_rank_fusion(rankings: &[Vec<Ulid>]) -> Vec<(Ulid, f32)> {
+    let mut scores: HashMap<Ulid, f32> = HashMap::new();
+    let mut order: Vec<Ulid> = Vec::new();
+    for ranking in rankings {
+        for (rank, id) in ranking.iter().enumerate() {
+            let score = scores.entry(*id).or_insert_with(|| {
+                order.push(*id);
+                0.0
+            });
+            *score += 1.0 / (RRF_K + rank as f32 + 1.0);
+        }
+    }
+
+    // Stable on ties: earlier lists (BM25) win
+    let mut fused: Vec<(Ulid, f32)> = order.into_iter().map(|id| (id, scores[&id])).collect();
+    fused.sort_by(|a, b| b.1.total_cmp(&a.1));
+    fused
+}
+
+/// Scale to unit length (no-op for zero vectors)
+fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+}
+
+/// Crude suffix stripping so word forms share a feature
+fn stem(word: &str) -> &str {
+    const SUFFIXES: &[&str] = &[
+        "ations", "ation", "ings", "ing", "ions", "ion", "ers", "er", "ies", "es", "ed", "ly", "s",
+    ];
+    SUFFIXES
+        .iter()
+        .find(|s| word.len() > s.len() + 2 && word.ends_with(*s))
+        .map(|s| &word[..word.len() - s.len()])
+        .unwrap_or(word)
+}
+
+/// FNV-1a: stable across runs and Rust versions, unlike `DefaultHasher`
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ *b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hashed_embedder_relates_word_forms() -> Result<()> {
+        let embedder = HashedEmbedder::new(512)?;
+        let vectors = embedder.embed(&[
+            "why do logins keep failing".to_string(),
+            "Login failures after deploy".to_string(),
+            "Quarterly budget spreadsheet".to_string(),
+        ])?;
+
+        let related = cosine(&vectors[0], &vectors[1]);
+        let unrelated = cosine(&vectors[0], &vectors[2]);
+        assert!(related > 0.3, "related: {}", related);
+        assert!(related > unrelated * 3.0);
+        assert!((cosine(&vectors[1], &vectors[1]) - 1.0).abs() < 1e-5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_reciprocal_rank_fusion() {
+        let (a, b, c) = (Ulid::new(), Ulid::new(), Ulid::new());
+        // b is second in both lists, so it beats a and c, each first in one
+        let fused = reciprocal_rank_fusion(&[vec![a, b], vec![c, b]]);
+        assert_eq!(fused[0].0, b);
+        assert_eq!(fused[1].0, a);
+        assert_eq!(fused.len(), 3);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_embedder_reads_output_while_writing_input() -> Result<()> {
+        // Prints more than a pipe holds before reading its (also large) input
+        let embedder = CommandEmbedder::new(vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "head -c 200000 /dev/zero | tr '\\0' ' '; cat >/dev/null; printf '[[3, 4]]'"
+                .to_string(),
+        ])?;
+        let vectors = embedder.embed(&["x".repeat(200_000)])?;
+        assert_eq!(vectors, vec![vec![0.6, 0.8]]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_command_embedder_requires_program() {
+        let config = EmbeddingConfig {
+            embedder: EmbedderKind::Command,
+            ..Default::default()
+        };
+        assert!(from_config(&config).is_err());
+    }
+}
@@ -0,0 +1,196 @@
+//! Expiry - time-bound facts
+//!
+//! Facts like "staging DB is down until Friday" carry a `valid_until`;
+//! facts that should be re-checked carry a `review_by`.
+//!
+//! # Key Points
+//! - Expired facts are deprecated with a system-authored notice
+//! - Passed review dates produce one notification per fact
+//! - Runs on MCP server start and in `meh gc`
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+use super::fact::{AuthorType, Fact, FactType, Status};
+use super::notifications::{Notification, NotificationStorage};
+use super::storage::Storage;
+
+/// Parse an expiry: a duration from `now` (`30m`, `12h`, `7d`, `2w`),
+/// a date (`2026-07-01`, midnight UTC) or an RFC 3339 timestamp
+pub fn parse_expiry(s: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let s = s.trim();
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+    }
+
+    let Some(unit) = s.chars().last() else {
+        bail!("Empty expiry");
+    };
+    let amount: i64 = s[..s.len() - unit.len_utf8()]
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid expiry '{}' (e.g. 7d, 12h, 2026-07-01)", s))?;
+
+    let duration = match unit {
+        'm' => Duration::minutes(amount),
+        'h' => Duration::hours(amount),
+        'd' => Duration::days(amount),
+        'w' => Duration::weeks(amount),
+        _ => bail!("Invalid expiry unit in '{}' (use m, h, d or w)", s),
+    };
+
+    Ok(now + duration)
+}
+
+/// What an expiry pass did (or would do, on a dry run)
+#[derive(Debug, Default)]
+pub struct ExpiryReport {
+    /// Facts past `valid_until`, now deprecated
+    pub expired: Vec<Fact>,
+    /// Facts past `review_by`, reported once
+    pub review_due: Vec<Fact>,
+}
+
+/// Deprecate expired facts and report passed review dates
+///
+/// Each expired fact gets a deprecation notice authored by `system`.
+/// Notifications are best-effort and skipped when `notifications` is None;
+/// a review date only counts as reported once its notification is stored.
+pub fn process(
+    storage: &Storage,
+    notifications: Option<&NotificationStorage>,
+    now: DateTime<Utc>,
+    dry_run: bool,
+) -> Result<ExpiryReport> {
+    let mut report = ExpiryReport {
+        expired: storage.get_expired(now)?,
+        review_due: storage.get_review_due(now)?,
+    };
+    // No point asking for a review of a fact that is being retired
+    report.review_due.retain(|f| !f.is_expired(now));
+
+    if dry_run {
+        return Ok(report);
+    }
+
+    for fact in &report.expired {
+        let valid_until = fact.valid_until.unwrap_or(now);
+        let reason = format!("Expired: valid until {}", valid_until.to_rfc3339());
+
+        let notice = expiry_notice(fact, &reason);
+
+        storage.transaction(|tx| {
+            tx.insert(&notice)?;
+            tx.mark_deprecated(&fact.id)
+        })?;
+
+        if let Some(notifications) = notifications {
+            let _ = notifications.add(&Notification::for_deprecation(
+                fact.id, &fact.path, &reason, "local",
+            ));
+        }
+    }
+
+    // Only a recorded reminder counts; otherwise the next pass retries
+    if let Some(notifications) = notifications {
+        for fact in &report.review_due {
+            let review_by = fact.review_by.unwrap_or(now);
+            let notification = Notification::for_review(fact.id, &fact.path, review_by, "local");
+            if notifications.add(&notification).is_ok() {
+                storage.mark_review_notified(&fact.id)?;
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Deprecation notice recording why an expired fact was retired
+///
+/// Stored already deprecated, so it never shows up as current knowledge.
+fn expiry_notice(fact: &Fact, reason: &str) -> Fact {
+    let mut notice = Fact::new(&fact.path, format!("Deprecated: {}", fact.title), reason);
+    notice.extends = vec![fact.id];
+    notice.fact_type = FactType::Deprecation;
+    notice.namespace = fact.namespace.clone();
+    notice.status = Status::Deprecated;
+    notice.author_type = AuthorType::System;
+    notice.author_id = "expiry".to_string();
+    notice.generate_summary(150);
+    notice
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_expiry() -> Result<()> {
+        let now = Utc::now();
+        assert_eq!(parse_expiry("7d", now)?, now + Duration::days(7));
+        assert_eq!(parse_expiry("12h", now)?, now + Duration::hours(12));
+        assert_eq!(parse_expiry("2w", now)?, now + Duration::weeks(2));
+        assert_eq!(
+            parse_expiry("2026-07-01", now)?.to_rfc3339(),
+            "2026-07-01T00:00:00+00:00"
+        );
+        assert!(parse_expiry("soon", now).is_err());
+        assert!(parse_expiry("7y", now).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_expires_and_reviews() -> Result<()> {
+        let storage = Storage::open_memory()?;
+        let notifications = NotificationStorage::in_memory()?;
+        let now = Utc::now();
+
+        let mut expired = Fact::new("@ops/staging", "Staging down", "Staging DB is down");
+        expired.valid_until = Some(now - Duration::hours(1));
+        let mut current = Fact::new("@ops/flag", "Flag on", "Feature flag X is on");
+        current.valid_until = Some(now + Duration::days(1));
+        let mut review = Fact::new("@ops/limits", "Limits", "Rate limit is 100/s");
+        review.review_by = Some(now - Duration::days(1));
+        for fact in [&expired, &current, &review] {
+            storage.insert(fact)?;
+        }
+
+        let preview = process(&storage, Some(&notifications), now, true)?;
+        assert_eq!(preview.expired.len(), 1);
+        assert_eq!(
+            storage.get_by_id(&expired.id)?.unwrap().status,
+            Status::Active
+        );
+
+        // Without a notification store the review reminder is kept for later
+        let silent = process(&storage, None, now - Duration::hours(2), false)?;
+        assert_eq!(silent.review_due.len(), 1);
+
+        let report = process(&storage, Some(&notifications), now, false)?;
+        assert_eq!(report.expired[0].id, expired.id);
+        assert_eq!(report.review_due[0].id, review.id);
+
+        assert_eq!(
+            storage.get_by_id(&expired.id)?.unwrap().status,
+            Status::Deprecated
+        );
+        assert_eq!(
+            storage.get_by_id(&current.id)?.unwrap().status,
+            Status::Active
+        );
+        let notice = storage.get_extensions(&expired.id)?;
+        assert_eq!(notice[0].fact_type, FactType::Deprecation);
+        assert_eq!(notice[0].author_type, AuthorType::System);
+
+        // Reported once; the expired fact is no longer active
+        let again = process(&storage, Some(&notifications), now, false)?;
+        assert!(again.expired.is_empty());
+        assert!(again.review_due.is_empty());
+        assert_eq!(notifications.unread_count()?, 2);
+
+        Ok(())
+    }
+}
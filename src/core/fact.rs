@@ -12,6 +12,8 @@
 //! - **trust_score**: 0.0-1.0 Bayesian trust
 //! - **supersedes**: For append-only corrections
 
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use ulid::Ulid;
@@ -75,6 +77,37 @@ pub enum Status {
     PendingReview,
 }
 
+impl std::str::FromStr for Status {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "active" => Ok(Status::Active),
+            "superseded" => Ok(Status::Superseded),
+            "deprecated" => Ok(Status::Deprecated),
+            "archived" => Ok(Status::Archived),
+            "pending_review" => Ok(Status::PendingReview),
+            _ => anyhow::bail!(
+                "Unknown status: {} (use active, deprecated, superseded, archived or pending_review)",
+                s
+            ),
+        }
+    }
+}
+
+impl Status {
+    /// Database representation (matches serde names)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Active => "active",
+            Status::Superseded => "superseded",
+            Status::Deprecated => "deprecated",
+            Status::Archived => "archived",
+            Status::PendingReview => "pending_review",
+        }
+    }
+}
+
 /// Author type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
@@ -102,6 +135,52 @@ pub enum FactType {
     Deprecation,
 }
 
+/// Kind of relation between two facts (see `fact_links` table)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkKind {
+    /// Source adds information to target
+    Extends,
+    /// Source replaces target
+    Supersedes,
+    /// Source is a vote on target
+    Vote,
+    /// Source mentions target in its content
+    References,
+}
+
+impl LinkKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LinkKind::Extends => "extends",
+            LinkKind::Supersedes => "supersedes",
+            LinkKind::Vote => "vote",
+            LinkKind::References => "references",
+        }
+    }
+
+    pub fn parse_str(s: &str) -> Option<Self> {
+        match s {
+            "extends" => Some(LinkKind::Extends),
+            "supersedes" => Some(LinkKind::Supersedes),
+            "vote" => Some(LinkKind::Vote),
+            "references" => Some(LinkKind::References),
+            _ => None,
+        }
+    }
+}
+
+/// A directed relation between two facts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FactLink {
+    /// Fact that declares the relation
+    pub source_id: Ulid,
+    /// Target fact ID
+    pub target: String,
+    pub kind: LinkKind,
+    pub created_at: DateTime<Utc>,
+}
+
 /// A fact - the fundamental unit of knowledge
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Fact {
@@ -125,11 +204,15 @@ pub struct Fact {
     #[serde(default)]
     pub tags: Vec<String>,
 
+    /// Structured key-value metadata (e.g. severity=high, component=billing)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub properties: BTreeMap<String, String>,
+
     /// Source type
     #[serde(default)]
     pub source: Source,
 
-    /// Namespace (for multi-tenant)
+    /// Namespace (`""` is the default namespace, see `Storage::with_namespace`)
     #[serde(default)]
     pub namespace: String,
 
@@ -149,6 +232,11 @@ pub struct Fact {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub supersedes: Option<Ulid>,
 
+    /// IDs of all facts this one consolidates (see `Fact::merge`);
+    /// `supersedes` holds the first of them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub merged_from: Vec<Ulid>,
+
     /// IDs of facts this extends
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub extends: Vec<Ulid>,
@@ -170,6 +258,14 @@ pub struct Fact {
     /// Last access timestamp
     #[serde(skip_serializing_if = "Option::is_none")]
     pub accessed_at: Option<DateTime<Utc>>,
+
+    /// After this moment the fact no longer holds (deprecated automatically)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<DateTime<Utc>>,
+
+    /// Date by which someone should check the fact is still right
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review_by: Option<DateTime<Utc>>,
 }
 
 fn default_trust() -> f32 {
@@ -194,18 +290,22 @@ impl Fact {
             content: content.into(),
             summary: None,
             tags: Vec::new(),
+            properties: BTreeMap::new(),
             source: Source::default(),
             namespace: String::new(),
             trust_score: initial_trust,
             status: Status::default(),
             fact_type: FactType::default(),
             supersedes: None,
+            merged_from: Vec::new(),
             extends: Vec::new(),
             author_type: AuthorType::default(),
             author_id: String::new(),
             created_at: now,
             updated_at: now,
             accessed_at: None,
+            valid_until: None,
+            review_by: None,
         }
     }
 
@@ -228,18 +328,22 @@ impl Fact {
             content: content.into(),
             summary: None,
             tags: Vec::new(),
+            properties: BTreeMap::new(),
             source: Source::default(),
             namespace: String::new(),
             trust_score: initial_trust,
             status: Status::default(),
             fact_type: FactType::default(),
             supersedes: None,
+            merged_from: Vec::new(),
             extends: Vec::new(),
             author_type,
             author_id: author_id.into(),
             created_at: now,
             updated_at: now,
             accessed_at: None,
+            valid_until: None,
+            review_by: None,
         }
     }
 
@@ -261,10 +365,61 @@ impl Fact {
         let mut fact = Fact::new(&original.path, &original.title, new_content);
         fact.supersedes = Some(original.id);
         fact.fact_type = FactType::Correction;
+        fact.namespace = original.namespace.clone();
         fact.tags = original.tags.clone();
+        fact.properties = original.properties.clone();
         fact
     }
 
+    /// Create a fact that consolidates several facts into one
+    ///
+    /// Every source gets superseded by it. The first line of the content
+    /// is its title (as for a new fact); it takes the namespace of the
+    /// first source, the union of their tags and their properties (earlier
+    /// sources win on conflicting keys).
+    pub fn merge(sources: &[Fact], path: impl Into<String>, content: impl Into<String>) -> Self {
+        let first = sources.first();
+        let content = content.into();
+        let title: String = content
+            .lines()
+            .next()
+            .unwrap_or(&content)
+            .chars()
+            .take(50)
+            .collect();
+
+        let mut fact = Fact::new(path, title, content);
+        fact.fact_type = FactType::Correction;
+        fact.supersedes = first.map(|f| f.id);
+        fact.merged_from = sources.iter().map(|f| f.id).collect();
+        fact.namespace = first.map(|f| f.namespace.clone()).unwrap_or_default();
+        for source in sources {
+            for tag in &source.tags {
+                if !fact.tags.contains(tag) {
+                    fact.tags.push(tag.clone());
+                }
+            }
+            for (key, value) in &source.properties {
+                fact.properties
+                    .entry(key.clone())
+                    .or_insert_with(|| value.clone());
+            }
+        }
+        fact
+    }
+
+    /// IDs of every fact this one supersedes (a correction's original or
+    /// all sources of a merge)
+    pub fn superseded_ids(&self) -> Vec<Ulid> {
+        let mut ids: Vec<Ulid> = self.supersedes.into_iter().collect();
+        for id in &self.merged_from {
+            if !ids.contains(id) {
+                ids.push(*id);
+            }
+        }
+        ids
+    }
+
     /// Create an extension of another fact
     pub fn extension(original: &Fact, additional_content: impl Into<String>) -> Self {
         let mut fact = Fact::new(
@@ -274,6 +429,36 @@ impl Fact {
         );
         fact.extends = vec![original.id];
         fact.fact_type = FactType::Extension;
+        fact.namespace = original.namespace.clone();
+        fact
+    }
+
+    /// Create a warning (caveat) on another fact
+    ///
+    /// Unlike a correction the original stays current; the warning is shown
+    /// next to it and lowers its trust (see `TrustCalculator`).
+    pub fn warning(original: &Fact, caveat: impl Into<String>) -> Self {
+        let mut fact = Fact::new(
+            &original.path,
+            format!("Warning: {}", original.title),
+            caveat,
+        );
+        fact.extends = vec![original.id];
+        fact.fact_type = FactType::Warning;
+        fact.namespace = original.namespace.clone();
+        fact
+    }
+
+    /// Create a vote on another fact (`vote` is e.g. "+1" or "-1")
+    pub fn vote(original: &Fact, vote: &str, reason: &str) -> Self {
+        let mut fact = Fact::new(
+            &original.path,
+            format!("Vote: {}", original.title),
+            format!("## 🗳️ Vote\n{} — {}\n", vote, reason),
+        );
+        fact.extends = vec![original.id];
+        fact.fact_type = FactType::Extension;
+        fact.namespace = original.namespace.clone();
         fact
     }
 
@@ -283,6 +468,12 @@ impl Fact {
         self
     }
 
+    /// Set a property
+    pub fn with_property(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.properties.insert(key.into(), value.into());
+        self
+    }
+
     /// Set source
     pub fn with_source(mut self, source: Source) -> Self {
         self.source = source;
@@ -296,6 +487,16 @@ impl Fact {
         self
     }
 
+    /// Whether `valid_until` has passed
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.valid_until.is_some_and(|until| until <= now)
+    }
+
+    /// Whether `review_by` has passed
+    pub fn is_review_due(&self, now: DateTime<Utc>) -> bool {
+        self.review_by.is_some_and(|by| by <= now)
+    }
+
     /// Generate summary from content (first sentence or first N chars)
     pub fn generate_summary(&mut self, max_chars: usize) {
         let content = self.content.trim();
@@ -331,6 +532,172 @@ impl Fact {
     pub fn meh_id(&self) -> String {
         format!("meh-{}", self.short_id())
     }
+
+    /// Facts this fact's content refers to (see `parse_references`)
+    pub fn references(&self) -> Vec<String> {
+        let own_id = self.id.to_string();
+        parse_references(&self.content)
+            .into_iter()
+            .filter(|target| *target != own_id)
+            .collect()
+    }
+
+    /// Hash of the normalized content (see `normalize_content`)
+    pub fn content_hash(&self) -> String {
+        content_hash(&self.content)
+    }
+}
+
+/// Content reduced to its words: markdown emphasis, headings, quotes,
+/// list markers, case and whitespace differences are dropped
+pub fn normalize_content(content: &str) -> String {
+    let mut words: Vec<String> = Vec::new();
+
+    for line in content.lines() {
+        let line = line
+            .trim_start()
+            .trim_start_matches(['#', '>'])
+            .trim_start();
+        let line = line
+            .strip_prefix("- ")
+            .or_else(|| line.strip_prefix("* "))
+            .or_else(|| line.strip_prefix("+ "))
+            .unwrap_or(line);
+
+        words.extend(
+            line.split_whitespace()
+                .map(|w| {
+                    w.chars()
+                        .filter(|c| !matches!(c, '*' | '_' | '`' | '~'))
+                        .collect::<String>()
+                        .to_lowercase()
+                })
+                .filter(|w| !w.is_empty()),
+        );
+    }
+
+    words.join(" ")
+}
+
+/// Parse a `key=value` property (key is trimmed and lowercased)
+pub fn parse_property(s: &str) -> anyhow::Result<(String, String)> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or_else(|| anyhow::anyhow!("Invalid property '{}', expected key=value", s))?;
+    let key = key.trim().to_lowercase();
+    if key.is_empty() {
+        anyhow::bail!("Invalid property '{}', key is empty", s);
+    }
+    Ok((key, value.trim().to_string()))
+}
+
+/// Name of the default namespace on the command line and in output
+pub const DEFAULT_NAMESPACE: &str = "default";
+
+/// Parse a namespace name: lowercase letters, digits, `-`, `_` and `.`
+///
+/// `default` (or an empty string) is the default namespace, stored as `""`.
+pub fn parse_namespace(s: &str) -> anyhow::Result<String> {
+    let name = s.trim().to_lowercase();
+    if name.is_empty() || name == DEFAULT_NAMESPACE {
+        return Ok(String::new());
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        anyhow::bail!(
+            "Invalid namespace '{}' (use letters, digits, '-', '_' or '.')",
+            s
+        );
+    }
+    Ok(name)
+}
+
+/// Display name of a namespace (`default` for `""`)
+pub fn namespace_label(namespace: &str) -> &str {
+    if namespace.is_empty() {
+        DEFAULT_NAMESPACE
+    } else {
+        namespace
+    }
+}
+
+/// Facts mentioned in content: `[[@path/to/fact]]`, `[[meh-<ULID>]]` or a
+/// bare `meh-<ULID>`
+///
+/// Paths are returned as written, IDs as plain ULIDs (the form used by
+/// `fact_links`). Duplicates are dropped, first mention wins.
+pub fn parse_references(content: &str) -> Vec<String> {
+    let mut refs: Vec<String> = Vec::new();
+    let mut push = |target: String| {
+        if !refs.contains(&target) {
+            refs.push(target);
+        }
+    };
+
+    // [[...]] wiki links
+    let mut rest = content;
+    while let Some(start) = rest.find("[[") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("]]") else {
+            break;
+        };
+        let inner = after[..end].trim();
+        if inner.starts_with('@') && !inner.contains(char::is_whitespace) {
+            push(inner.trim_end_matches('/').to_string());
+        } else if let Some(id) = parse_meh_id(inner) {
+            push(id.to_string());
+        }
+        rest = &after[end + 2..];
+    }
+
+    // Bare meh-<ULID> mentions
+    for (pos, _) in content.match_indices("meh-") {
+        let boundary = content[..pos]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !c.is_alphanumeric());
+        let candidate = &content[pos..];
+        let len = 4 + candidate[4..]
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or(candidate.len() - 4);
+        if boundary {
+            if let Some(id) = parse_meh_id(&candidate[..len]) {
+                push(id.to_string());
+            }
+        }
+    }
+
+    refs
+}
+
+/// How a reference target is written: paths as-is, IDs as `meh-<ULID>`
+pub fn reference_label(target: &str) -> String {
+    if target.starts_with('@') {
+        target.to_string()
+    } else {
+        format!("meh-{}", target)
+    }
+}
+
+/// Parse `meh-<ULID>` (full 26-character ULID only)
+fn parse_meh_id(s: &str) -> Option<Ulid> {
+    let id = s.strip_prefix("meh-")?;
+    if id.len() != 26 {
+        return None;
+    }
+    Ulid::from_string(id).ok()
+}
+
+/// SHA-256 (hex) of `normalize_content(content)`
+pub fn content_hash(content: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    format!(
+        "{:x}",
+        Sha256::digest(normalize_content(content).as_bytes())
+    )
 }
 
 impl std::fmt::Display for Fact {
@@ -525,6 +892,81 @@ mod tests {
         assert_eq!(correction.tags, vec!["important".to_string()]);
     }
 
+    #[test]
+    fn test_content_hash_ignores_formatting() {
+        let plain = content_hash("API timeout is 30 seconds.");
+        assert_eq!(plain, content_hash("  **API   timeout** is\n30 seconds.  "));
+        assert_eq!(plain, content_hash("# API timeout is `30` seconds."));
+        assert_eq!(plain, content_hash("- api TIMEOUT is _30_ seconds."));
+        assert_ne!(plain, content_hash("API timeout is 60 seconds."));
+    }
+
+    #[test]
+    fn test_vote() {
+        let original = Fact::new("@test", "Test", "Content");
+        let vote = Fact::vote(&original, "+1", "confirmed");
+
+        assert_eq!(vote.extends, vec![original.id]);
+        assert_eq!(vote.fact_type, FactType::Extension);
+        assert_eq!(vote.title, "Vote: Test");
+        assert!(vote.content.contains("+1 — confirmed"));
+    }
+
+    #[test]
+    fn test_properties() {
+        let original =
+            Fact::new("@bugs/crash", "Crash", "Segfault").with_property("severity", "high");
+        let correction = Fact::correction(&original, "Segfault (fixed)");
+        assert_eq!(correction.properties.get("severity").unwrap(), "high");
+
+        assert_eq!(
+            parse_property(" Component = billing ").unwrap(),
+            ("component".to_string(), "billing".to_string())
+        );
+        assert!(parse_property("severity").is_err());
+        assert!(parse_property("=high").is_err());
+    }
+
+    #[test]
+    fn test_parse_references() {
+        let id = Ulid::new();
+        let content = format!(
+            "See [[@api/limits]] and [[ @ops/runbook/ ]], also meh-{} (and meh-{} again).\n\
+             Not links: [[not a path]], meh-short, xmeh-{}, [[@api/limits]]",
+            id,
+            id.to_string().to_lowercase(),
+            Ulid::new()
+        );
+
+        assert_eq!(
+            parse_references(&content),
+            vec![
+                "@api/limits".to_string(),
+                "@ops/runbook".to_string(),
+                id.to_string()
+            ]
+        );
+
+        let fact = Fact::new("@a", "A", format!("Self: meh-{}", id));
+        let mut same = fact.clone();
+        same.id = id;
+        assert!(same.references().is_empty());
+        assert_eq!(fact.references(), vec![id.to_string()]);
+    }
+
+    #[test]
+    fn test_parse_namespace() {
+        assert_eq!(parse_namespace("Billing").unwrap(), "billing");
+        assert_eq!(parse_namespace("default").unwrap(), "");
+        assert_eq!(parse_namespace("").unwrap(), "");
+        assert!(parse_namespace("a b").is_err());
+        assert_eq!(namespace_label(""), "default");
+
+        let mut original = Fact::new("@api", "API", "Timeout is 30s");
+        original.namespace = "billing".to_string();
+        assert_eq!(Fact::correction(&original, "60s").namespace, "billing");
+    }
+
     #[test]
     fn test_unique_ids() {
         let fact1 = Fact::new("@test", "Test1", "Content1");
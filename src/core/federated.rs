@@ -0,0 +1,413 @@
+//! Federated search - one query across several knowledge bases
+//!
+//! Every KB is searched on its own thread; KBs that do not answer within
+//! `federated_timeout_secs` are reported as errors instead of holding up
+//! the others. The results are merged into one ranked list:
+//!
+//! - Scores are normalized per KB to 0-1 (min-max over local BM25 or the
+//!   remote `score`; by rank when a KB returns no scores)
+//! - Then weighted by the KB's `trust` (see `KbConfig`)
+//! - A fact found in several KBs (same content hash or path) is listed once,
+//!   under the KB that ranked it highest
+//!
+//! Used by MCP `federated_search` and `meh search --all-kbs`.
+
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use ulid::Ulid;
+
+use super::fact::Fact;
+use super::search::{self, SearchQuery};
+use super::storage::Storage;
+use crate::config::{Config, KbConfig};
+
+/// One fact of a federated search
+#[derive(Debug)]
+pub struct FederatedHit {
+    /// KB the fact came from
+    pub kb: String,
+    pub fact: Fact,
+    /// Normalized score (0-1) times the KB's trust, higher is better
+    pub score: f32,
+    /// Other KBs that returned the same fact
+    pub also_in: Vec<String>,
+    /// Highlighted snippet (local KBs, see `search::render_snippet`)
+    pub snippet: Option<String>,
+}
+
+/// Merged results of a federated search
+#[derive(Debug, Default)]
+pub struct FederatedResults {
+    /// Best first, at most the requested limit
+    pub hits: Vec<FederatedHit>,
+    /// KBs that answered
+    pub searched: Vec<String>,
+    /// One message per KB that failed or timed out
+    pub errors: Vec<String>,
+}
+
+/// What one KB returned: facts in its own order, with its raw score (if
+/// it has one) and snippet
+type KbHits = Vec<(Fact, Option<f32>, Option<String>)>;
+
+/// Search `kbs` in parallel with `query` (its `limit` applies per KB) and
+/// merge the results into at most `limit` hits
+///
+/// Local KBs are searched in `namespace` (the caller's, e.g. the MCP
+/// session's after `switch_namespace`).
+pub fn search(
+    config: &Config,
+    kbs: &[String],
+    query: &SearchQuery,
+    limit: usize,
+    namespace: &str,
+) -> FederatedResults {
+    let timeout = Duration::from_secs(config.search.federated_timeout_secs);
+    let (tx, rx) = mpsc::channel();
+    for (index, kb_name) in kbs.iter().enumerate() {
+        let (tx, config, query, kb_name, namespace) = (
+            tx.clone(),
+            config.clone(),
+            query.clone(),
+            kb_name.clone(),
+            namespace.to_string(),
+        );
+        // Not joined: a KB that times out is left to finish on its own
+        std::thread::spawn(move || {
+            let hits = search_kb(&config, &kb_name, &query, &namespace, timeout);
+            let _ = tx.send((index, hits));
+        });
+    }
+    drop(tx);
+
+    let mut answers: Vec<Option<Result<KbHits>>> = kbs.iter().map(|_| None).collect();
+    let deadline = Instant::now() + timeout;
+    while answers.iter().any(Option::is_none) {
+        let left = deadline.saturating_duration_since(Instant::now());
+        match rx.recv_timeout(left) {
+            Ok((index, hits)) => answers[index] = Some(hits),
+            Err(_) => break,
+        }
+    }
+
+    let mut results = FederatedResults::default();
+    let mut hits = Vec::new();
+    for (kb_name, answer) in kbs.iter().zip(answers) {
+        match answer {
+            Some(Ok(kb_hits)) => {
+                let weight = config.get_kb(kb_name).map_or(1.0, |kb| kb.trust);
+                let scores: Vec<Option<f32>> = kb_hits.iter().map(|(_, s, _)| *s).collect();
+                for ((fact, _, snippet), score) in kb_hits.into_iter().zip(normalize(&scores)) {
+                    hits.push(FederatedHit {
+                        kb: kb_name.clone(),
+                        fact,
+                        score: score * weight.clamp(0.0, 1.0),
+                        also_in: Vec::new(),
+                        snippet,
+                    });
+                }
+                results.searched.push(kb_name.clone());
+            }
+            Some(Err(e)) => results.errors.push(format!("{}: {:#}", kb_name, e)),
+            None => results.errors.push(format!(
+                "{}: no answer within {}s (federated_timeout_secs)",
+                kb_name,
+                timeout.as_secs()
+            )),
+        }
+    }
+
+    results.hits = merge(hits, limit);
+    results
+}
+
+/// Scores scaled to 0-1 (min-max); by rank if any is missing, all 1.0 if
+/// they are equal
+fn normalize(scores: &[Option<f32>]) -> Vec<f32> {
+    let n = scores.len();
+    let Some(values) = scores.iter().copied().collect::<Option<Vec<f32>>>() else {
+        return (0..n).map(|i| 1.0 - i as f32 / n as f32).collect();
+    };
+    let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    if max - min <= 1e-6 {
+        return vec![1.0; n];
+    }
+    values.iter().map(|v| (v - min) / (max - min)).collect()
+}
+
+/// Best first (ties: higher fact trust, then KB order), duplicates (same
+/// ID or path, or same content where both hits have it) folded into the
+/// first hit
+fn merge(mut hits: Vec<FederatedHit>, limit: usize) -> Vec<FederatedHit> {
+    hits.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(b.fact.trust_score.total_cmp(&a.fact.trust_score))
+    });
+
+    let mut merged: Vec<FederatedHit> = Vec::new();
+    for hit in hits {
+        let hash = (!hit.fact.content.trim().is_empty()).then(|| hit.fact.content_hash());
+        let same = merged.iter_mut().find(|kept| {
+            kept.fact.id == hit.fact.id
+                || kept.fact.path == hit.fact.path
+                || (hash.is_some()
+                    && !kept.fact.content.trim().is_empty()
+                    && hash == Some(kept.fact.content_hash()))
+        });
+        match same {
+            Some(kept) => {
+                if kept.kb != hit.kb && !kept.also_in.contains(&hit.kb) {
+                    kept.also_in.push(hit.kb);
+                }
+            }
+            None => merged.push(hit),
+        }
+    }
+    merged.truncate(limit);
+    merged
+}
+
+/// Search a single KB
+fn search_kb(
+    config: &Config,
+    kb_name: &str,
+    query: &SearchQuery,
+    namespace: &str,
+    timeout: Duration,
+) -> Result<KbHits> {
+    let kb_config = config.get_kb(kb_name).context("not found in config")?;
+
+    match kb_config.kb_type.as_str() {
+        "sqlite" => search_sqlite_kb(config, kb_config, query, namespace),
+        "remote" => search_remote_kb(config, kb_config, query, timeout),
+        other => bail!("unknown type '{}'", other),
+    }
+}
+
+/// Search local SQLite KB
+fn search_sqlite_kb(
+    config: &Config,
+    kb_config: &KbConfig,
+    query: &SearchQuery,
+    namespace: &str,
+) -> Result<KbHits> {
+    let db_path = match &kb_config.path {
+        Some(path) => PathBuf::from(path),
+        None => config.data_dir(),
+    };
+
+    let storage = Storage::open(&db_path)
+        .context("open error")?
+        .with_namespace(namespace)
+        .with_fuzzy(kb_config.fuzzy)
+        .context("fuzzy index error")?;
+
+    let results = search::search(&storage, query).context("search error")?;
+    Ok(results
+        .into_iter()
+        .map(|r| (r.fact, Some(r.relevance), r.snippet))
+        .collect())
+}
+
+/// Search remote KB via HTTP
+///
+/// The API only takes the text; the other filters are applied to what it
+/// returns.
+fn search_remote_kb(
+    config: &Config,
+    kb_config: &KbConfig,
+    query: &SearchQuery,
+    timeout: Duration,
+) -> Result<KbHits> {
+    if query.author_type.is_some() || !query.properties.is_empty() {
+        bail!("author: and prop: filters are not supported for remote KBs");
+    }
+    if query.fuzzy || query.embedder.is_some() {
+        bail!("fuzzy and semantic search are not supported for remote KBs");
+    }
+
+    let server = config
+        .get_server_for_kb(&kb_config.name)
+        .context("no server configured")?;
+    let slug = kb_config.slug.as_ref().context("no slug configured")?;
+
+    let client = reqwest::blocking::Client::builder()
+        .timeout(timeout)
+        .build()
+        .context("client error")?;
+
+    let encoded_query = encode_query(query.text.as_deref().unwrap_or_default());
+    let url = format!(
+        "{}/api/v1/kbs/{}/search?q={}&limit={}",
+        server.url, slug, encoded_query, query.limit
+    );
+
+    let mut request = client.get(&url);
+    if let Some(ref key) = server.api_key {
+        request = request.header("X-API-Key", key);
+    }
+
+    let response = request.send().context("request error")?;
+    if !response.status().is_success() {
+        bail!("HTTP {}", response.status());
+    }
+
+    let json_facts: Vec<serde_json::Value> = response.json().context("parse error")?;
+
+    // Convert to Fact structs
+    let now = chrono::Utc::now();
+    Ok(json_facts
+        .iter()
+        .filter_map(|v| {
+            let id = v["id"].as_str()?;
+            let path = v["path"].as_str()?;
+            let title = v["title"].as_str()?;
+            let summary = v["summary"].as_str();
+            let trust = v["trust_score"].as_f64().unwrap_or(0.5) as f32;
+            let score = v["score"].as_f64().map(|s| s as f32);
+
+            let mut fact = Fact::new(path, title, summary.unwrap_or(""));
+            fact.trust_score = trust;
+            if let Ok(ulid) = Ulid::from_string(id.trim_start_matches("meh-")) {
+                fact.id = ulid;
+            }
+            if !query.matches(&fact, now) {
+                return None;
+            }
+            // Only the summary came back: keep it out of `content`, which
+            // merging compares across KBs
+            fact.summary = Some(std::mem::take(&mut fact.content));
+            Some((fact, score, None))
+        })
+        .collect())
+}
+
+/// URL encoding for query string: UTF-8 bytes, percent-encoded
+fn encode_query(query: &str) -> String {
+    query
+        .bytes()
+        .map(|b| match b {
+            b' ' => "+".to_string(),
+            b if b.is_ascii_alphanumeric() || b"-_.~".contains(&b) => (b as char).to_string(),
+            b => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::WritePolicy;
+
+    #[test]
+    fn test_encode_query() {
+        assert_eq!(encode_query("api timeout"), "api+timeout");
+        assert_eq!(encode_query("tag:x \"a&b\""), "tag%3Ax+%22a%26b%22");
+        assert_eq!(encode_query("café"), "caf%C3%A9");
+    }
+
+    fn sqlite_kb(name: &str, path: &std::path::Path, trust: f32) -> KbConfig {
+        KbConfig {
+            name: name.to_string(),
+            kb_type: "sqlite".to_string(),
+            path: Some(path.display().to_string()),
+            server: None,
+            slug: None,
+            write: WritePolicy::Allow,
+            fuzzy: false,
+            trust,
+        }
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize(&[Some(4.0), Some(3.0), Some(2.0)]),
+            vec![1.0, 0.5, 0.0]
+        );
+        assert_eq!(normalize(&[Some(0.0), Some(0.0)]), vec![1.0, 1.0]);
+        assert_eq!(normalize(&[None, None]), vec![1.0, 0.5]);
+    }
+
+    #[test]
+    fn test_federated_merge_and_dedupe() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let (team_db, mine_db) = (dir.path().join("team.db"), dir.path().join("mine.db"));
+        let team = Storage::open(&team_db)?;
+        team.insert(&Fact::new(
+            "@api/timeout",
+            "Timeout",
+            "API timeout is 30 seconds",
+        ))?;
+        team.insert(&Fact::new(
+            "@api/retry",
+            "Retry",
+            "Retry after a timeout, then give up",
+        ))?;
+        let mine = Storage::open(&mine_db)?;
+        // Same content under another path: the same fact
+        mine.insert(&Fact::new(
+            "@notes/timeout",
+            "Timeout",
+            "API timeout is 30 seconds",
+        ))?;
+        mine.insert(&Fact::new(
+            "@notes/pool",
+            "Pool",
+            "Pool timeout of the database",
+        ))?;
+
+        let mut config = Config::default();
+        config.kbs.kb = vec![
+            sqlite_kb("team", &team_db, 1.0),
+            sqlite_kb("mine", &mine_db, 0.5),
+        ];
+        let kbs = vec!["team".to_string(), "mine".to_string(), "nope".to_string()];
+        let results = search(&config, &kbs, &SearchQuery::new("timeout"), 10, "");
+
+        assert_eq!(results.searched, vec!["team", "mine"]);
+        assert_eq!(results.errors.len(), 1);
+        assert!(results.errors[0].starts_with("nope:"));
+
+        // Three distinct facts; the duplicate is kept under the trusted KB
+        assert_eq!(results.hits.len(), 3);
+        let timeout = results
+            .hits
+            .iter()
+            .find(|h| h.fact.content == "API timeout is 30 seconds")
+            .unwrap();
+        assert_eq!(timeout.kb, "team");
+        assert_eq!(timeout.also_in, vec!["mine"]);
+        assert!(results.hits.windows(2).all(|w| w[0].score >= w[1].score));
+        assert!(results
+            .hits
+            .iter()
+            .all(|h| h.kb == "team" || h.score <= 0.5));
+
+        // The overall limit applies after merging
+        assert_eq!(
+            search(&config, &kbs, &SearchQuery::new("timeout"), 1, "")
+                .hits
+                .len(),
+            1
+        );
+
+        // Local KBs are searched in the caller's namespace
+        Storage::open(&team_db)?
+            .with_namespace("billing")
+            .insert(&Fact::new(
+                "@invoices/timeout",
+                "Timeout",
+                "Invoice timeout",
+            ))?;
+        let billing = search(&config, &kbs, &SearchQuery::new("timeout"), 10, "billing");
+        assert_eq!(billing.hits.len(), 1);
+        assert_eq!(billing.hits[0].fact.path, "@invoices/timeout");
+        Ok(())
+    }
+}
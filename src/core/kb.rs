@@ -14,13 +14,16 @@
 //! └─────────────────────────────────────┘
 //! ```
 
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::Mutex;
 
 use anyhow::Result;
 use async_trait::async_trait;
 
-use super::fact::Fact;
+use super::fact::{Fact, Status};
+use super::path::Path;
+use super::search::{self, SearchQuery, SearchResult};
 use super::storage::PathInfo;
 
 /// Statistics for a knowledge base
@@ -58,6 +61,9 @@ pub trait KnowledgeBaseBackend: Send + Sync {
     /// Mark a fact as deprecated
     async fn mark_deprecated(&self, id: &ulid::Ulid) -> Result<()>;
 
+    /// Record that facts were read (for usage statistics)
+    async fn record_access(&self, ids: &[ulid::Ulid]) -> Result<()>;
+
     /// Whether this KB is read-only
     fn is_readonly(&self) -> bool;
 
@@ -75,9 +81,12 @@ pub struct LocalKb {
 }
 
 impl LocalKb {
-    /// Open a local KB from a database path
-    pub fn open(path: PathBuf) -> Result<Self> {
-        let storage = super::storage::Storage::open(&path)?;
+    /// Open a local KB from a database path, scoped to a namespace, with
+    /// fuzzy search on or off (see `Storage::with_fuzzy`)
+    pub fn open(path: PathBuf, namespace: &str, fuzzy: bool) -> Result<Self> {
+        let storage = super::storage::Storage::open(&path)?
+            .with_namespace(namespace)
+            .with_fuzzy(fuzzy)?;
         let name = path
             .file_stem()
             .and_then(|s| s.to_str())
@@ -95,16 +104,99 @@ impl LocalKb {
     pub fn path(&self) -> &PathBuf {
         &self.path
     }
-}
 
-#[async_trait]
-impl KnowledgeBaseBackend for LocalKb {
-    async fn search(&self, query: &str, limit: usize) -> Result<Vec<Fact>> {
+    /// Full supersede chain through a fact, oldest first
+    ///
+    /// Includes superseded and archived versions.
+    pub fn history_chain(&self, id: &ulid::Ulid) -> Result<Vec<Fact>> {
+        let storage = self
+            .storage
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+        let mut chain = storage.get_history_chain(id)?;
+        chain.extend(storage.get_superseding_facts(id)?);
+        Ok(chain)
+    }
+
+    /// Active warnings attached to a fact
+    pub fn warnings(&self, id: &ulid::Ulid) -> Result<Vec<Fact>> {
+        let storage = self
+            .storage
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+        storage.get_warnings(id)
+    }
+
+    /// Outgoing references of a fact with what they resolve to
+    pub fn references(&self, id: &ulid::Ulid) -> Result<Vec<(String, Option<Fact>)>> {
+        let storage = self
+            .storage
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+        storage.references_from(id)
+    }
+
+    /// Active facts that reference a fact
+    pub fn backlinks(&self, fact: &Fact) -> Result<Vec<Fact>> {
+        let storage = self
+            .storage
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+        storage.backlinks(fact)
+    }
+
+    /// Search with all `SearchQuery` filters (run in SQL)
+    pub fn search_query(&self, query: &SearchQuery) -> Result<Vec<SearchResult>> {
+        let storage = self
+            .storage
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+        search::search(&storage, query)
+    }
+
+    /// Facts whose path matches a glob (see `Storage::find_by_pattern`)
+    pub fn find(
+        &self,
+        pattern: &str,
+        statuses: &[Status],
+        tags: &[String],
+        limit: usize,
+    ) -> Result<Vec<Fact>> {
+        let storage = self
+            .storage
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+        storage.find_by_pattern(pattern, statuses, tags, limit)
+    }
+
+    /// The namespace this KB is scoped to
+    pub fn namespace(&self) -> Result<String> {
         let storage = self
             .storage
             .lock()
             .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
-        storage.search(query, limit as i64)
+        Ok(storage.namespace().to_string())
+    }
+
+    /// Attachments of a fact
+    pub fn attachments(&self, id: &ulid::Ulid) -> Result<Vec<super::attachment::Attachment>> {
+        let storage = self
+            .storage
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+        storage.list_attachments(id)
+    }
+}
+
+#[async_trait]
+impl KnowledgeBaseBackend for LocalKb {
+    async fn search(&self, query: &str, limit: usize) -> Result<Vec<Fact>> {
+        let query = SearchQuery::new(query).with_limit(limit);
+        Ok(self
+            .search_query(&query)?
+            .into_iter()
+            .map(|r| r.fact)
+            .collect())
     }
 
     async fn get_fact(&self, id_or_path: &str) -> Result<Option<Fact>> {
@@ -150,7 +242,7 @@ impl KnowledgeBaseBackend for LocalKb {
             total_facts: stats.total,
             active_facts: stats.active_facts as i64,
             deprecated_facts: stats.deprecated_facts as i64,
-            superseded_facts: 0, // Not tracked in StorageStats
+            superseded_facts: stats.superseded_facts as i64,
         })
     }
 
@@ -170,6 +262,14 @@ impl KnowledgeBaseBackend for LocalKb {
         storage.mark_deprecated(id)
     }
 
+    async fn record_access(&self, ids: &[ulid::Ulid]) -> Result<()> {
+        let storage = self
+            .storage
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+        storage.record_access(ids)
+    }
+
     fn is_readonly(&self) -> bool {
         false
     }
@@ -210,6 +310,148 @@ impl RemoteKb {
     pub fn client(&self) -> &crate::remote::RemoteClient {
         &self.client
     }
+
+    /// Search with a `SearchQuery`
+    ///
+    /// The server only takes text, a path prefix and a limit; the other
+    /// filters are applied to what it returns.
+    pub async fn search_query(&self, query: &SearchQuery) -> Result<Vec<Fact>> {
+        if query.author_type.is_some() || !query.properties.is_empty() {
+            anyhow::bail!(
+                "author: and prop: filters are not supported for remote KB '{}'",
+                self.kb_slug
+            );
+        }
+        if query.fuzzy || query.embedder.is_some() {
+            anyhow::bail!(
+                "Fuzzy and semantic search are not supported for remote KB '{}'",
+                self.kb_slug
+            );
+        }
+
+        let now = chrono::Utc::now();
+        let results = self
+            .client
+            .search(
+                &self.kb_slug,
+                query.text.as_deref().unwrap_or_default(),
+                Some(query.limit),
+                query.path_prefix.as_deref(),
+            )
+            .await?;
+
+        Ok(results
+            .into_iter()
+            .map(remote_to_fact)
+            .filter(|f| query.matches(f, now))
+            .collect())
+    }
+
+    /// Facts whose path matches a glob, found by browsing
+    ///
+    /// The API has no glob search, so this walks the tree from the
+    /// pattern's literal prefix (every page of each level), only
+    /// descending into paths that can still match. The API has no bulk
+    /// fetch either: matching paths that hold facts are fetched
+    /// concurrently, only as many as the limit still needs.
+    pub async fn find(
+        &self,
+        pattern: &str,
+        statuses: &[Status],
+        tags: &[String],
+        limit: usize,
+    ) -> Result<Vec<Fact>> {
+        Path::validate_pattern(pattern)?;
+        let statuses = if statuses.is_empty() {
+            &[Status::Active][..]
+        } else {
+            statuses
+        };
+
+        let start = Path::pattern_prefix(pattern).unwrap_or_else(|| "@".to_string());
+        let mut matching: Vec<String> = Vec::new();
+        if Path::parse(&start).is_ok_and(|p| p.matches_pattern(pattern)) {
+            matching.push(start.clone());
+        }
+        let mut seen: HashSet<String> = HashSet::from([start.clone()]);
+        let mut pending = vec![start];
+        while let Some(dir) = pending.pop() {
+            let mut cursor: Option<String> = None;
+            loop {
+                let page = self
+                    .client
+                    .browse_page(&self.kb_slug, &dir, cursor.as_deref())
+                    .await?;
+                for node in page.nodes {
+                    if !seen.insert(node.path.clone()) {
+                        continue;
+                    }
+                    let Ok(path) = Path::parse(&node.path) else {
+                        continue;
+                    };
+                    // Nothing to fetch at or under an empty node
+                    if node.fact_count > 0 && path.matches_pattern(pattern) {
+                        matching.push(node.path.clone());
+                    }
+                    if path.may_contain_matches(pattern) {
+                        pending.push(node.path);
+                    }
+                }
+                cursor = page.cursor;
+                if cursor.is_none() {
+                    break;
+                }
+            }
+        }
+        matching.sort();
+
+        let mut facts = Vec::new();
+        let mut paths = matching.iter();
+        while facts.len() < limit {
+            let batch: Vec<&String> = paths.by_ref().take(limit - facts.len()).collect();
+            if batch.is_empty() {
+                break;
+            }
+            let fetched =
+                futures::future::try_join_all(batch.into_iter().map(|p| self.get_fact(p))).await?;
+            facts.extend(fetched.into_iter().flatten().filter(|fact| {
+                statuses.contains(&fact.status) && tags.iter().all(|t| fact.tags.contains(t))
+            }));
+        }
+        Ok(facts)
+    }
+}
+
+/// Convert a fact from the HTTP API (fields it does not carry get defaults)
+fn remote_to_fact(rf: crate::remote::RemoteFact) -> Fact {
+    Fact {
+        id: rf.id.parse().unwrap_or_else(|_| ulid::Ulid::new()),
+        path: rf.path,
+        title: rf.title,
+        content: rf.content.unwrap_or_default(),
+        summary: rf.summary,
+        tags: rf.tags,
+        properties: Default::default(),
+        trust_score: rf.trust_score,
+        status: rf
+            .status
+            .as_deref()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_default(),
+        fact_type: super::fact::FactType::Fact,
+        source: super::fact::Source::Local,
+        namespace: String::new(),
+        supersedes: None,
+        merged_from: Vec::new(),
+        extends: Vec::new(),
+        author_type: super::fact::AuthorType::Ai,
+        author_id: String::new(),
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+        accessed_at: None,
+        valid_until: None,
+        review_by: None,
+    }
 }
 
 #[async_trait]
@@ -220,30 +462,7 @@ impl KnowledgeBaseBackend for RemoteKb {
             .search(&self.kb_slug, query, Some(limit), None)
             .await?;
 
-        // Convert RemoteFact to Fact
-        let facts: Vec<Fact> = results
-            .into_iter()
-            .map(|rf| Fact {
-                id: rf.id.parse().unwrap_or_else(|_| ulid::Ulid::new()),
-                path: rf.path,
-                title: rf.title,
-                content: rf.content.unwrap_or_default(),
-                summary: rf.summary,
-                tags: rf.tags,
-                trust_score: rf.trust_score,
-                status: super::fact::Status::Active,
-                fact_type: super::fact::FactType::Fact,
-                source: super::fact::Source::Local,
-                namespace: String::new(),
-                supersedes: None,
-                extends: Vec::new(),
-                author_type: super::fact::AuthorType::Ai,
-                author_id: String::new(),
-                created_at: chrono::Utc::now(),
-                updated_at: chrono::Utc::now(),
-                accessed_at: None,
-            })
-            .collect();
+        let facts: Vec<Fact> = results.into_iter().map(remote_to_fact).collect();
 
         Ok(facts)
     }
@@ -251,26 +470,7 @@ impl KnowledgeBaseBackend for RemoteKb {
     async fn get_fact(&self, id_or_path: &str) -> Result<Option<Fact>> {
         match self.client.get_fact(&self.kb_slug, id_or_path).await {
             Ok(rf) => {
-                let fact = Fact {
-                    id: rf.id.parse().unwrap_or_else(|_| ulid::Ulid::new()),
-                    path: rf.path,
-                    title: rf.title,
-                    content: rf.content.unwrap_or_default(),
-                    summary: rf.summary,
-                    tags: rf.tags,
-                    trust_score: rf.trust_score,
-                    status: super::fact::Status::Active,
-                    fact_type: super::fact::FactType::Fact,
-                    source: super::fact::Source::Local,
-                    namespace: String::new(),
-                    supersedes: None,
-                    extends: Vec::new(),
-                    author_type: super::fact::AuthorType::Ai,
-                    author_id: String::new(),
-                    created_at: chrono::Utc::now(),
-                    updated_at: chrono::Utc::now(),
-                    accessed_at: None,
-                };
+                let fact = remote_to_fact(rf);
                 Ok(Some(fact))
             }
             Err(e) => {
@@ -330,6 +530,11 @@ impl KnowledgeBaseBackend for RemoteKb {
         anyhow::bail!("Remote deprecate not yet implemented")
     }
 
+    async fn record_access(&self, _ids: &[ulid::Ulid]) -> Result<()> {
+        // Reads are tracked by the server
+        Ok(())
+    }
+
     fn is_readonly(&self) -> bool {
         false // Remote KBs are writable (if authenticated)
     }
@@ -346,6 +551,11 @@ pub enum KnowledgeBase {
 }
 
 impl KnowledgeBase {
+    /// `record_access` for read paths (see `Storage::record_usage_best_effort`)
+    pub async fn record_usage_best_effort(&self, ids: &[ulid::Ulid]) {
+        let _ = self.record_access(ids).await;
+    }
+
     /// Create from CLI args and config
     ///
     /// Priority:
@@ -419,13 +629,22 @@ impl KnowledgeBase {
                     } else {
                         config.data_dir()
                     };
-                    Ok(KnowledgeBase::Local(LocalKb::open(db_path)?))
+                    Ok(KnowledgeBase::Local(LocalKb::open(
+                        db_path,
+                        &config.namespace()?,
+                        kb_config.fuzzy,
+                    )?))
                 }
             }
         } else {
             // Fallback to local default
             let db_path = config.data_dir();
-            Ok(KnowledgeBase::Local(LocalKb::open(db_path)?))
+            let fuzzy = config.fuzzy_for(&db_path);
+            Ok(KnowledgeBase::Local(LocalKb::open(
+                db_path,
+                &config.namespace()?,
+                fuzzy,
+            )?))
         }
     }
 
@@ -433,7 +652,12 @@ impl KnowledgeBase {
     pub fn local_default() -> Result<Self> {
         let config = crate::config::Config::load()?;
         let db_path = config.data_dir();
-        Ok(KnowledgeBase::Local(LocalKb::open(db_path)?))
+        let fuzzy = config.fuzzy_for(&db_path);
+        Ok(KnowledgeBase::Local(LocalKb::open(
+            db_path,
+            &config.namespace()?,
+            fuzzy,
+        )?))
     }
 
     /// Check if this is a local KB
@@ -498,6 +722,13 @@ impl KnowledgeBaseBackend for KnowledgeBase {
         }
     }
 
+    async fn record_access(&self, ids: &[ulid::Ulid]) -> Result<()> {
+        match self {
+            KnowledgeBase::Local(kb) => kb.record_access(ids).await,
+            KnowledgeBase::Remote(kb) => kb.record_access(ids).await,
+        }
+    }
+
     fn is_readonly(&self) -> bool {
         match self {
             KnowledgeBase::Local(kb) => kb.is_readonly(),
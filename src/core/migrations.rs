@@ -0,0 +1,207 @@
+//! Migrations - Versioned schema changes for SQLite databases
+//!
+//! Each database (`data.db`, `notifications.db`, `pending_queue.db`) declares
+//! an ordered list of migrations. The applied version is recorded in a
+//! `schema_version` table inside the database itself.
+//!
+//! # Key Points
+//! - Migrations run in order, each in its own transaction
+//! - A database newer than this binary is refused (never downgraded)
+//! - Version 1 is the original `CREATE ... IF NOT EXISTS` schema, so
+//!   databases created before versioning adopt it as a no-op
+
+use anyhow::{bail, Context, Result};
+use rusqlite::{params, Connection, Transaction, TransactionBehavior};
+
+/// A single schema migration
+pub struct Migration {
+    /// Schema version this migration upgrades to (1, 2, 3, ...)
+    pub version: u32,
+    /// Short human-readable description
+    pub description: &'static str,
+    /// Apply the migration (runs inside a transaction)
+    pub up: fn(&Connection) -> rusqlite::Result<()>,
+}
+
+/// Create the `schema_version` table if missing
+fn ensure_version_table(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER PRIMARY KEY,
+            description TEXT NOT NULL,
+            applied_at TEXT NOT NULL
+        );
+        "#,
+    )?;
+    Ok(())
+}
+
+/// Get the current schema version of a database (0 = unversioned)
+pub fn current_version(conn: &Connection) -> Result<u32> {
+    let has_table: bool = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'schema_version'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0),
+    )?;
+
+    if !has_table {
+        return Ok(0);
+    }
+
+    let version: Option<u32> =
+        conn.query_row("SELECT MAX(version) FROM schema_version", [], |row| {
+            row.get(0)
+        })?;
+
+    Ok(version.unwrap_or(0))
+}
+
+/// Latest version known to this binary
+pub fn latest_version(migrations: &[Migration]) -> u32 {
+    migrations.iter().map(|m| m.version).max().unwrap_or(0)
+}
+
+/// List migrations that have not yet been applied
+pub fn pending<'a>(conn: &Connection, migrations: &'a [Migration]) -> Result<Vec<&'a Migration>> {
+    let current = current_version(conn)?;
+    Ok(migrations.iter().filter(|m| m.version > current).collect())
+}
+
+/// Fail if the database was written by a newer version of meh
+pub fn check_compatible(conn: &Connection, db_name: &str, migrations: &[Migration]) -> Result<()> {
+    let current = current_version(conn)?;
+    let latest = latest_version(migrations);
+
+    if current > latest {
+        bail!(
+            "{} has schema version {}, but this version of meh only supports up to {}. \
+             Upgrade meh to open this database.",
+            db_name,
+            current,
+            latest
+        );
+    }
+
+    Ok(())
+}
+
+/// Apply all pending migrations in order
+///
+/// Each migration runs in its own transaction together with its
+/// `schema_version` row, so a failed migration leaves the database at the
+/// previous version.
+///
+/// # Returns
+/// Versions that were applied (empty if already up to date)
+pub fn migrate(conn: &Connection, db_name: &str, migrations: &[Migration]) -> Result<Vec<u32>> {
+    check_compatible(conn, db_name, migrations)?;
+    ensure_version_table(conn)?;
+
+    let mut applied = Vec::new();
+
+    for migration in pending(conn, migrations)? {
+        let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?;
+
+        (migration.up)(&tx).with_context(|| {
+            format!(
+                "{}: migration {} ({}) failed",
+                db_name, migration.version, migration.description
+            )
+        })?;
+
+        tx.execute(
+            "INSERT INTO schema_version (version, description, applied_at) VALUES (?1, ?2, ?3)",
+            params![
+                migration.version,
+                migration.description,
+                chrono::Utc::now().to_rfc3339()
+            ],
+        )?;
+
+        tx.commit()?;
+        applied.push(migration.version);
+    }
+
+    Ok(applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_MIGRATIONS: &[Migration] = &[
+        Migration {
+            version: 1,
+            description: "create items",
+            up: |conn| conn.execute_batch("CREATE TABLE items (id INTEGER PRIMARY KEY);"),
+        },
+        Migration {
+            version: 2,
+            description: "add name column",
+            up: |conn| conn.execute_batch("ALTER TABLE items ADD COLUMN name TEXT;"),
+        },
+    ];
+
+    #[test]
+    fn test_migrate_applies_in_order() -> Result<()> {
+        let conn = Connection::open_in_memory()?;
+
+        assert_eq!(current_version(&conn)?, 0);
+        assert_eq!(pending(&conn, TEST_MIGRATIONS)?.len(), 2);
+
+        let applied = migrate(&conn, "test.db", TEST_MIGRATIONS)?;
+        assert_eq!(applied, vec![1, 2]);
+        assert_eq!(current_version(&conn)?, 2);
+
+        // Second run is a no-op
+        let applied = migrate(&conn, "test.db", TEST_MIGRATIONS)?;
+        assert!(applied.is_empty());
+
+        conn.execute("INSERT INTO items (name) VALUES ('x')", [])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_newer_database_is_refused() -> Result<()> {
+        let conn = Connection::open_in_memory()?;
+        migrate(&conn, "test.db", TEST_MIGRATIONS)?;
+
+        let err = migrate(&conn, "test.db", &TEST_MIGRATIONS[..1]).unwrap_err();
+        assert!(err.to_string().contains("Upgrade meh"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_failed_migration_rolls_back() -> Result<()> {
+        const BROKEN: &[Migration] = &[
+            Migration {
+                version: 1,
+                description: "create items",
+                up: |conn| conn.execute_batch("CREATE TABLE items (id INTEGER PRIMARY KEY);"),
+            },
+            Migration {
+                version: 2,
+                description: "broken",
+                up: |conn| {
+                    conn.execute_batch("CREATE TABLE partial (id INTEGER); SELECT * FROM nope;")
+                },
+            },
+        ];
+
+        let conn = Connection::open_in_memory()?;
+        assert!(migrate(&conn, "test.db", BROKEN).is_err());
+
+        assert_eq!(current_version(&conn)?, 1);
+        let partial: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE name = 'partial'",
+            [],
+            |row| row.get(0),
+        )?;
+        assert_eq!(partial, 0);
+
+        Ok(())
+    }
+}
@@ -2,12 +2,21 @@
 //!
 //! Contains the core data structures and logic for meh.
 
+pub mod attachment;
+pub mod backup;
+pub mod embed;
+pub mod expiry;
 pub mod fact;
+pub mod federated;
 pub mod kb;
+pub mod migrations;
 pub mod multi_storage;
 pub mod notifications;
 pub mod path;
 pub mod pending_queue;
+pub mod query;
+pub mod related;
+pub mod schema;
 pub mod search;
 pub mod storage;
 pub mod trust;
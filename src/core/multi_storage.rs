@@ -131,7 +131,7 @@ impl MultiStorage {
         }
 
         // Sort by created_at desc, dedupe by id
-        results.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        results.sort_by_key(|f| std::cmp::Reverse(f.created_at));
         results.dedup_by(|a, b| a.id == b.id);
 
         Ok(results)
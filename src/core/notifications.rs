@@ -16,6 +16,8 @@ use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection};
 use ulid::Ulid;
 
+use super::migrations::{self, Migration};
+
 /// Notification priority level
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Priority {
@@ -107,6 +109,8 @@ pub enum NotificationType {
     Deprecation,
     /// Fact was extended
     Extension,
+    /// Fact is due for review (`review_by` passed)
+    Review,
     /// External alert (CI/CD, monitoring)
     Alert,
     /// Subscription match
@@ -120,6 +124,7 @@ impl NotificationType {
             NotificationType::Correction => "correction",
             NotificationType::Deprecation => "deprecation",
             NotificationType::Extension => "extension",
+            NotificationType::Review => "review",
             NotificationType::Alert => "alert",
             NotificationType::Match => "match",
         }
@@ -131,6 +136,7 @@ impl NotificationType {
             "correction" => Some(NotificationType::Correction),
             "deprecation" => Some(NotificationType::Deprecation),
             "extension" => Some(NotificationType::Extension),
+            "review" => Some(NotificationType::Review),
             "alert" => Some(NotificationType::Alert),
             "match" => Some(NotificationType::Match),
             _ => None,
@@ -229,6 +235,22 @@ impl Notification {
         .with_fact(fact_id, path)
     }
 
+    /// Create notification for a fact whose review date has passed
+    pub fn for_review(fact_id: Ulid, path: &str, review_by: DateTime<Utc>, source: &str) -> Self {
+        Self::new(
+            Category::Facts,
+            Priority::Normal,
+            source,
+            NotificationType::Review,
+            format!("Review due: {}", path),
+            format!(
+                "Review date {} has passed; check the fact is still right",
+                review_by.format("%Y-%m-%d")
+            ),
+        )
+        .with_fact(fact_id, path)
+    }
+
     /// Create notification for CI event
     pub fn for_ci(title: &str, summary: &str, priority: Priority) -> Self {
         Self::new(
@@ -376,6 +398,70 @@ impl Subscription {
     }
 }
 
+/// Schema migrations for the notifications database (see `migrations.rs`)
+pub const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    description: "notifications and sessions tables",
+    up: migrate_v1,
+}];
+
+/// Version 1: original schema, including the `onboarding_shown` column that
+/// older unversioned databases may be missing
+fn migrate_v1(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(
+        r#"
+        -- Notifications table
+        CREATE TABLE IF NOT EXISTS notifications (
+            id TEXT PRIMARY KEY,
+            category TEXT NOT NULL,
+            priority INTEGER NOT NULL,
+            source TEXT NOT NULL,
+            notification_type TEXT NOT NULL,
+            fact_id TEXT,
+            path TEXT,
+            title TEXT NOT NULL,
+            summary TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_notifications_created
+            ON notifications(created_at DESC);
+        CREATE INDEX IF NOT EXISTS idx_notifications_category
+            ON notifications(category);
+        CREATE INDEX IF NOT EXISTS idx_notifications_priority
+            ON notifications(priority DESC);
+        CREATE INDEX IF NOT EXISTS idx_notifications_path
+            ON notifications(path);
+
+        -- Sessions table - tracks each AI session's read position
+        CREATE TABLE IF NOT EXISTS sessions (
+            session_id TEXT PRIMARY KEY,
+            last_seen_id TEXT,
+            subscription TEXT NOT NULL DEFAULT '{}',
+            onboarding_shown INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            last_active TEXT NOT NULL
+        );
+        "#,
+    )?;
+
+    // SQLite doesn't support ADD COLUMN IF NOT EXISTS, so we check first
+    let has_onboarding: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('sessions') WHERE name='onboarding_shown'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0),
+    )?;
+
+    if !has_onboarding {
+        conn.execute(
+            "ALTER TABLE sessions ADD COLUMN onboarding_shown INTEGER NOT NULL DEFAULT 0",
+            [],
+        )?;
+    }
+
+    Ok(())
+}
+
 /// Notification storage with session support
 pub struct NotificationStorage {
     conn: Connection,
@@ -399,65 +485,9 @@ impl NotificationStorage {
     }
 
     fn init_schema(&self) -> Result<()> {
-        self.conn.execute_batch(
-            r#"
-            PRAGMA journal_mode = WAL;
-            PRAGMA busy_timeout = 5000;
-            
-            -- Notifications table
-            CREATE TABLE IF NOT EXISTS notifications (
-                id TEXT PRIMARY KEY,
-                category TEXT NOT NULL,
-                priority INTEGER NOT NULL,
-                source TEXT NOT NULL,
-                notification_type TEXT NOT NULL,
-                fact_id TEXT,
-                path TEXT,
-                title TEXT NOT NULL,
-                summary TEXT NOT NULL,
-                created_at TEXT NOT NULL
-            );
-            
-            CREATE INDEX IF NOT EXISTS idx_notifications_created 
-                ON notifications(created_at DESC);
-            CREATE INDEX IF NOT EXISTS idx_notifications_category 
-                ON notifications(category);
-            CREATE INDEX IF NOT EXISTS idx_notifications_priority 
-                ON notifications(priority DESC);
-            CREATE INDEX IF NOT EXISTS idx_notifications_path 
-                ON notifications(path);
-            
-            -- Sessions table - tracks each AI session's read position
-            CREATE TABLE IF NOT EXISTS sessions (
-                session_id TEXT PRIMARY KEY,
-                last_seen_id TEXT,
-                subscription TEXT NOT NULL DEFAULT '{}',
-                onboarding_shown INTEGER NOT NULL DEFAULT 0,
-                created_at TEXT NOT NULL,
-                last_active TEXT NOT NULL
-            );
-
-            -- Migration: add onboarding_shown column if missing
-            -- SQLite doesn't support ADD COLUMN IF NOT EXISTS, so we check first
-            "#,
-        )?;
-
-        // Check if onboarding_shown column exists, add if not
-        let has_onboarding: bool = self
-            .conn
-            .query_row(
-                "SELECT COUNT(*) FROM pragma_table_info('sessions') WHERE name='onboarding_shown'",
-                [],
-                |row| Ok(row.get::<_, i64>(0)? > 0),
-            )
-            .unwrap_or(false);
-
-        if !has_onboarding {
-            let _ = self.conn.execute(
-                "ALTER TABLE sessions ADD COLUMN onboarding_shown INTEGER NOT NULL DEFAULT 0",
-                [],
-            );
-        }
+        self.conn
+            .execute_batch("PRAGMA journal_mode = WAL; PRAGMA busy_timeout = 5000;")?;
+        migrations::migrate(&self.conn, "Notifications database", MIGRATIONS)?;
         Ok(())
     }
 
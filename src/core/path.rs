@@ -148,6 +148,15 @@ impl Path {
             .all(|(a, b)| a == b)
     }
 
+    /// Number of leading segments this path shares with another
+    pub fn common_depth(&self, other: &Path) -> usize {
+        self.segments
+            .iter()
+            .zip(other.segments.iter())
+            .take_while(|(a, b)| a == b)
+            .count()
+    }
+
     /// Join with another path or segment
     pub fn join(&self, other: &str) -> Result<Path> {
         let mut new_segments = self.segments.clone();
@@ -184,6 +193,69 @@ impl Path {
         Self::match_segments(&self.segments, &pattern_segments)
     }
 
+    /// Check that a pattern is a path whose segments may be `*` or `**`
+    ///
+    /// Wildcards stand for whole segments: `@products/*/api` is a pattern,
+    /// `@products/al*` is not.
+    pub fn validate_pattern(pattern: &str) -> Result<()> {
+        let segments = Self::pattern_segments(pattern);
+        if segments.is_empty() {
+            bail!("Pattern cannot be empty");
+        }
+        for segment in segments {
+            if segment == "*" || segment == "**" {
+                continue;
+            }
+            if segment.contains('*') {
+                bail!(
+                    "Wildcards must be whole segments ('{}'): use * for one segment, ** for any",
+                    segment
+                );
+            }
+            Self::validate_segment(segment)?;
+        }
+        Ok(())
+    }
+
+    /// The segments of a pattern before its first wildcard, joined (`None`
+    /// if it starts with one)
+    ///
+    /// Every match lies at or under this path.
+    pub fn pattern_prefix(pattern: &str) -> Option<String> {
+        let literal: Vec<&str> = Self::pattern_segments(pattern)
+            .into_iter()
+            .take_while(|s| !s.contains('*'))
+            .collect();
+        (!literal.is_empty()).then(|| literal.join("/"))
+    }
+
+    /// Whether this path or paths under it can match `pattern` (to walk a
+    /// tree without listing every path)
+    pub fn may_contain_matches(&self, pattern: &str) -> bool {
+        Self::match_prefix(&self.segments, &Self::pattern_segments(pattern))
+    }
+
+    fn pattern_segments(pattern: &str) -> Vec<&str> {
+        pattern
+            .trim()
+            .trim_start_matches('/')
+            .trim_end_matches('/')
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+
+    fn match_prefix(path: &[String], pattern: &[&str]) -> bool {
+        match (path.first(), pattern.first()) {
+            // Deeper segments may still match the rest of the pattern
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(_), Some(&"**")) => true,
+            (Some(_), Some(&"*")) => Self::match_prefix(&path[1..], &pattern[1..]),
+            (Some(p), Some(pat)) => p == pat && Self::match_prefix(&path[1..], &pattern[1..]),
+        }
+    }
+
     fn match_segments(path: &[String], pattern: &[&str]) -> bool {
         match (path.first(), pattern.first()) {
             // Both empty = match
@@ -359,6 +431,16 @@ mod tests {
         assert_eq!(root.name(), None);
     }
 
+    #[test]
+    fn test_common_depth() {
+        let path = Path::parse("@api/auth/tokens").unwrap();
+        let sibling = Path::parse("@api/auth/scopes").unwrap();
+        let other = Path::parse("@web/auth/tokens").unwrap();
+        assert_eq!(path.common_depth(&sibling), 2);
+        assert_eq!(path.common_depth(&path), 3);
+        assert_eq!(path.common_depth(&other), 0);
+    }
+
     #[test]
     fn test_parent_chain() {
         let path = Path::parse("@a/b/c/d").unwrap();
@@ -386,6 +468,29 @@ mod tests {
         assert!(!path.starts_with(&longer));
     }
 
+    #[test]
+    fn test_pattern_helpers() {
+        assert!(Path::validate_pattern("@products/*/api/**").is_ok());
+        assert!(Path::validate_pattern("@products/al*").is_err());
+        assert!(Path::validate_pattern("@products/a b/*").is_err());
+
+        assert_eq!(
+            Path::pattern_prefix("@products/*/api/**").as_deref(),
+            Some("@products")
+        );
+        assert_eq!(
+            Path::pattern_prefix("@products/alpha").as_deref(),
+            Some("@products/alpha")
+        );
+        assert_eq!(Path::pattern_prefix("**/timeout"), None);
+
+        let alpha = Path::parse("@products/alpha").unwrap();
+        assert!(alpha.may_contain_matches("@products/*/api/timeout"));
+        assert!(!alpha.may_contain_matches("@users/**"));
+        let api = Path::parse("@products/alpha/docs").unwrap();
+        assert!(!api.may_contain_matches("@products/*/api/**"));
+    }
+
     #[test]
     fn test_wildcard_at_start() {
         let path = Path::parse("@products/alpha/api").unwrap();
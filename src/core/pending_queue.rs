@@ -12,6 +12,37 @@ use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
 use ulid::Ulid;
 
+use super::migrations::{self, Migration};
+
+/// Schema migrations for the pending queue database (see `migrations.rs`)
+pub const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    description: "pending_writes table",
+    up: |conn| {
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS pending_writes (
+                id TEXT PRIMARY KEY,
+                target_kb TEXT NOT NULL,
+                target_url TEXT NOT NULL,
+                write_type TEXT NOT NULL,
+                path TEXT NOT NULL,
+                content TEXT NOT NULL,
+                title TEXT,
+                tags TEXT,
+                supersedes TEXT,
+                extends TEXT,
+                reason TEXT,
+                created_at TEXT NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_pending_target_kb ON pending_writes(target_kb);
+            CREATE INDEX IF NOT EXISTS idx_pending_created ON pending_writes(created_at);
+            "#,
+        )
+    },
+}];
+
 /// Type of pending write operation
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -189,27 +220,7 @@ impl PendingQueue {
     }
 
     fn init_schema(&self) -> Result<()> {
-        self.conn.execute_batch(
-            r#"
-            CREATE TABLE IF NOT EXISTS pending_writes (
-                id TEXT PRIMARY KEY,
-                target_kb TEXT NOT NULL,
-                target_url TEXT NOT NULL,
-                write_type TEXT NOT NULL,
-                path TEXT NOT NULL,
-                content TEXT NOT NULL,
-                title TEXT,
-                tags TEXT,
-                supersedes TEXT,
-                extends TEXT,
-                reason TEXT,
-                created_at TEXT NOT NULL
-            );
-            
-            CREATE INDEX IF NOT EXISTS idx_pending_target_kb ON pending_writes(target_kb);
-            CREATE INDEX IF NOT EXISTS idx_pending_created ON pending_writes(created_at);
-            "#,
-        )?;
+        migrations::migrate(&self.conn, "Pending queue database", MIGRATIONS)?;
         Ok(())
     }
 
@@ -0,0 +1,339 @@
+//! Query language for search
+//!
+//! Compiles queries like
+//! `timeout tag:api path:@products/** -deprecated "connection reset" trust>0.6 author:human`
+//! into an FTS5 `MATCH` expression plus `SearchQuery` filters.
+//!
+//! | Syntax | Meaning |
+//! |--------|---------|
+//! | `word` | any of the words (OR), ranked by BM25 |
+//! | `"some phrase"`, `+word` | must appear |
+//! | `-word`, `-"some phrase"` | must not appear |
+//! | `tag:api`, `-tag:legacy` | has / lacks a tag |
+//! | `path:@products/api`, `path:@products/**` | at or under a path |
+//! | `trust>0.6`, `trust>=0.6` | minimum trust |
+//! | `author:human` | author type (human, ai, system) |
+//! | `prop:severity=high` | property value |
+//!
+//! Words with an unknown `field:` prefix are searched as plain text.
+
+use anyhow::{bail, Result};
+
+use super::fact::AuthorType;
+use super::search::SearchQuery;
+
+/// One text term of a query
+#[derive(Debug, Clone, PartialEq)]
+enum Term {
+    Word(String),
+    Phrase(String),
+}
+
+impl Term {
+    fn text(&self) -> String {
+        match self {
+            Term::Word(w) | Term::Phrase(w) => w.clone(),
+        }
+    }
+
+    /// FTS5 string for the term (quoted, so special characters are literal)
+    fn to_fts(&self) -> String {
+        format!("\"{}\"", self.text().replace('"', "\"\""))
+    }
+}
+
+/// Parse a search query into a `SearchQuery` (limit 20, no other options)
+pub fn parse(input: &str) -> Result<SearchQuery> {
+    let mut query = SearchQuery {
+        limit: 20,
+        ..Default::default()
+    };
+    let mut optional: Vec<Term> = Vec::new();
+    let mut required: Vec<Term> = Vec::new();
+    let mut excluded: Vec<Term> = Vec::new();
+
+    for token in tokenize(input)? {
+        let (sign, body) = match token.chars().next() {
+            Some(c @ ('+' | '-')) => (Some(c), &token[1..]),
+            _ => (None, token.as_str()),
+        };
+        if body.is_empty() {
+            bail!(
+                "'{}' must be followed by a word, e.g. '{}legacy'",
+                token,
+                token
+            );
+        }
+
+        // Quoted phrase
+        if let Some(phrase) = body.strip_prefix('"') {
+            let phrase = phrase.trim_end_matches('"').trim();
+            if phrase.is_empty() {
+                continue;
+            }
+            let term = Term::Phrase(phrase.to_string());
+            match sign {
+                Some('-') => excluded.push(term),
+                _ => required.push(term),
+            }
+            continue;
+        }
+
+        if let Some(value) = body.strip_prefix("trust>") {
+            if sign.is_some() {
+                bail!("'{}': trust filters cannot be negated", token);
+            }
+            let (strict, value) = match value.strip_prefix('=') {
+                Some(v) => (false, v),
+                None => (true, value),
+            };
+            let min: f32 = value
+                .parse()
+                .ok()
+                .filter(|v| (0.0..=1.0).contains(v))
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Invalid trust '{}': expected a number from 0 to 1, e.g. trust>0.6",
+                        value
+                    )
+                })?;
+            query.min_trust = Some(if strict { min + 1e-6 } else { min });
+            continue;
+        }
+        if body.starts_with("trust<") || body.starts_with("trust=") {
+            bail!(
+                "'{}': only minimum trust is supported (trust>0.6 or trust>=0.6)",
+                token
+            );
+        }
+
+        if let Some((field, value)) = body.split_once(':') {
+            let value = unquote(value);
+            match field.to_lowercase().as_str() {
+                "tag" => {
+                    if value.is_empty() {
+                        bail!("'{}' needs a tag name, e.g. tag:api", token);
+                    }
+                    match sign {
+                        Some('-') => query.not_tags.push(value.to_string()),
+                        _ => query.tags.push(value.to_string()),
+                    }
+                    continue;
+                }
+                "path" => {
+                    if sign == Some('-') {
+                        bail!("'{}': paths cannot be excluded", token);
+                    }
+                    query.path_prefix = Some(parse_path(value)?);
+                    continue;
+                }
+                "author" => {
+                    if sign == Some('-') {
+                        bail!("'{}': authors cannot be excluded", token);
+                    }
+                    query.author_type = Some(match value.to_lowercase().as_str() {
+                        "human" => AuthorType::Human,
+                        "ai" => AuthorType::Ai,
+                        "system" => AuthorType::System,
+                        _ => bail!(
+                            "Unknown author '{}' (use author:human, author:ai or author:system)",
+                            value
+                        ),
+                    });
+                    continue;
+                }
+                "prop" => {
+                    if sign == Some('-') {
+                        bail!("'{}': properties cannot be excluded", token);
+                    }
+                    let Some((key, val)) = value.split_once('=') else {
+                        bail!("'{}' needs key=value, e.g. prop:severity=high", token);
+                    };
+                    let key = key.trim().to_lowercase();
+                    if key.is_empty() {
+                        bail!("'{}' needs key=value, e.g. prop:severity=high", token);
+                    }
+                    query.properties.push((key, val.trim().to_string()));
+                    continue;
+                }
+                // Unknown prefix: plain text (e.g. "error:", "http://")
+                _ => {}
+            }
+        }
+
+        let term = Term::Word(body.replace('"', ""));
+        match sign {
+            Some('-') => excluded.push(term),
+            Some(_) => required.push(term),
+            None => optional.push(term),
+        }
+    }
+
+    query.fts_match = compile(&optional, &required, &excluded)?;
+    let words = |terms: &[Term]| -> Vec<String> { terms.iter().map(Term::text).collect() };
+    query.required_terms = words(&required);
+    query.exclude_terms = words(&excluded);
+    let positive = [query.required_terms.clone(), words(&optional)].concat();
+    if !positive.is_empty() {
+        query.text = Some(positive.join(" "));
+    }
+
+    Ok(query)
+}
+
+/// Split on whitespace, keeping quoted parts together
+fn tokenize(input: &str) -> Result<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in input.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                current.push(c);
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if in_quotes {
+        bail!("Unterminated quote in query: {}", input.trim());
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    Ok(tokens)
+}
+
+/// FTS5 expression: required terms AND-ed, optional ones OR-ed, exclusions
+/// as NOT
+fn compile(optional: &[Term], required: &[Term], excluded: &[Term]) -> Result<Option<String>> {
+    let or = |terms: &[Term]| -> String {
+        terms
+            .iter()
+            .map(Term::to_fts)
+            .collect::<Vec<_>>()
+            .join(" OR ")
+    };
+    let and = |terms: &[Term]| -> String {
+        terms
+            .iter()
+            .map(Term::to_fts)
+            .collect::<Vec<_>>()
+            .join(" AND ")
+    };
+
+    let positive = match (required.is_empty(), optional.is_empty()) {
+        (true, true) => None,
+        (true, false) => Some(or(optional)),
+        (false, true) => Some(and(required)),
+        // The OR group always matches (it repeats a required term), so
+        // optional words only add to the BM25 rank
+        (false, false) => Some(format!(
+            "{} AND ({} OR {})",
+            and(required),
+            required[0].to_fts(),
+            or(optional)
+        )),
+    };
+
+    let Some(positive) = positive else {
+        if let Some(first) = excluded.first() {
+            bail!(
+                "'-{}' needs at least one word to search for, e.g. 'timeout -{}'",
+                first.text(),
+                first.text()
+            );
+        }
+        return Ok(None);
+    };
+
+    let mut expr = format!("({})", positive);
+    for term in excluded {
+        expr.push_str(&format!(" NOT {}", term.to_fts()));
+    }
+    Ok(Some(expr))
+}
+
+/// `path:` value as a prefix: `@a/b`, `@a/b/` and `@a/b/**` all mean
+/// "at or under @a/b"
+fn parse_path(value: &str) -> Result<String> {
+    let prefix = value.trim_end_matches("/**").trim_end_matches('/');
+    if prefix.is_empty() {
+        bail!("'path:' needs a path, e.g. path:@products/api");
+    }
+    if prefix.contains('*') {
+        bail!(
+            "'path:{}': wildcards are only supported at the end (path:@products/**)",
+            value
+        );
+    }
+    Ok(prefix.to_string())
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_full_query() -> Result<()> {
+        let q = parse(
+            r#"timeout tag:api path:@products/** -deprecated "connection reset" trust>=0.6 author:human"#,
+        )?;
+        assert_eq!(
+            q.fts_match.as_deref(),
+            Some(r#"("connection reset" AND ("connection reset" OR "timeout")) NOT "deprecated""#)
+        );
+        assert_eq!(q.text.as_deref(), Some("connection reset timeout"));
+        assert_eq!(q.required_terms, vec!["connection reset"]);
+        assert_eq!(q.tags, vec!["api"]);
+        assert_eq!(q.path_prefix.as_deref(), Some("@products"));
+        assert_eq!(q.min_trust, Some(0.6));
+        assert_eq!(q.author_type, Some(AuthorType::Human));
+        assert_eq!(q.exclude_terms, vec!["deprecated"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_plain_and_filters_only() -> Result<()> {
+        // Plain words keep the forgiving OR behaviour
+        let q = parse("api timeout")?;
+        assert_eq!(q.fts_match.as_deref(), Some(r#"("api" OR "timeout")"#));
+
+        // Filters alone need no text
+        let q = parse("-tag:legacy prop:Severity=high")?;
+        assert_eq!(q.fts_match, None);
+        assert_eq!(q.not_tags, vec!["legacy"]);
+        assert_eq!(q.properties, vec![("severity".into(), "high".into())]);
+
+        // Unknown prefixes are plain words
+        let q = parse("error:")?;
+        assert_eq!(q.fts_match.as_deref(), Some(r#"("error:")"#));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = |q: &str| parse(q).unwrap_err().to_string();
+        assert!(err(r#"timeout "connection reset"#).contains("Unterminated quote"));
+        assert!(err("trust>high").contains("number from 0 to 1"));
+        assert!(err("author:robot").contains("Unknown author"));
+        assert!(err("-legacy").contains("at least one word"));
+        assert!(err("path:@a/*/api").contains("only supported at the end"));
+        assert!(err("prop:severity").contains("key=value"));
+        assert!(err("timeout -").contains("must be followed"));
+    }
+}
@@ -0,0 +1,332 @@
+//! Related facts - "more like this" for one fact
+//!
+//! Other active facts are scored by four signals, each 0-1 and weighted:
+//!
+//! | Signal | Weight | Score |
+//! |--------|--------|-------|
+//! | Link | 1.0 | references or extensions, either way |
+//! | Tags | 0.8 | shared tags / all tags of both (Jaccard) |
+//! | Text | 0.8 | BM25 on the fact's top terms, relative to the fact itself (square root) |
+//! | Path | 0.5 | shared leading segments / depth of the deeper path |
+//!
+//! Used by `meh related`, MCP `related` and `get` with `include_related`.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use ulid::Ulid;
+
+use super::embed::STOPWORDS;
+use super::fact::{Fact, FactType, Status};
+use super::path::Path;
+use super::search::SearchQuery;
+use super::storage::Storage;
+
+const LINK_WEIGHT: f32 = 1.0;
+const TAG_WEIGHT: f32 = 0.8;
+const TEXT_WEIGHT: f32 = 0.8;
+const PATH_WEIGHT: f32 = 0.5;
+
+/// Below this a fact is not worth listing (e.g. only a shared top-level
+/// path segment)
+const MIN_SCORE: f32 = 0.2;
+
+/// Terms of the fact searched for text similarity
+const TOP_TERMS: usize = 8;
+
+/// Candidates fetched per signal
+const CANDIDATES: usize = 50;
+
+/// A fact related to another one
+#[derive(Debug)]
+pub struct RelatedFact {
+    pub fact: Fact,
+    /// Sum of the weighted signals (0-3.1), higher is more related
+    pub score: f32,
+    /// What connects the two, e.g. "links to it", "tags: api"
+    pub reasons: Vec<String>,
+    /// Linked either way (already listed with the fact's links)
+    pub linked: bool,
+}
+
+/// Candidate with its signals so far
+#[derive(Default)]
+struct Signals {
+    link: Option<&'static str>,
+    text: f32,
+}
+
+/// Candidates by id, each with its link and text signals
+type Candidates = HashMap<Ulid, (Fact, Signals)>;
+
+/// Record a candidate (once) and return its signals
+fn add(candidates: &mut Candidates, other: Fact) -> &mut Signals {
+    &mut candidates
+        .entry(other.id)
+        .or_insert_with(|| (other, Signals::default()))
+        .1
+}
+
+/// Facts related to `fact`, most related first; with `exclude_linked` facts
+/// linked either way are left out before the limit is applied
+pub fn related(
+    storage: &Storage,
+    fact: &Fact,
+    limit: usize,
+    exclude_linked: bool,
+) -> Result<Vec<RelatedFact>> {
+    let mut candidates = Candidates::new();
+
+    // Explicit links
+    for (_, target) in storage.references_from(&fact.id)? {
+        if let Some(target) = target {
+            add(&mut candidates, target).link = Some("linked from it");
+        }
+    }
+    for source in storage.backlinks(fact)? {
+        add(&mut candidates, source).link = Some("links to it");
+    }
+    for id in &fact.extends {
+        if let Some(target) = storage.get_by_id(id)? {
+            add(&mut candidates, target).link = Some("extended by it");
+        }
+    }
+    for extension in storage.get_extensions(&fact.id)? {
+        add(&mut candidates, extension).link = Some("extends it");
+    }
+
+    // Shared tags
+    for tag in &fact.tags {
+        let query = SearchQuery::default()
+            .with_tags(vec![tag.clone()])
+            .with_limit(CANDIDATES);
+        for (other, _) in storage.search_query(&query)? {
+            add(&mut candidates, other);
+        }
+    }
+
+    // Nearby paths
+    if let Ok(path) = Path::parse(&fact.path) {
+        if let Some(parent) = path.parent().filter(|p| !p.is_root()) {
+            let pattern = format!("{}/**", parent.segments().join("/"));
+            for other in storage.find_by_pattern(&pattern, &[], &[], CANDIDATES)? {
+                add(&mut candidates, other);
+            }
+        }
+    }
+
+    // Similar text
+    let terms = top_terms(fact);
+    if !terms.is_empty() {
+        let query = SearchQuery {
+            fts_match: Some(
+                terms
+                    .iter()
+                    .map(|t| format!("\"{}\"", t))
+                    .collect::<Vec<_>>()
+                    .join(" OR "),
+            ),
+            text: Some(terms.join(" ")),
+            limit: CANDIDATES + 1,
+            ..Default::default()
+        };
+        let hits = storage.search_query(&query)?;
+        // The fact is the best possible match for its own terms; its title
+        // weighs heavily in BM25, so the ratio is softened with a square root
+        let reference = hits
+            .iter()
+            .find(|(f, _)| f.id == fact.id)
+            .or(hits.first())
+            .map(|(_, score)| *score)
+            .unwrap_or_default();
+        for (other, score) in hits {
+            let similarity = if reference < 0.0 {
+                (score / reference).clamp(0.0, 1.0).sqrt() as f32
+            } else {
+                0.0
+            };
+            add(&mut candidates, other).text = similarity;
+        }
+    }
+
+    let path = Path::parse(&fact.path).ok();
+    let mut related: Vec<RelatedFact> = candidates
+        .into_values()
+        .filter(|(other, _)| {
+            other.id != fact.id
+                && other.status == Status::Active
+                && other.fact_type != FactType::Warning
+        })
+        .filter(|(_, signals)| !(exclude_linked && signals.link.is_some()))
+        .filter_map(|(other, signals)| {
+            let mut score = 0.0;
+            let mut reasons = Vec::new();
+
+            if let Some(link) = signals.link {
+                score += LINK_WEIGHT;
+                reasons.push(link.to_string());
+            }
+
+            let shared: Vec<&String> = other
+                .tags
+                .iter()
+                .filter(|t| fact.tags.contains(t))
+                .collect();
+            if !shared.is_empty() {
+                let union = fact.tags.len() + other.tags.len() - shared.len();
+                score += TAG_WEIGHT * shared.len() as f32 / union as f32;
+                let names: Vec<&str> = shared.iter().map(|t| t.as_str()).collect();
+                reasons.push(format!("tags: {}", names.join(", ")));
+            }
+
+            if signals.text > 0.0 {
+                score += TEXT_WEIGHT * signals.text;
+                reasons.push("similar text".to_string());
+            }
+
+            if let (Some(a), Ok(b)) = (&path, Path::parse(&other.path)) {
+                let common = a.common_depth(&b);
+                if common > 0 {
+                    let proximity = common as f32 / a.depth().max(b.depth()) as f32;
+                    score += PATH_WEIGHT * proximity;
+                    reasons.push("nearby path".to_string());
+                }
+            }
+
+            (score >= MIN_SCORE).then_some(RelatedFact {
+                fact: other,
+                score,
+                reasons,
+                linked: signals.link.is_some(),
+            })
+        })
+        .collect();
+
+    related.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(b.fact.trust_score.total_cmp(&a.fact.trust_score))
+    });
+    related.truncate(limit);
+    Ok(related)
+}
+
+/// The fact's most frequent words (title words count double), without
+/// stopwords, short words and numbers
+fn top_terms(fact: &Fact) -> Vec<String> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for (text, weight) in [(&fact.title, 2), (&fact.content, 1)] {
+        for word in text
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| w.chars().count() >= 3 && !STOPWORDS.contains(w))
+            .filter(|w| !w.chars().all(|c| c.is_ascii_digit()))
+        {
+            *counts.entry(word.to_string()).or_default() += weight;
+        }
+    }
+
+    let mut terms: Vec<(String, usize)> = counts.into_iter().collect();
+    // Most frequent first; longer words say more on ties
+    terms.sort_by(|a, b| {
+        b.1.cmp(&a.1)
+            .then(b.0.len().cmp(&a.0.len()))
+            .then(a.0.cmp(&b.0))
+    });
+    terms.into_iter().take(TOP_TERMS).map(|(t, _)| t).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_related_ranks_by_signals() -> Result<()> {
+        let storage = Storage::open_memory()?;
+        let mut fact = Fact::new(
+            "@api/auth/tokens",
+            "Token expiry",
+            "Access tokens expire after 15 minutes; refresh tokens after 30 days.",
+        );
+        fact.tags = vec!["auth".into(), "api".into()];
+        storage.insert(&fact)?;
+
+        let linking = Fact::new(
+            "@ops/runbook",
+            "Runbook",
+            "On login errors check [[@api/auth/tokens]] first.",
+        );
+        storage.insert(&linking)?;
+        let mut tagged = Fact::new("@web/session", "Sessions", "Sessions live in Redis.");
+        tagged.tags = vec!["auth".into()];
+        storage.insert(&tagged)?;
+        let sibling = Fact::new(
+            "@api/auth/scopes",
+            "Scopes",
+            "Scopes limit what a client may do.",
+        );
+        storage.insert(&sibling)?;
+        let similar = Fact::new(
+            "@mobile/offline",
+            "Offline mode",
+            "The app keeps refresh tokens so access tokens can be renewed offline.",
+        );
+        storage.insert(&similar)?;
+        storage.insert(&Fact::new(
+            "@hr/holidays",
+            "Holidays",
+            "Office closed in August.",
+        ))?;
+
+        let related = related(&storage, &fact, 10, false)?;
+        let paths: Vec<&str> = related.iter().map(|r| r.fact.path.as_str()).collect();
+        assert_eq!(paths[0], "@ops/runbook");
+        assert!(related[0].reasons.contains(&"links to it".to_string()));
+        assert!(related[0].linked && !related[1].linked);
+        for path in ["@web/session", "@api/auth/scopes", "@mobile/offline"] {
+            assert!(paths.contains(&path), "{} missing from {:?}", path, paths);
+        }
+        assert!(!paths.contains(&"@hr/holidays"));
+        assert!(!paths.contains(&"@api/auth/tokens"));
+
+        assert_eq!(super::related(&storage, &fact, 1, false)?.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_related_extensions_and_exclude_linked() -> Result<()> {
+        let storage = Storage::open_memory()?;
+        let mut fact = Fact::new("@api/limits", "Rate limits", "100 requests per minute.");
+        fact.tags = vec!["api".into()];
+        storage.insert(&fact)?;
+
+        let mut extension = Fact::new("@api/limits", "Burst", "Bursts of 20 are allowed.");
+        extension.extends = vec![fact.id];
+        storage.insert(&extension)?;
+        let mut tagged = Fact::new("@web/cdn", "CDN", "Static files come from the CDN.");
+        tagged.tags = vec!["api".into()];
+        storage.insert(&tagged)?;
+
+        let all = related(&storage, &fact, 1, false)?;
+        assert_eq!(all[0].fact.id, extension.id);
+        assert!(all[0].linked && all[0].reasons.contains(&"extends it".to_string()));
+
+        let unlinked = related(&storage, &fact, 1, true)?;
+        assert_eq!(unlinked.len(), 1);
+        assert_eq!(unlinked[0].fact.id, tagged.id);
+        Ok(())
+    }
+
+    #[test]
+    fn test_top_terms() {
+        let fact = Fact::new(
+            "@a",
+            "Pool size",
+            "The pool holds 20 connections to the pool.",
+        );
+        let terms = top_terms(&fact);
+        assert_eq!(terms[0], "pool");
+        assert!(!terms.contains(&"the".to_string()));
+        assert!(!terms.contains(&"20".to_string()));
+    }
+}
@@ -0,0 +1,272 @@
+//! Schemas - per-path rules for facts
+//!
+//! Configured as `[[schemas]]` in config.toml. A schema applies to every
+//! path matching its glob (see `Path::matches_pattern`) and lists the
+//! markdown sections, properties and tags such facts must have, plus a
+//! maximum length.
+//!
+//! # Key Points
+//! - Checked on `meh add` and MCP `add` / `correct`
+//! - A violation rejects the write, or with `on_violation = "review"`
+//!   stores it as pending_review
+//! - `template()` is the skeleton to fill in (`meh template show`)
+
+use serde::{Deserialize, Serialize};
+
+use super::fact::Fact;
+use super::path::Path;
+
+/// Rules for facts under a path pattern
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FactSchema {
+    /// Path glob, e.g. `@project/bugs/**`
+    pub pattern: String,
+
+    /// Markdown sections (headings) the content must contain
+    #[serde(default)]
+    pub sections: Vec<String>,
+
+    /// Property keys the fact must carry
+    #[serde(default)]
+    pub properties: Vec<String>,
+
+    /// Tags the fact must carry
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// Maximum content length (characters)
+    #[serde(default)]
+    pub max_length: Option<usize>,
+
+    /// What a violating write does
+    #[serde(default)]
+    pub on_violation: SchemaAction,
+}
+
+/// What a write that violates a schema does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SchemaAction {
+    /// Refuse the write
+    #[default]
+    Reject,
+    /// Store it as pending_review
+    Review,
+}
+
+impl FactSchema {
+    /// Whether this schema applies to facts at `path`
+    pub fn applies_to(&self, path: &str) -> bool {
+        Path::parse(path)
+            .map(|p| p.matches_pattern(&self.pattern))
+            .unwrap_or(false)
+    }
+
+    /// Everything `fact` is missing or gets wrong, empty if it conforms
+    pub fn violations(&self, fact: &Fact) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        let headings = headings(&fact.content);
+        for section in &self.sections {
+            if !headings.contains(&section.trim().to_lowercase()) {
+                problems.push(format!("Missing section: ## {}", section));
+            }
+        }
+        for key in &self.properties {
+            if !fact.properties.contains_key(&key.to_lowercase()) {
+                problems.push(format!("Missing property: {}", key));
+            }
+        }
+        for tag in &self.tags {
+            if !fact.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                problems.push(format!("Missing tag: {}", tag));
+            }
+        }
+        if let Some(max) = self.max_length {
+            let len = fact.content.chars().count();
+            if len > max {
+                problems.push(format!("Content is {} characters, max is {}", len, max));
+            }
+        }
+
+        problems
+    }
+}
+
+/// Schemas that apply to facts at `path`
+pub fn matching<'a>(schemas: &'a [FactSchema], path: &str) -> Vec<&'a FactSchema> {
+    schemas.iter().filter(|s| s.applies_to(path)).collect()
+}
+
+/// Markdown skeleton with every section the schemas require
+pub fn template(schemas: &[&FactSchema]) -> String {
+    let mut out = String::from("# <Title>\n");
+    let mut seen: Vec<String> = Vec::new();
+    for section in schemas.iter().flat_map(|s| &s.sections) {
+        if seen.contains(&section.to_lowercase()) {
+            continue;
+        }
+        seen.push(section.to_lowercase());
+        out.push_str(&format!("\n## {}\n", section));
+    }
+    out
+}
+
+/// Why a fact was refused (or sent to review) by its schemas
+#[derive(Debug, Clone)]
+pub struct SchemaViolation {
+    /// Path of the offending fact
+    pub path: String,
+    /// Patterns of the violated schemas
+    pub patterns: Vec<String>,
+    /// One line per problem
+    pub problems: Vec<String>,
+    /// Review only if every violated schema asks for review
+    pub action: SchemaAction,
+    /// Skeleton to fill in (empty if no sections are required)
+    pub template: String,
+}
+
+impl SchemaViolation {
+    /// Explanation listing each problem and the template to follow
+    pub fn explanation(&self) -> String {
+        let mut out = format!(
+            "Fact at {} does not match schema {}:\n",
+            self.path,
+            self.patterns.join(", ")
+        );
+        for problem in &self.problems {
+            out.push_str(&format!("  - {}\n", problem));
+        }
+        if !self.template.is_empty() {
+            out.push_str("Use this structure:\n");
+            out.push_str(&self.template);
+        }
+        out.trim_end().to_string()
+    }
+}
+
+/// Check `fact` against every schema for its path
+pub fn check(schemas: &[FactSchema], fact: &Fact) -> Option<SchemaViolation> {
+    let applicable = matching(schemas, &fact.path);
+
+    let mut patterns = Vec::new();
+    let mut problems = Vec::new();
+    let mut action = SchemaAction::Review;
+    for schema in &applicable {
+        let found = schema.violations(fact);
+        if found.is_empty() {
+            continue;
+        }
+        patterns.push(schema.pattern.clone());
+        problems.extend(found);
+        if schema.on_violation == SchemaAction::Reject {
+            action = SchemaAction::Reject;
+        }
+    }
+
+    if problems.is_empty() {
+        return None;
+    }
+    let has_sections = applicable.iter().any(|s| !s.sections.is_empty());
+    Some(SchemaViolation {
+        path: fact.path.clone(),
+        patterns,
+        problems,
+        action,
+        template: if has_sections {
+            template(&applicable)
+        } else {
+            String::new()
+        },
+    })
+}
+
+/// Lowercased text of every markdown heading
+fn headings(content: &str) -> Vec<String> {
+    let mut in_fence = false;
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim_start();
+            // `#` lines inside fenced code blocks are code, not headings
+            if line.starts_with("```") || line.starts_with("~~~") {
+                in_fence = !in_fence;
+                return None;
+            }
+            if in_fence {
+                return None;
+            }
+            let text = line.strip_prefix('#')?;
+            let text = text.trim_start_matches('#');
+            Some(text.trim().trim_end_matches(':').trim().to_lowercase())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bug_schema() -> FactSchema {
+        FactSchema {
+            pattern: "@project/bugs/**".to_string(),
+            sections: vec!["Symptom".into(), "Cause".into(), "Fix".into()],
+            properties: vec!["severity".into()],
+            tags: vec!["bug".into()],
+            max_length: Some(200),
+            on_violation: SchemaAction::Reject,
+        }
+    }
+
+    #[test]
+    fn test_schema_check() {
+        let schemas = vec![bug_schema()];
+
+        let mut fact = Fact::new(
+            "@project/bugs/login",
+            "Login crash",
+            "# Login crash\n## Symptom\nCrash\n### cause:\nNull token\n## Fix\nCheck it",
+        );
+        fact.tags = vec!["Bug".to_string()];
+        fact.properties
+            .insert("severity".to_string(), "high".to_string());
+        assert!(check(&schemas, &fact).is_none());
+
+        fact.content = "## Symptom\nCrash".to_string();
+        fact.properties.clear();
+        let violation = check(&schemas, &fact).unwrap();
+        assert_eq!(violation.action, SchemaAction::Reject);
+        assert_eq!(
+            violation.problems,
+            vec![
+                "Missing section: ## Cause",
+                "Missing section: ## Fix",
+                "Missing property: severity"
+            ]
+        );
+        assert!(violation.explanation().contains("## Fix"));
+
+        // Comments in code blocks are not sections
+        fact.content = "## Symptom\nCrash\n```sh\n# Cause\n# Fix\n```".to_string();
+        let violation = check(&schemas, &fact).unwrap();
+        assert!(violation
+            .problems
+            .contains(&"Missing section: ## Fix".to_string()));
+
+        // Other paths are not affected
+        let other = Fact::new("@project/docs/login", "Login", "Anything");
+        assert!(check(&schemas, &other).is_none());
+    }
+
+    #[test]
+    fn test_template() {
+        let schemas = vec![bug_schema()];
+        let applicable = matching(&schemas, "@project/bugs");
+        assert_eq!(applicable.len(), 1);
+        assert_eq!(
+            template(&applicable),
+            "# <Title>\n\n## Symptom\n\n## Cause\n\n## Fix\n"
+        );
+    }
+}
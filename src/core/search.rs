@@ -1,21 +1,45 @@
 //! Search - Full-text search engine
 //!
-//! Uses SQLite FTS5 with BM25 ranking.
+//! Uses SQLite FTS5 with BM25 ranking. KBs with `fuzzy = true` also keep a
+//! trigram index: queries with few hits (or `fuzzy` set) fall back to it,
+//! so misspelled or partial words ("postgress", "authenticat") still match.
+//! With an embedder set (`--semantic`), BM25 and vector similarity rankings
+//! are fused with reciprocal rank fusion (see `embed`).
+//!
+//! Results can carry an FTS5 snippet: the words around the best match,
+//! with matched words marked (see `render_snippet`).
 //!
 //! # Architecture
 //! See `../../plan/ANALYSIS_AUTO_CONTEXT_SEARCH.md`
 
-use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
 
-use super::fact::Fact;
+use super::embed::{self, Embedder};
+use super::fact::{AuthorType, Fact, FactType, Status};
 use super::storage::Storage;
 
 /// Search query builder
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct SearchQuery {
     /// Full-text query
     pub text: Option<String>,
 
+    /// Ready-made FTS5 `MATCH` expression, used instead of escaping `text`
+    /// (set by `SearchQuery::parse`)
+    pub fts_match: Option<String>,
+
+    /// Words and phrases that must appear
+    pub required_terms: Vec<String>,
+
+    /// Words and phrases that must not appear
+    pub exclude_terms: Vec<String>,
+
+    /// Author type filter
+    pub author_type: Option<AuthorType>,
+
     /// Path prefix filter
     pub path_prefix: Option<String>,
 
@@ -25,17 +49,35 @@ pub struct SearchQuery {
     /// Excluded tags
     pub not_tags: Vec<String>,
 
+    /// Required properties (key, value), AND logic
+    pub properties: Vec<(String, String)>,
+
     /// Minimum trust score
     pub min_trust: Option<f32>,
 
     /// Include deprecated facts
     pub include_deprecated: bool,
 
+    /// Include facts past their `valid_until`
+    pub include_expired: bool,
+
+    /// Search every namespace instead of the storage's own
+    pub all_namespaces: bool,
+
     /// Maximum results
     pub limit: usize,
 
     /// Token budget (for AI context)
     pub token_budget: Option<usize>,
+
+    /// Always add fuzzy (trigram) matches, not only when few facts match
+    pub fuzzy: bool,
+
+    /// Hybrid search: fuse BM25 with similarity to this embedder's vectors
+    pub embedder: Option<Arc<dyn Embedder>>,
+
+    /// Attach snippets of up to this many words to the results
+    pub snippet_words: Option<usize>,
 }
 
 impl SearchQuery {
@@ -47,6 +89,12 @@ impl SearchQuery {
         }
     }
 
+    /// Parse the query language (`tag:api path:@a/** -legacy "a phrase"
+    /// trust>0.6 author:human`, see `query` module)
+    pub fn parse(input: &str) -> Result<Self> {
+        super::query::parse(input)
+    }
+
     pub fn with_path(mut self, path: impl Into<String>) -> Self {
         self.path_prefix = Some(path.into());
         self
@@ -57,6 +105,17 @@ impl SearchQuery {
         self
     }
 
+    pub fn without_tags(mut self, tags: Vec<String>) -> Self {
+        self.not_tags = tags;
+        self
+    }
+
+    /// Require a property value (e.g. `severity=high`)
+    pub fn with_property(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.properties.push((key.into(), value.into()));
+        self
+    }
+
     pub fn with_limit(mut self, limit: usize) -> Self {
         self.limit = limit;
         self
@@ -67,69 +126,386 @@ impl SearchQuery {
         self
     }
 
+    pub fn with_token_budget(mut self, budget: usize) -> Self {
+        self.token_budget = Some(budget);
+        self
+    }
+
     pub fn include_deprecated(mut self) -> Self {
         self.include_deprecated = true;
         self
     }
+
+    pub fn include_expired(mut self) -> Self {
+        self.include_expired = true;
+        self
+    }
+
+    pub fn all_namespaces(mut self) -> Self {
+        self.all_namespaces = true;
+        self
+    }
+
+    pub fn fuzzy(mut self) -> Self {
+        self.fuzzy = true;
+        self
+    }
+
+    /// Rank by meaning as well as keywords (see `embed`)
+    pub fn semantic(mut self, embedder: Arc<dyn Embedder>) -> Self {
+        self.embedder = Some(embedder);
+        self
+    }
+
+    /// Attach highlighted snippets of up to `words` words (0 = none)
+    pub fn with_snippets(mut self, words: usize) -> Self {
+        self.snippet_words = Some(words);
+        self
+    }
+
+    /// Whether `fact` passes the filters (for backends that cannot run
+    /// them, e.g. remote KBs; optional words are not checked)
+    pub fn matches(&self, fact: &Fact, now: chrono::DateTime<chrono::Utc>) -> bool {
+        let status_ok = fact.status == Status::Active
+            || (self.include_deprecated && fact.status == Status::Deprecated);
+        let path_ok = self
+            .path_prefix
+            .as_ref()
+            .is_none_or(|p| at_or_under(&fact.path, p));
+        let text = words(&format!("{}\n{}", fact.title, fact.content));
+
+        status_ok
+            && path_ok
+            && (self.include_expired || !fact.is_expired(now))
+            && self.min_trust.is_none_or(|min| fact.trust_score >= min)
+            && self.author_type.is_none_or(|a| fact.author_type == a)
+            && self.required_terms.iter().all(|t| contains_words(&text, t))
+            && !self.exclude_terms.iter().any(|t| contains_words(&text, t))
+            && self.tags.iter().all(|t| fact.tags.contains(t))
+            && !self.not_tags.iter().any(|t| fact.tags.contains(t))
+            && self
+                .properties
+                .iter()
+                .all(|(k, v)| fact.properties.get(k) == Some(v))
+    }
+}
+
+/// Whether `path` is `prefix` or below it (whole segments: `@api` covers
+/// `@api/auth` but not `@apiv2`)
+fn at_or_under(path: &str, prefix: &str) -> bool {
+    let prefix = prefix.trim_end_matches('/');
+    prefix.is_empty()
+        || path
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Lowercased words of `text`, split like the FTS tokenizer does (on
+/// anything that is not a letter or digit)
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Whether the words of `term` appear in `text`, in order and next to each
+/// other (so "reset" does not match "preset")
+fn contains_words(text: &[String], term: &str) -> bool {
+    let term = words(term);
+    !term.is_empty() && text.windows(term.len()).any(|w| w == term.as_slice())
 }
 
 /// Search result with relevance score
 #[derive(Debug)]
 pub struct SearchResult {
     pub fact: Fact,
+    /// Negated BM25 score, higher is better (`0.0` for searches without
+    /// text; the fused RRF score for semantic searches; fuzzy matches get
+    /// `similarity - 1.0`, below every other hit)
     pub relevance: f32,
+    /// Estimated tokens to show the result (with its snippet instead of the
+    /// content, if it has one)
     pub token_count: usize,
+    /// Found through the trigram index, not an exact word match
+    pub fuzzy: bool,
+    /// Words around the best match, matched words between
+    /// `HIGHLIGHT_START` and `HIGHLIGHT_END` (if snippets were asked for
+    /// and the text matched; see `render_snippet`)
+    pub snippet: Option<String>,
 }
 
+/// Start of a matched word in a snippet
+pub const HIGHLIGHT_START: char = '\u{2}';
+
+/// End of a matched word in a snippet
+pub const HIGHLIGHT_END: char = '\u{3}';
+
+/// Snippet on one line, each matched word passed through `mark`
+pub fn render_snippet(snippet: &str, mark: impl Fn(&str) -> String) -> String {
+    let mut parts = snippet.split(HIGHLIGHT_START);
+    let mut rendered = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        match part.split_once(HIGHLIGHT_END) {
+            Some((matched, rest)) => {
+                rendered.push_str(&mark(matched));
+                rendered.push_str(rest);
+            }
+            None => rendered.push_str(part),
+        }
+    }
+    rendered.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Drop warnings whose target is also among `facts`: they are shown
+/// under that fact instead of as hits of their own
+pub fn fold_warnings(facts: Vec<Fact>) -> Vec<Fact> {
+    let ids: HashSet<ulid::Ulid> = facts.iter().map(|f| f.id).collect();
+    facts
+        .into_iter()
+        .filter(|f| f.fact_type != FactType::Warning || !f.extends.iter().any(|t| ids.contains(t)))
+        .collect()
+}
+
+/// Fall back to fuzzy matching when an exact search finds fewer facts
+const FUZZY_FALLBACK_BELOW: usize = 3;
+
+/// Minimum similarity (0-1) between the query words and a fuzzy match
+const FUZZY_MIN_SIMILARITY: f32 = 0.5;
+
 /// Execute a search query
+///
+/// All filters run in SQL before the LIMIT, so a filtered search still
+/// returns up to `limit` results. Fuzzy matches (if the KB has `fuzzy`
+/// set, see `Storage::with_fuzzy`) come after the exact ones. Honors
+/// `token_budget`.
 pub fn search(storage: &Storage, query: &SearchQuery) -> Result<Vec<SearchResult>> {
-    // TODO: Implement full search with all filters
-    // For now, just use basic FTS search
+    let mut results = match (&query.embedder, &query.text) {
+        (Some(embedder), Some(_)) => hybrid_search(storage, query, embedder.as_ref())?,
+        _ => storage
+            .search_query(query)?
+            .into_iter()
+            .map(|(fact, score)| {
+                let token_count = estimate_tokens(&fact);
+                SearchResult {
+                    fact,
+                    relevance: -score as f32,
+                    token_count,
+                    fuzzy: false,
+                    snippet: None,
+                }
+            })
+            .collect(),
+    };
+
+    let few = results.len() < FUZZY_FALLBACK_BELOW.min(query.limit);
+    if (query.fuzzy || few) && results.len() < query.limit {
+        if storage.fuzzy() {
+            let seen: HashSet<_> = results.iter().map(|r| r.fact.id).collect();
+            let room = query.limit - results.len();
+            results.extend(
+                fuzzy_search(storage, query)?
+                    .into_iter()
+                    .filter(|r| !seen.contains(&r.fact.id))
+                    .take(room),
+            );
+        } else if query.fuzzy {
+            bail!("Fuzzy search is off for this KB: set `fuzzy = true` for it in config.toml");
+        }
+    }
 
-    let text = query.text.as_deref().unwrap_or("*");
-    let facts = storage.search(text, query.limit as i64)?;
+    if let Some(words) = query.snippet_words.filter(|w| *w > 0) {
+        add_snippets(storage, query, &mut results, words)?;
+    }
 
-    let results: Vec<SearchResult> = facts
-        .into_iter()
-        .filter(|f| {
-            // Apply path filter
-            if let Some(prefix) = &query.path_prefix {
-                if !f.path.starts_with(prefix.trim_end_matches('/')) {
-                    return false;
-                }
-            }
+    Ok(match query.token_budget {
+        Some(budget) => truncate_to_budget(results, budget),
+        None => results,
+    })
+}
 
-            // Apply trust filter
-            if let Some(min) = query.min_trust {
-                if f.trust_score < min {
-                    return false;
-                }
-            }
+/// Snippets for the exact matches (trigram matches would highlight
+/// fragments of words, so fuzzy ones keep their summary)
+fn add_snippets(
+    storage: &Storage,
+    query: &SearchQuery,
+    results: &mut [SearchResult],
+    words: usize,
+) -> Result<()> {
+    let ids: Vec<_> = results
+        .iter()
+        .filter(|r| !r.fuzzy)
+        .map(|r| r.fact.id)
+        .collect();
+    let mut snippets = storage.snippets(query, &ids, words)?;
+    for result in results.iter_mut() {
+        if let Some(snippet) = snippets.remove(&result.fact.id) {
+            result.token_count = estimate_tokens_with(&result.fact, &snippet);
+            result.snippet = Some(snippet);
+        }
+    }
+    Ok(())
+}
 
-            // Apply tag filter
-            if !query.tags.is_empty() && !query.tags.iter().all(|t| f.tags.contains(t)) {
-                return false;
-            }
+/// BM25 and vector similarity rankings fused with reciprocal rank fusion
+///
+/// Only reads: facts without a vector from `embedder` (see
+/// `embed::embed_written`) are found by keywords alone. Vectors are
+/// compared by brute force, which is fine for a local knowledge base.
+fn hybrid_search(
+    storage: &Storage,
+    query: &SearchQuery,
+    embedder: &dyn Embedder,
+) -> Result<Vec<SearchResult>> {
+    // Rank deeper than the limit, so facts found by only one side can
+    // still make it
+    let depth = (query.limit * 3).max(50);
+    let keyword = storage.search_query(&SearchQuery {
+        limit: depth,
+        ..query.clone()
+    })?;
+
+    let text = query.text.clone().unwrap_or_default();
+    let target = embedder.embed(&[text])?.pop().unwrap_or_default();
+    let now = chrono::Utc::now();
+    let mut similar: Vec<(Fact, f32)> = storage
+        .embedded_facts(query, &embedder.model())?
+        .into_iter()
+        // Phrases and exclusions are enforced by FTS on the keyword side
+        .filter(|(fact, _)| query.matches(fact, now))
+        .map(|(fact, vector)| {
+            let similarity = embed::cosine(&target, &vector);
+            (fact, similarity)
+        })
+        .filter(|(_, similarity)| *similarity >= embedder.min_similarity())
+        .collect();
+    similar.sort_by(|a, b| b.1.total_cmp(&a.1));
+    similar.truncate(depth);
+
+    let rankings = [
+        keyword.iter().map(|(f, _)| f.id).collect::<Vec<_>>(),
+        similar.iter().map(|(f, _)| f.id).collect::<Vec<_>>(),
+    ];
+    let mut facts: HashMap<_, Fact> = keyword
+        .into_iter()
+        .map(|(f, _)| f)
+        .chain(similar.into_iter().map(|(f, _)| f))
+        .map(|f| (f.id, f))
+        .collect();
 
-            true
+    Ok(embed::reciprocal_rank_fusion(&rankings)
+        .into_iter()
+        .take(query.limit)
+        .filter_map(|(id, score)| {
+            let fact = facts.remove(&id)?;
+            Some(SearchResult {
+                token_count: estimate_tokens(&fact),
+                fact,
+                relevance: score,
+                fuzzy: false,
+                snippet: None,
+            })
         })
-        .map(|f| {
-            let token_count = estimate_tokens(&f);
-            SearchResult {
-                fact: f,
-                relevance: 1.0, // TODO: Get actual BM25 score
-                token_count,
-            }
+        .collect())
+}
+
+/// Facts whose words are close to the query words, best first
+fn fuzzy_search(storage: &Storage, query: &SearchQuery) -> Result<Vec<SearchResult>> {
+    let words: Vec<String> = query
+        .text
+        .as_deref()
+        .unwrap_or_default()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= 3)
+        .map(str::to_lowercase)
+        .collect();
+    let grams: HashSet<String> = words.iter().flat_map(|w| trigrams(w)).collect();
+    if grams.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Any shared trigram makes a candidate; similarity decides
+    let mut expr = grams
+        .iter()
+        .map(|g| format!("\"{}\"", g.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" OR ");
+    expr = format!("({})", expr);
+    for term in query
+        .exclude_terms
+        .iter()
+        .filter(|t| t.chars().count() >= 3)
+    {
+        expr.push_str(&format!(" NOT \"{}\"", term.replace('"', "\"\"")));
+    }
+
+    let candidates = storage.search_trigram(query, &expr, (query.limit * 5).max(50))?;
+    let mut results: Vec<SearchResult> = candidates
+        .into_iter()
+        .filter_map(|(fact, _)| {
+            // Plain words are OR-ed, so one close word is enough; the mean
+            // over all words ranks the matches
+            let scores = word_similarities(&words, &format!("{} {}", fact.title, fact.content));
+            let best = scores.iter().copied().fold(0.0, f32::max);
+            let mean = scores.iter().sum::<f32>() / scores.len() as f32;
+            (best >= FUZZY_MIN_SIMILARITY).then(|| SearchResult {
+                token_count: estimate_tokens(&fact),
+                fact,
+                relevance: mean - 1.0,
+                fuzzy: true,
+                snippet: None,
+            })
         })
         .collect();
+    results.sort_by(|a, b| b.relevance.total_cmp(&a.relevance));
 
     Ok(results)
 }
 
+/// Lowercased character trigrams of a word
+fn trigrams(word: &str) -> HashSet<String> {
+    let chars: Vec<char> = word.to_lowercase().chars().collect();
+    chars.windows(3).map(|w| w.iter().collect()).collect()
+}
+
+/// How close each query word comes to a word of `text` (0-1)
+///
+/// A word scores against its closest word in `text`: the mean of the share
+/// of its trigrams found there (partial words score high) and the trigram
+/// Jaccard index (typos score high).
+fn word_similarities(words: &[String], text: &str) -> Vec<f32> {
+    let text_grams: Vec<HashSet<String>> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= 3)
+        .map(trigrams)
+        .collect();
+
+    words
+        .iter()
+        .map(|word| {
+            let grams = trigrams(word);
+            text_grams
+                .iter()
+                .map(|other| {
+                    let shared = grams.intersection(other).count() as f32;
+                    let union = grams.union(other).count() as f32;
+                    (shared / grams.len() as f32 + shared / union) / 2.0
+                })
+                .fold(0.0, f32::max)
+        })
+        .collect()
+}
+
 /// Estimate token count for a fact
 fn estimate_tokens(fact: &Fact) -> usize {
+    estimate_tokens_with(fact, &fact.content)
+}
+
+/// Estimate token count for a fact shown with `body` instead of its
+/// content (e.g. a snippet)
+fn estimate_tokens_with(fact: &Fact, body: &str) -> usize {
     // Rough estimation: ~4 chars per token
-    let content_tokens = fact.content.len() / 4;
+    let content_tokens = body.len() / 4;
     let title_tokens = fact.title.len() / 4;
     let path_tokens = fact.path.len() / 4;
     let overhead = 20; // Metadata overhead
@@ -157,6 +533,18 @@ pub fn truncate_to_budget(results: Vec<SearchResult>, budget: usize) -> Vec<Sear
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_fold_warnings_keeps_only_orphan_warnings() {
+        let fact = Fact::new("@api/timeout", "Timeout", "Timeout is 30s");
+        let other = Fact::new("@api/retry", "Retry", "Retry 3 times");
+        let folded = Fact::warning(&fact, "Not on /health");
+        let orphan = Fact::warning(&other, "Except uploads");
+
+        let hits = fold_warnings(vec![fact.clone(), folded, orphan.clone()]);
+        let ids: Vec<_> = hits.iter().map(|f| f.id).collect();
+        assert_eq!(ids, vec![fact.id, orphan.id]);
+    }
+
     #[test]
     fn test_estimate_tokens() {
         let mut fact = Fact::new("@test", "Title", "This is content.");
@@ -166,6 +554,293 @@ mod tests {
         assert!(tokens > 0);
     }
 
+    #[test]
+    fn test_property_filter() -> Result<()> {
+        let storage = Storage::open_memory()?;
+        storage.insert(
+            &Fact::new("@bugs/a", "Login crash", "Crash on login")
+                .with_property("severity", "high"),
+        )?;
+        storage.insert(
+            &Fact::new("@bugs/b", "Logout crash", "Crash on logout")
+                .with_property("severity", "low"),
+        )?;
+
+        let query = SearchQuery::new("crash").with_property("severity", "high");
+        let results = search(&storage, &query)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].fact.path, "@bugs/a");
+
+        // No text: served from the property index
+        let query = SearchQuery {
+            limit: 20,
+            ..Default::default()
+        }
+        .with_property("severity", "low");
+        let results = search(&storage, &query)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].fact.path, "@bugs/b");
+
+        // Filtered before the limit: the high-severity match is found even
+        // when better text matches fill the limit
+        for i in 0..3 {
+            storage.insert(&Fact::new(
+                format!("@bugs/crash-{}", i),
+                "Crash crash",
+                "Crash crash crash",
+            ))?;
+        }
+        let query = SearchQuery::new("crash")
+            .with_limit(1)
+            .with_property("severity", "high");
+        let results = search(&storage, &query)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].fact.path, "@bugs/a");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_filters_apply_before_limit() -> Result<()> {
+        let storage = Storage::open_memory()?;
+        for i in 0..5 {
+            storage.insert(&Fact::new(
+                format!("@web/crash-{}", i),
+                "Web crash",
+                "Crash in the web client",
+            ))?;
+        }
+        let mut tagged = Fact::new("@api/crash", "API crash", "Crash when the API restarts");
+        tagged.tags = vec!["api".to_string()];
+        storage.insert(&tagged)?;
+        let mut legacy = Fact::new("@api/old", "Old crash", "Crash in the v1 API");
+        legacy.tags = vec!["api".to_string(), "legacy".to_string()];
+        storage.insert(&legacy)?;
+
+        // The path filter no longer fights the LIMIT
+        let results = search(
+            &storage,
+            &SearchQuery::new("crash").with_limit(2).with_path("@api"),
+        )?;
+        assert_eq!(results.len(), 2);
+
+        let query = SearchQuery::new("crash")
+            .with_tags(vec!["api".to_string()])
+            .without_tags(vec!["legacy".to_string()]);
+        let results = search(&storage, &query)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].fact.id, tagged.id);
+
+        // Title matches outrank content matches; scores are real BM25
+        let results = search(&storage, &SearchQuery::new("api"))?;
+        assert_eq!(results[0].fact.path, "@api/crash");
+        assert!(results[0].relevance > 0.0);
+        assert!(results.windows(2).all(|w| w[0].relevance >= w[1].relevance));
+
+        storage.mark_deprecated(&legacy.id)?;
+        assert_eq!(search(&storage, &SearchQuery::new("v1"))?.len(), 0);
+        assert_eq!(
+            search(&storage, &SearchQuery::new("v1").include_deprecated())?.len(),
+            1
+        );
+
+        let budget = estimate_tokens(&tagged) + 1;
+        let results = search(
+            &storage,
+            &SearchQuery::new("crash").with_token_budget(budget),
+        )?;
+        assert_eq!(results.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parsed_query() -> Result<()> {
+        let storage = Storage::open_memory()?;
+        let mut reset = Fact::new(
+            "@products/api/reset",
+            "Connection reset",
+            "Timeout then connection reset by the proxy",
+        );
+        reset.author_type = AuthorType::Human;
+        storage.insert(&reset)?;
+        storage.insert(&Fact::new(
+            "@products/api/old",
+            "Old timeout",
+            "Deprecated timeout, the connection was reset",
+        ))?;
+        storage.insert(&Fact::new("@ops/timeout", "Ops timeout", "Timeout in ops"))?;
+        let sibling = Fact::new(
+            "@products-old/api",
+            "Old API",
+            "Deprecated timeout of the API",
+        );
+        storage.insert(&sibling)?;
+
+        let run = |q: &str| -> Result<Vec<String>> {
+            Ok(search(&storage, &SearchQuery::parse(q)?)?
+                .into_iter()
+                .map(|r| r.fact.path)
+                .collect())
+        };
+        assert_eq!(run("timeout")?.len(), 4);
+        // Whole segments: @products does not cover @products-old
+        assert_eq!(run("timeout path:@products/**")?.len(), 2);
+        assert_eq!(run("timeout path:@products/api/reset")?.len(), 1);
+        assert_eq!(run(r#""connection reset""#)?, vec!["@products/api/reset"]);
+        assert_eq!(
+            run("timeout -deprecated -ops")?,
+            vec!["@products/api/reset"]
+        );
+        assert_eq!(run("author:human")?, vec!["@products/api/reset"]);
+
+        // Remote post-filtering agrees
+        let now = chrono::Utc::now();
+        let query = SearchQuery::parse(r#"timeout -deprecated "connection reset""#)?;
+        assert!(query.matches(&reset, now));
+        assert!(!query.matches(&Fact::new("@a", "Old", "Deprecated timeout"), now));
+        // Whole words only, as in SQL
+        let preset = Fact::new("@a", "Preset", "Timeouts of the connection, reset-free");
+        assert!(!SearchQuery::parse("+timeout")?.matches(&preset, now));
+        assert!(SearchQuery::parse("timeout -deprecate")?
+            .matches(&Fact::new("@a", "Old", "Deprecated timeout"), now));
+        let products = SearchQuery::parse("path:@products")?;
+        assert!(products.matches(&reset, now));
+        assert!(!products.matches(&sibling, now));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fuzzy_fallback() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let db_path = dir.path().join("data.db");
+        let storage = Storage::open(&db_path)?;
+        storage.insert(&Fact::new(
+            "@db/pool",
+            "Postgres pool",
+            "The postgres pool holds 20 connections",
+        ))?;
+        assert!(search(&storage, &SearchQuery::new("postgress").fuzzy()).is_err());
+
+        // Facts written before and after the index is created are both found
+        let storage = storage.with_fuzzy(true)?;
+        storage.insert(&Fact::new(
+            "@api/auth",
+            "Login flow",
+            "Authentication uses short-lived tokens",
+        ))?;
+
+        let results = search(&storage, &SearchQuery::new("postgress"))?;
+        assert_eq!(results.len(), 1);
+        assert!(results[0].fuzzy);
+        assert_eq!(results[0].fact.path, "@db/pool");
+        let results = search(&storage, &SearchQuery::new("authenticat"))?;
+        assert_eq!(results[0].fact.path, "@api/auth");
+        assert!(search(&storage, &SearchQuery::new("kubernetes"))?.is_empty());
+
+        // Exact hits rank above fuzzy ones
+        let results = search(&storage, &SearchQuery::new("pool authentcation").fuzzy())?;
+        assert_eq!(results.len(), 2);
+        assert!(!results[0].fuzzy && results[1].fuzzy);
+        assert!(results[0].relevance > results[1].relevance);
+
+        // The KB's setting decides, not whether the index exists
+        let other = Storage::open(&db_path)?;
+        assert!(other.has_fuzzy_index()?);
+        assert!(search(&other, &SearchQuery::new("postgress"))?.is_empty());
+
+        let storage = storage.with_fuzzy(false)?;
+        assert!(!storage.has_fuzzy_index()?);
+        assert!(search(&storage, &SearchQuery::new("postgress"))?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_snippets() -> Result<()> {
+        let storage = Storage::open_memory()?;
+        let filler = "Unrelated setup notes about the build. ".repeat(20);
+        storage.insert(&Fact::new(
+            "@ops/deploy",
+            "Deploy notes",
+            format!("{}The deploy fails when the cache volume is full.", filler),
+        ))?;
+
+        let results = search(&storage, &SearchQuery::new("cache").with_snippets(8))?;
+        let snippet = results[0].snippet.as_deref().unwrap();
+        let rendered = render_snippet(snippet, |w| format!("**{}**", w));
+        assert!(rendered.contains("**cache**"), "{}", rendered);
+        assert!(!rendered.contains("Unrelated setup notes about the build. Unrelated"));
+        // The budget counts the snippet, not the whole content
+        assert!(results[0].token_count < estimate_tokens(&results[0].fact));
+
+        // Only content matches make a snippet, not the path
+        storage.insert(&Fact::new("@cache/size", "Size", "Keep it small"))?;
+        let results = search(&storage, &SearchQuery::new("cache").with_snippets(8))?;
+        let by_path = results
+            .iter()
+            .find(|r| r.fact.path == "@cache/size")
+            .unwrap();
+        assert!(by_path.snippet.is_none());
+
+        // Off unless asked for, and never without text
+        assert!(search(&storage, &SearchQuery::new("cache"))?[0]
+            .snippet
+            .is_none());
+        let no_text = SearchQuery::default().with_limit(5).with_snippets(8);
+        assert!(search(&storage, &no_text)?[0].snippet.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_semantic_search() -> Result<()> {
+        let storage = Storage::open_memory()?;
+        storage.insert(&Fact::new(
+            "@auth/jwt",
+            "JWT clock skew",
+            "Login failures after deploy: tokens rejected because server clocks drift",
+        ))?;
+        storage.insert(&Fact::new(
+            "@finance/budget",
+            "Budget",
+            "Quarterly budget spreadsheet",
+        ))?;
+
+        // Keywords alone miss the word forms
+        assert!(search(&storage, &SearchQuery::new("logins failing"))?.is_empty());
+
+        let embedder: Arc<dyn Embedder> = Arc::new(embed::HashedEmbedder::new(512)?);
+        assert_eq!(embed::backfill(&storage, embedder.as_ref())?, 2);
+        let query = SearchQuery::new("why do logins keep failing").semantic(embedder.clone());
+        let results = search(&storage, &query)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].fact.path, "@auth/jwt");
+
+        // Searches only read vectors: a new fact counts once it is embedded
+        let login = Fact::new(
+            "@web/login",
+            "Login page",
+            "The login page fails to load on Safari",
+        );
+        storage.insert(&login)?;
+        let web = query.clone().with_path("@web");
+        assert!(search(&storage, &web)?.is_empty());
+        assert_eq!(
+            storage
+                .facts_without_embedding(&embedder.model(), 10)?
+                .len(),
+            1
+        );
+
+        embed::embed_written(&storage, &embed::EmbeddingConfig::default(), &[&login])?;
+        let results = search(&storage, &web)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].fact.path, "@web/login");
+
+        Ok(())
+    }
+
     #[test]
     fn test_truncate_to_budget() {
         let results = vec![
@@ -173,16 +848,22 @@ mod tests {
                 fact: Fact::new("@a", "A", "Content A"),
                 relevance: 1.0,
                 token_count: 100,
+                fuzzy: false,
+                snippet: None,
             },
             SearchResult {
                 fact: Fact::new("@b", "B", "Content B"),
                 relevance: 0.9,
                 token_count: 100,
+                fuzzy: false,
+                snippet: None,
             },
             SearchResult {
                 fact: Fact::new("@c", "C", "Content C"),
                 relevance: 0.8,
                 token_count: 100,
+                fuzzy: false,
+                snippet: None,
             },
         ];
 
@@ -10,64 +10,102 @@
 //! - Append-only: no UPDATE, only INSERT
 //! - Path index for fast prefix queries
 
+use std::collections::HashMap;
 use std::path::Path as FilePath;
 
 use anyhow::{Context, Result};
-use rusqlite::{params, Connection, OpenFlags};
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{params, Connection, OpenFlags, Transaction, TransactionBehavior};
+use serde::{Deserialize, Serialize};
 use ulid::Ulid;
 
-use super::fact::{AuthorType, Fact, FactType, Status};
-
-/// Database storage
-pub struct Storage {
-    conn: Connection,
-    path: Option<std::path::PathBuf>,
-}
-
-impl Storage {
-    /// Open or create a database
-    pub fn open(path: &FilePath) -> Result<Self> {
-        let conn = Connection::open_with_flags(
-            path,
-            OpenFlags::SQLITE_OPEN_READ_WRITE
-                | OpenFlags::SQLITE_OPEN_CREATE
-                | OpenFlags::SQLITE_OPEN_NO_MUTEX,
-        )
-        .context("Failed to open database")?;
-
-        // Enable WAL mode for better concurrent access
-        conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA busy_timeout=5000;")?;
-
-        let storage = Self {
-            conn,
-            path: Some(path.to_path_buf()),
-        };
-        storage.init_schema()?;
-
-        Ok(storage)
-    }
-
-    /// Open an in-memory database (for testing)
-    pub fn open_memory() -> Result<Self> {
-        let conn = Connection::open_in_memory()?;
-        let storage = Self { conn, path: None };
-        storage.init_schema()?;
-        Ok(storage)
-    }
-
-    /// Clone by opening a new connection to the same database
-    /// This is needed for async operations with spawn_blocking
-    pub fn clone_connection(&self) -> Result<Self> {
-        match &self.path {
-            Some(path) => Self::open(path),
-            None => Self::open_memory(),
-        }
-    }
-
-    /// Initialize database schema
-    fn init_schema(&self) -> Result<()> {
-        self.conn.execute_batch(
-            r#"
+use super::attachment::{self, Attachment};
+use super::fact::{self, AuthorType, Fact, FactLink, FactType, LinkKind, Status};
+use super::migrations::{self, Migration};
+use super::path::Path;
+use super::search::{SearchQuery, HIGHLIGHT_END, HIGHLIGHT_START};
+
+/// Schema migrations for the facts database (see `migrations.rs`)
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "initial facts schema with FTS5 index",
+        up: |conn| conn.execute_batch(SCHEMA_V1),
+    },
+    Migration {
+        version: 2,
+        description: "incremental FTS sync on delete and update",
+        up: |conn| conn.execute_batch(SCHEMA_V2),
+    },
+    Migration {
+        version: 3,
+        description: "fix status spelling of pending review facts",
+        // Older builds stored Status::PendingReview as 'pendingreview'
+        up: |conn| {
+            conn.execute(
+                "UPDATE facts SET status = 'pending_review' WHERE status = 'pendingreview'",
+                [],
+            )
+            .map(|_| ())
+        },
+    },
+    Migration {
+        version: 4,
+        description: "fact_links relation table",
+        up: |conn| conn.execute_batch(SCHEMA_V4),
+    },
+    Migration {
+        version: 5,
+        description: "fact_access read counters",
+        up: |conn| conn.execute_batch(SCHEMA_V5),
+    },
+    Migration {
+        version: 6,
+        description: "normalized content hash for duplicate detection",
+        up: |conn| {
+            conn.execute_batch(SCHEMA_V6)?;
+            backfill_content_hashes(conn)
+        },
+    },
+    Migration {
+        version: 7,
+        description: "fact attachments with content-addressed blobs",
+        up: |conn| conn.execute_batch(SCHEMA_V7),
+    },
+    Migration {
+        version: 8,
+        description: "structured fact properties",
+        up: |conn| conn.execute_batch(SCHEMA_V8),
+    },
+    Migration {
+        version: 9,
+        description: "fact expiry and review dates",
+        up: |conn| conn.execute_batch(SCHEMA_V9),
+    },
+    Migration {
+        version: 10,
+        description: "references links parsed from fact content",
+        up: backfill_references,
+    },
+    Migration {
+        version: 11,
+        description: "namespace-scoped path index",
+        up: |conn| conn.execute_batch(SCHEMA_V11),
+    },
+    Migration {
+        version: 12,
+        description: "merged facts with several superseded sources",
+        up: |conn| conn.execute_batch(SCHEMA_V12),
+    },
+    Migration {
+        version: 13,
+        description: "fact embeddings for semantic search",
+        up: |conn| conn.execute_batch(SCHEMA_V13),
+    },
+];
+
+/// Version 1: original schema (IF NOT EXISTS, so pre-versioning databases adopt it)
+const SCHEMA_V1: &str = r#"
             -- Facts table
             CREATE TABLE IF NOT EXISTS facts (
                 id TEXT PRIMARY KEY,
@@ -118,27 +156,465 @@ impl Storage {
             END;
 
             -- Note: No UPDATE trigger needed - append-only model
-            "#,
-        )?;
+            "#;
+
+/// Version 2: delete/update triggers for the external-content FTS table,
+/// so GC and rejections no longer need a full `rebuild`
+const SCHEMA_V2: &str = r#"
+            CREATE TRIGGER IF NOT EXISTS facts_ad AFTER DELETE ON facts BEGIN
+                INSERT INTO facts_fts(facts_fts, rowid, id, path, title, content, summary, tags)
+                VALUES ('delete', old.rowid, old.id, old.path, old.title, old.content, old.summary, old.tags);
+            END;
+
+            -- Only indexed columns: status changes don't touch the FTS index
+            CREATE TRIGGER IF NOT EXISTS facts_au AFTER UPDATE OF path, title, content, summary, tags ON facts BEGIN
+                INSERT INTO facts_fts(facts_fts, rowid, id, path, title, content, summary, tags)
+                VALUES ('delete', old.rowid, old.id, old.path, old.title, old.content, old.summary, old.tags);
+                INSERT INTO facts_fts(rowid, id, path, title, content, summary, tags)
+                VALUES (new.rowid, new.id, new.path, new.title, new.content, new.summary, new.tags);
+            END;
+
+            -- Start from a consistent index
+            INSERT INTO facts_fts(facts_fts) VALUES('rebuild');
+            "#;
+
+/// Version 4: normalized relations between facts, backfilled from the
+/// `extends` JSON and `supersedes` columns (which are kept for reading facts)
+const SCHEMA_V4: &str = r#"
+            CREATE TABLE IF NOT EXISTS fact_links (
+                source_id TEXT NOT NULL,
+                target TEXT NOT NULL,
+                kind TEXT NOT NULL,  -- extends, supersedes, vote, references
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (source_id, kind, target)
+            );
+
+            -- "What points at X" lookups
+            CREATE INDEX IF NOT EXISTS idx_fact_links_target ON fact_links(target, kind);
+            CREATE INDEX IF NOT EXISTS idx_fact_links_kind ON fact_links(kind);
+
+            -- Links declared by a fact go away with it (GC, rejection)
+            CREATE TRIGGER IF NOT EXISTS facts_links_ad AFTER DELETE ON facts BEGIN
+                DELETE FROM fact_links WHERE source_id = old.id;
+            END;
+
+            INSERT OR IGNORE INTO fact_links (source_id, target, kind, created_at)
+            SELECT f.id, j.value, 'extends', f.created_at
+            FROM facts f, json_each(f.extends) j
+            WHERE json_valid(f.extends);
+
+            INSERT OR IGNORE INTO fact_links (source_id, target, kind, created_at)
+            SELECT id, supersedes, 'supersedes', created_at
+            FROM facts WHERE supersedes IS NOT NULL;
+
+            -- Votes recorded by meh_bulk_vote before links existed
+            INSERT OR IGNORE INTO fact_links (source_id, target, kind, created_at)
+            SELECT f.id, j.value, 'vote', f.created_at
+            FROM facts f, json_each(f.extends) j
+            WHERE json_valid(f.extends) AND f.fact_type = 'extension' AND f.title LIKE 'Vote: %';
+            "#;
+
+/// Version 5: read tracking in a side table, so recording a read never
+/// rewrites the append-only `facts` rows
+const SCHEMA_V5: &str = r#"
+            CREATE TABLE IF NOT EXISTS fact_access (
+                fact_id TEXT PRIMARY KEY,
+                access_count INTEGER NOT NULL DEFAULT 0,
+                last_accessed_at TEXT NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_fact_access_count ON fact_access(access_count);
+
+            CREATE TRIGGER IF NOT EXISTS facts_access_ad AFTER DELETE ON facts BEGIN
+                DELETE FROM fact_access WHERE fact_id = old.id;
+            END;
+            "#;
+
+/// Version 6: `content_hash` column (filled by `backfill_content_hashes`,
+/// since the normalization lives in Rust)
+const SCHEMA_V6: &str = r#"
+            ALTER TABLE facts ADD COLUMN content_hash TEXT;
+            CREATE INDEX IF NOT EXISTS idx_facts_content_hash ON facts(content_hash);
+            "#;
+
+/// Version 7: attachments; blobs are shared between facts (corrections
+/// inherit attachments) and removed with their last reference
+const SCHEMA_V7: &str = r#"
+            CREATE TABLE IF NOT EXISTS blobs (
+                hash TEXT PRIMARY KEY,  -- sha256 of data
+                data BLOB NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS attachments (
+                fact_id TEXT NOT NULL,
+                name TEXT NOT NULL,
+                mime_type TEXT NOT NULL,
+                hash TEXT NOT NULL,
+                size INTEGER NOT NULL,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (fact_id, name)
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_attachments_hash ON attachments(hash);
+
+            CREATE TRIGGER IF NOT EXISTS facts_attachments_ad AFTER DELETE ON facts BEGIN
+                DELETE FROM attachments WHERE fact_id = old.id;
+            END;
+
+            CREATE TRIGGER IF NOT EXISTS attachments_ad AFTER DELETE ON attachments BEGIN
+                DELETE FROM blobs WHERE hash = old.hash
+                AND NOT EXISTS (SELECT 1 FROM attachments WHERE hash = old.hash);
+            END;
+            "#;
+
+/// Version 8: `properties` JSON object on facts, mirrored into
+/// `fact_properties` so key/value filters can use an index
+const SCHEMA_V8: &str = r#"
+            ALTER TABLE facts ADD COLUMN properties TEXT;  -- JSON object
+
+            CREATE TABLE IF NOT EXISTS fact_properties (
+                fact_id TEXT NOT NULL,
+                key TEXT NOT NULL,
+                value TEXT NOT NULL,
+                PRIMARY KEY (fact_id, key)
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_fact_properties_kv ON fact_properties(key, value);
+
+            CREATE TRIGGER IF NOT EXISTS facts_properties_ad AFTER DELETE ON facts BEGIN
+                DELETE FROM fact_properties WHERE fact_id = old.id;
+            END;
+            "#;
+
+/// Version 9: `valid_until` / `review_by` timestamps; `review_notified_at`
+/// records that the review reminder was sent
+const SCHEMA_V9: &str = r#"
+            ALTER TABLE facts ADD COLUMN valid_until TEXT;
+            ALTER TABLE facts ADD COLUMN review_by TEXT;
+            ALTER TABLE facts ADD COLUMN review_notified_at TEXT;
+
+            CREATE INDEX IF NOT EXISTS idx_facts_valid_until ON facts(valid_until)
+                WHERE valid_until IS NOT NULL;
+            CREATE INDEX IF NOT EXISTS idx_facts_review_by ON facts(review_by)
+                WHERE review_by IS NOT NULL;
+            "#;
+
+/// Version 11: every query is scoped by namespace; rows from before
+/// namespaces were used may hold NULL
+const SCHEMA_V11: &str = r#"
+            UPDATE facts SET namespace = '' WHERE namespace IS NULL;
+
+            CREATE INDEX IF NOT EXISTS idx_facts_namespace_path ON facts(namespace, path);
+            "#;
+
+/// Version 12: `merged_from` lists every source of a merged fact (JSON);
+/// lineage itself lives in `supersedes` links, one per source
+const SCHEMA_V12: &str = r#"
+            ALTER TABLE facts ADD COLUMN merged_from TEXT;
+            "#;
+
+/// Version 13: one embedding per fact for semantic search, written with the
+/// fact; `model` names the embedder, so after switching embedders searches
+/// skip old vectors until `meh db reindex --embeddings` re-embeds them
+const SCHEMA_V13: &str = r#"
+            CREATE TABLE IF NOT EXISTS fact_embeddings (
+                fact_id TEXT PRIMARY KEY,
+                model TEXT NOT NULL,
+                vector BLOB NOT NULL  -- little-endian f32
+            );
+
+            CREATE TRIGGER IF NOT EXISTS facts_embeddings_ad AFTER DELETE ON facts BEGIN
+                DELETE FROM fact_embeddings WHERE fact_id = old.id;
+            END;
+            "#;
+
+/// Optional trigram index for typo-tolerant search (not a migration: it is
+/// created and dropped per KB, see `Storage::with_fuzzy`)
+const FUZZY_INDEX: &str = r#"
+            CREATE VIRTUAL TABLE IF NOT EXISTS facts_trigram USING fts5(
+                id UNINDEXED,
+                title,
+                content,
+                tokenize='trigram',
+                content='facts',
+                content_rowid='rowid'
+            );
+
+            CREATE TRIGGER IF NOT EXISTS facts_trigram_ai AFTER INSERT ON facts BEGIN
+                INSERT INTO facts_trigram(rowid, id, title, content)
+                VALUES (new.rowid, new.id, new.title, new.content);
+            END;
+
+            CREATE TRIGGER IF NOT EXISTS facts_trigram_ad AFTER DELETE ON facts BEGIN
+                INSERT INTO facts_trigram(facts_trigram, rowid, id, title, content)
+                VALUES ('delete', old.rowid, old.id, old.title, old.content);
+            END;
+
+            CREATE TRIGGER IF NOT EXISTS facts_trigram_au AFTER UPDATE OF title, content ON facts BEGIN
+                INSERT INTO facts_trigram(facts_trigram, rowid, id, title, content)
+                VALUES ('delete', old.rowid, old.id, old.title, old.content);
+                INSERT INTO facts_trigram(rowid, id, title, content)
+                VALUES (new.rowid, new.id, new.title, new.content);
+            END;
+
+            INSERT INTO facts_trigram(facts_trigram) VALUES('rebuild');
+            "#;
+
+const DROP_FUZZY_INDEX: &str = r#"
+            DROP TRIGGER IF EXISTS facts_trigram_ai;
+            DROP TRIGGER IF EXISTS facts_trigram_ad;
+            DROP TRIGGER IF EXISTS facts_trigram_au;
+            DROP TABLE IF EXISTS facts_trigram;
+            "#;
+
+/// Add a positional parameter, returning its placeholder (`?N`)
+fn bind(values: &mut Vec<SqlValue>, value: SqlValue) -> String {
+    values.push(value);
+    format!("?{}", values.len())
+}
+
+fn backfill_content_hashes(conn: &Connection) -> rusqlite::Result<()> {
+    let mut select = conn.prepare("SELECT id, content FROM facts WHERE content_hash IS NULL")?;
+    let rows = select
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut update = conn.prepare("UPDATE facts SET content_hash = ?2 WHERE id = ?1")?;
+    for (id, content) in rows {
+        update.execute(params![id, fact::content_hash(&content)])?;
+    }
+    Ok(())
+}
+
+/// Record `[[@path]]` / `meh-<ULID>` mentions of existing facts as links
+fn backfill_references(conn: &Connection) -> rusqlite::Result<()> {
+    let mut select = conn.prepare("SELECT id, content, created_at FROM facts")?;
+    let rows = select
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut insert = conn.prepare(
+        "INSERT OR IGNORE INTO fact_links (source_id, target, kind, created_at) VALUES (?1, ?2, 'references', ?3)",
+    )?;
+    for (id, content, created_at) in rows {
+        for target in fact::parse_references(&content) {
+            if target != id {
+                insert.execute(params![id, target, created_at])?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Database storage
+pub struct Storage {
+    conn: Connection,
+    path: Option<std::path::PathBuf>,
+    /// Namespace that reads are scoped to and new facts are written to
+    namespace: String,
+    /// Searches fall back to the trigram index (see `with_fuzzy`)
+    fuzzy: bool,
+}
+
+impl Storage {
+    /// Open or create a database
+    pub fn open(path: &FilePath) -> Result<Self> {
+        let conn = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_WRITE
+                | OpenFlags::SQLITE_OPEN_CREATE
+                | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )
+        .context("Failed to open database")?;
+
+        // Enable WAL mode for better concurrent access
+        conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA busy_timeout=5000;")?;
+
+        let storage = Self {
+            conn,
+            path: Some(path.to_path_buf()),
+            namespace: String::new(),
+            fuzzy: false,
+        };
+        storage.init_schema()?;
+
+        Ok(storage)
+    }
+
+    /// Open an existing database as it is: no migrations, no schema changes
+    /// (`meh db check` reports on it before anything is written; see
+    /// `migrate`)
+    pub fn open_unmigrated(path: &FilePath) -> Result<Self> {
+        let conn = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )
+        .context("Failed to open database")?;
+        conn.execute_batch("PRAGMA busy_timeout=5000;")?;
+
+        Ok(Self {
+            conn,
+            path: Some(path.to_path_buf()),
+            namespace: String::new(),
+            fuzzy: false,
+        })
+    }
+
+    /// Open an in-memory database (for testing)
+    pub fn open_memory() -> Result<Self> {
+        let conn = Connection::open_in_memory()?;
+        let storage = Self {
+            conn,
+            path: None,
+            namespace: String::new(),
+            fuzzy: false,
+        };
+        storage.init_schema()?;
+        Ok(storage)
+    }
+
+    /// Clone by opening a new connection to the same database
+    /// This is needed for async operations with spawn_blocking
+    pub fn clone_connection(&self) -> Result<Self> {
+        let storage = match &self.path {
+            Some(path) => Self::open(path)?,
+            None => Self::open_memory()?,
+        };
+        let mut storage = storage.with_namespace(&self.namespace);
+        storage.fuzzy = self.fuzzy;
+        Ok(storage)
+    }
+
+    /// Scope this storage to a namespace (`""` is the default namespace)
+    ///
+    /// Path lookups, listings, search, stats and GC only see facts of the
+    /// namespace, and new facts are written to it. Lookups by ID are not
+    /// scoped: IDs are unique across the whole database.
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = namespace.into();
+        self
+    }
+
+    /// Switch the namespace of an open storage (see `with_namespace`)
+    pub fn set_namespace(&mut self, namespace: impl Into<String>) {
+        self.namespace = namespace.into();
+    }
+
+    /// The namespace this storage is scoped to
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Follow the KB's `fuzzy` setting (see `Config::fuzzy_for`): build or
+    /// drop the trigram index as needed, and let searches fall back to it
+    /// only when enabled
+    pub fn with_fuzzy(mut self, enabled: bool) -> Result<Self> {
+        self.set_fuzzy_index(enabled)?;
+        self.fuzzy = enabled;
+        Ok(self)
+    }
+
+    /// Whether searches may use the trigram index (see `with_fuzzy`)
+    pub fn fuzzy(&self) -> bool {
+        self.fuzzy
+    }
+
+    /// Apply pending migrations to a database opened with `open_unmigrated`
+    /// (`open` already does)
+    pub fn migrate(&self) -> Result<()> {
+        self.init_schema()
+    }
 
+    /// Initialize database schema (applies pending migrations)
+    fn init_schema(&self) -> Result<()> {
+        migrations::migrate(&self.conn, "Facts database", MIGRATIONS)?;
         Ok(())
     }
 
+    /// Run several writes as one atomic unit
+    ///
+    /// The closure receives this same storage; every call it makes is part
+    /// of one `BEGIN IMMEDIATE` transaction that commits if the closure
+    /// returns `Ok` and rolls back otherwise. Calls made while a transaction
+    /// is already open join it instead of nesting.
+    pub fn transaction<T>(&self, f: impl FnOnce(&Self) -> Result<T>) -> Result<T> {
+        if !self.conn.is_autocommit() {
+            return f(self);
+        }
+
+        let tx = Transaction::new_unchecked(&self.conn, TransactionBehavior::Immediate)?;
+        let value = f(self)?; // dropping `tx` on error rolls back
+        tx.commit()?;
+        Ok(value)
+    }
+
     /// Insert a new fact
+    ///
+    /// Also records the fact's `supersedes`/`extends` relations and the facts
+    /// its content mentions (`references`) in `fact_links`.
+    /// A correction inherits the attachments of the fact(s) it supersedes
+    /// (on a name clash the first source wins).
+    /// A fact without a namespace is written to this storage's namespace.
     pub fn insert(&self, fact: &Fact) -> Result<()> {
+        self.transaction(|tx| {
+            tx.insert_row(fact)?;
+
+            for (key, value) in &fact.properties {
+                tx.conn.execute(
+                    "INSERT INTO fact_properties (fact_id, key, value) VALUES (?1, ?2, ?3)",
+                    params![fact.id.to_string(), key, value],
+                )?;
+            }
+
+            for original in fact.superseded_ids() {
+                tx.add_link(&fact.id, &original.to_string(), LinkKind::Supersedes)?;
+                tx.copy_attachments(&original, &fact.id)?;
+            }
+            for target in &fact.extends {
+                tx.add_link(&fact.id, &target.to_string(), LinkKind::Extends)?;
+            }
+            for target in fact.references() {
+                tx.add_link(&fact.id, &target, LinkKind::References)?;
+            }
+
+            Ok(())
+        })
+    }
+
+    fn insert_row(&self, fact: &Fact) -> Result<()> {
         let tags_json = serde_json::to_string(&fact.tags)?;
         let extends_json = serde_json::to_string(&fact.extends)?;
+        let merged_from_json = if fact.merged_from.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&fact.merged_from)?)
+        };
+        let properties_json = if fact.properties.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&fact.properties)?)
+        };
 
         self.conn.execute(
             r#"
             INSERT INTO facts (
                 id, path, title, content, summary, tags, source, namespace,
                 trust_score, status, fact_type, supersedes, extends,
-                author_type, author_id, created_at, updated_at, accessed_at
+                author_type, author_id, created_at, updated_at, accessed_at,
+                content_hash, properties, valid_until, review_by, merged_from
             ) VALUES (
                 ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8,
                 ?9, ?10, ?11, ?12, ?13,
-                ?14, ?15, ?16, ?17, ?18
+                ?14, ?15, ?16, ?17, ?18,
+                ?19, ?20, ?21, ?22, ?23
             )
             "#,
             params![
@@ -149,9 +625,13 @@ impl Storage {
                 fact.summary,
                 tags_json,
                 format!("{}", fact.source),
-                fact.namespace,
+                if fact.namespace.is_empty() {
+                    &self.namespace
+                } else {
+                    &fact.namespace
+                },
                 fact.trust_score,
-                format!("{:?}", fact.status).to_lowercase(),
+                fact.status.as_str(),
                 format!("{:?}", fact.fact_type).to_lowercase(),
                 fact.supersedes.map(|u| u.to_string()),
                 extends_json,
@@ -160,78 +640,646 @@ impl Storage {
                 fact.created_at.to_rfc3339(),
                 fact.updated_at.to_rfc3339(),
                 fact.accessed_at.map(|dt| dt.to_rfc3339()),
+                fact.content_hash(),
+                properties_json,
+                fact.valid_until.map(|dt| dt.to_rfc3339()),
+                fact.review_by.map(|dt| dt.to_rfc3339()),
+                merged_from_json,
             ],
         )?;
 
         Ok(())
     }
 
-    /// Get a fact by ID
-    pub fn get_by_id(&self, id: &Ulid) -> Result<Option<Fact>> {
-        let mut stmt = self.conn.prepare("SELECT * FROM facts WHERE id = ?1")?;
-
-        let result = stmt.query_row([id.to_string()], Self::row_to_fact);
+    /// Insert a merged fact (see `Fact::merge`) and mark its sources superseded
+    ///
+    /// A merge awaiting review leaves the sources alone; `approve_fact`
+    /// supersedes them once it is approved.
+    pub fn insert_merge(&self, merged: &Fact) -> Result<()> {
+        self.transaction(|tx| {
+            tx.insert(merged)?;
+            if merged.status == Status::Active {
+                for source in &merged.merged_from {
+                    tx.mark_superseded(source)?;
+                }
+            }
+            Ok(())
+        })
+    }
 
-        match result {
-            Ok(fact) => Ok(Some(fact)),
-            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-            Err(e) => Err(e.into()),
-        }
+    /// Insert a vote (see `Fact::vote`) and link it to the facts it votes on
+    pub fn insert_vote(&self, vote: &Fact) -> Result<()> {
+        self.transaction(|tx| {
+            tx.insert(vote)?;
+            for target in &vote.extends {
+                tx.add_link(&vote.id, &target.to_string(), LinkKind::Vote)?;
+            }
+            Ok(())
+        })
     }
 
-    /// Get facts by path (exact match)
-    pub fn get_by_path(&self, path: &str) -> Result<Vec<Fact>> {
+    /// Live facts with the same normalized content as `fact`
+    ///
+    /// Facts at the same path come first. Extensions, votes and deprecation
+    /// notices are not considered (their content is often boilerplate).
+    pub fn find_duplicates(&self, fact: &Fact) -> Result<Vec<Fact>> {
         let mut stmt = self.conn.prepare(
-            "SELECT * FROM facts WHERE path = ?1 AND status = 'active' ORDER BY created_at DESC",
+            r#"
+            SELECT * FROM facts
+            WHERE content_hash = ?1 AND id != ?2 AND namespace = ?4
+            AND status IN ('active', 'pending_review')
+            AND fact_type NOT IN ('extension', 'deprecation')
+            ORDER BY path = ?3 DESC, created_at ASC
+            "#,
         )?;
 
         let facts = stmt
-            .query_map([path], Self::row_to_fact)?
+            .query_map(
+                params![
+                    fact.content_hash(),
+                    fact.id.to_string(),
+                    fact.path,
+                    self.namespace
+                ],
+                Self::row_to_fact,
+            )?
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(facts)
     }
 
-    /// Get facts by path prefix
-    pub fn get_by_path_prefix(&self, prefix: &str) -> Result<Vec<Fact>> {
-        let pattern = format!("{}%", prefix.trim_end_matches('/'));
-
-        let mut stmt = self.conn.prepare(
-            "SELECT * FROM facts WHERE path LIKE ?1 AND status = 'active' ORDER BY path, created_at DESC"
-        )?;
-
-        let facts = stmt
-            .query_map([pattern], Self::row_to_fact)?
+    /// Groups of live facts sharing the same normalized content
+    /// (largest groups first, oldest fact first within a group)
+    pub fn duplicate_clusters(&self) -> Result<Vec<DuplicateCluster>> {
+        const LIVE: &str = "status IN ('active', 'pending_review')
+            AND fact_type NOT IN ('extension', 'deprecation') AND namespace = ?1";
+
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT content_hash FROM facts WHERE content_hash IS NOT NULL AND {}
+             GROUP BY content_hash HAVING COUNT(*) > 1
+             ORDER BY COUNT(*) DESC, MIN(created_at)",
+            LIVE
+        ))?;
+        let hashes = stmt
+            .query_map([&self.namespace], |row| row.get::<_, String>(0))?
             .collect::<Result<Vec<_>, _>>()?;
 
-        Ok(facts)
+        let mut facts_stmt = self.conn.prepare(&format!(
+            "SELECT * FROM facts WHERE content_hash = ?2 AND {} ORDER BY created_at ASC",
+            LIVE
+        ))?;
+        let mut clusters = Vec::new();
+        for content_hash in hashes {
+            let facts = facts_stmt
+                .query_map([&self.namespace, &content_hash], Self::row_to_fact)?
+                .collect::<Result<Vec<_>, _>>()?;
+            clusters.push(DuplicateCluster {
+                content_hash,
+                facts,
+            });
+        }
+
+        Ok(clusters)
     }
 
-    /// List child paths (for browse/ls) with pagination
-    /// Returns (paths, has_more) tuple
-    pub fn list_children(
+    /// Attach a file to a fact (replaces an attachment with the same name)
+    ///
+    /// Size limits are the caller's business (see `attachment::check_size`).
+    pub fn add_attachment(
         &self,
-        parent: &str,
-        limit: i64,
-        cursor: Option<&str>,
-    ) -> Result<(Vec<PathInfo>, bool)> {
-        // Handle root "@" specially - it matches all paths starting with "@"
-        // For "@meh", we want to find children like "@meh/architecture"
-        let (prefix, pattern) = if parent.is_empty() || parent == "/" || parent == "@" {
-            // Root: match all paths starting with @, group by first segment
-            ("@".to_string(), "@%".to_string())
-        } else {
-            // Non-root: match paths that start with parent + "/"
-            let p = format!("{}/", parent.trim_end_matches('/'));
-            let pat = format!("{}%", p);
-            (p, pat)
+        fact_id: &Ulid,
+        name: &str,
+        mime_type: &str,
+        data: &[u8],
+    ) -> Result<Attachment> {
+        let attachment = Attachment {
+            fact_id: *fact_id,
+            name: name.to_string(),
+            mime_type: mime_type.to_string(),
+            hash: attachment::blob_hash(data),
+            size: data.len() as u64,
+            created_at: chrono::Utc::now(),
         };
-        let cursor_path = cursor.unwrap_or("");
 
-        // Fetch limit+1 to know if there are more results
-        let fetch_limit = limit + 1;
+        self.transaction(|tx| {
+            // Replace before storing the blob: the cleanup trigger may drop
+            // the old blob, which can be this very data
+            tx.conn.execute(
+                "DELETE FROM attachments WHERE fact_id = ?1 AND name = ?2",
+                params![fact_id.to_string(), name],
+            )?;
+            tx.conn.execute(
+                "INSERT OR IGNORE INTO blobs (hash, data) VALUES (?1, ?2)",
+                params![attachment.hash, data],
+            )?;
+            tx.conn.execute(
+                "INSERT INTO attachments (fact_id, name, mime_type, hash, size, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    fact_id.to_string(),
+                    attachment.name,
+                    attachment.mime_type,
+                    attachment.hash,
+                    attachment.size as i64,
+                    attachment.created_at.to_rfc3339(),
+                ],
+            )?;
+            Ok(())
+        })?;
+
+        Ok(attachment)
+    }
 
-        let mut stmt = self.conn.prepare(
+    /// Attachments of a fact, by name
+    pub fn list_attachments(&self, fact_id: &Ulid) -> Result<Vec<Attachment>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT * FROM attachments WHERE fact_id = ?1 ORDER BY name")?;
+        let attachments = stmt
+            .query_map([fact_id.to_string()], Self::row_to_attachment)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(attachments)
+    }
+
+    /// One attachment with its data
+    pub fn get_attachment(
+        &self,
+        fact_id: &Ulid,
+        name: &str,
+    ) -> Result<Option<(Attachment, Vec<u8>)>> {
+        let result = self.conn.query_row(
+            "SELECT a.*, b.data FROM attachments a JOIN blobs b ON b.hash = a.hash
+             WHERE a.fact_id = ?1 AND a.name = ?2",
+            params![fact_id.to_string(), name],
+            |row| {
+                Ok((
+                    Self::row_to_attachment(row)?,
+                    row.get::<_, Vec<u8>>("data")?,
+                ))
+            },
+        );
+
+        match result {
+            Ok(found) => Ok(Some(found)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Remove an attachment (its blob goes too if nothing else uses it)
+    pub fn remove_attachment(&self, fact_id: &Ulid, name: &str) -> Result<bool> {
+        let deleted = self.conn.execute(
+            "DELETE FROM attachments WHERE fact_id = ?1 AND name = ?2",
+            params![fact_id.to_string(), name],
+        )?;
+        Ok(deleted > 0)
+    }
+
+    /// Give `to` the attachments of `from` (blobs are shared, not copied)
+    fn copy_attachments(&self, from: &Ulid, to: &Ulid) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO attachments (fact_id, name, mime_type, hash, size, created_at)
+             SELECT ?2, name, mime_type, hash, size, created_at
+             FROM attachments WHERE fact_id = ?1",
+            params![from.to_string(), to.to_string()],
+        )?;
+        Ok(())
+    }
+
+    fn row_to_attachment(row: &rusqlite::Row) -> rusqlite::Result<Attachment> {
+        let fact_id: String = row.get("fact_id")?;
+        let created: String = row.get("created_at")?;
+
+        Ok(Attachment {
+            fact_id: Ulid::from_string(&fact_id).unwrap_or_else(|_| Ulid::nil()),
+            name: row.get("name")?,
+            mime_type: row.get("mime_type")?,
+            hash: row.get("hash")?,
+            size: row.get::<_, i64>("size")? as u64,
+            created_at: chrono::DateTime::parse_from_rfc3339(&created)
+                .map(|dt| dt.with_timezone(&chrono::Utc))
+                .unwrap_or_else(|_| chrono::Utc::now()),
+        })
+    }
+
+    /// Record a relation between two facts (no-op if it already exists)
+    pub fn add_link(&self, source_id: &Ulid, target: &str, kind: LinkKind) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO fact_links (source_id, target, kind, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                source_id.to_string(),
+                target,
+                kind.as_str(),
+                chrono::Utc::now().to_rfc3339()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Relations pointing at a fact ("what extends/supersedes/votes on X")
+    pub fn links_to(&self, target: &str, kind: Option<LinkKind>) -> Result<Vec<FactLink>> {
+        self.query_links(
+            "SELECT * FROM fact_links WHERE target = ?1 AND (?2 IS NULL OR kind = ?2) ORDER BY created_at",
+            params![target, kind.map(|k| k.as_str())],
+        )
+    }
+
+    /// Relations declared by a fact ("what does X extend/supersede")
+    pub fn links_from(&self, source_id: &Ulid, kind: Option<LinkKind>) -> Result<Vec<FactLink>> {
+        self.query_links(
+            "SELECT * FROM fact_links WHERE source_id = ?1 AND (?2 IS NULL OR kind = ?2) ORDER BY created_at",
+            params![source_id.to_string(), kind.map(|k| k.as_str())],
+        )
+    }
+
+    /// All relations of one kind
+    pub fn links_of_kind(&self, kind: LinkKind) -> Result<Vec<FactLink>> {
+        self.query_links(
+            "SELECT * FROM fact_links WHERE kind = ?1 ORDER BY created_at",
+            params![kind.as_str()],
+        )
+    }
+
+    /// Facts that extend the given fact (newest first)
+    pub fn get_extensions(&self, id: &Ulid) -> Result<Vec<Fact>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT f.* FROM fact_links l
+            JOIN facts f ON f.id = l.source_id
+            WHERE l.target = ?1 AND l.kind = 'extends'
+            ORDER BY f.created_at DESC
+            "#,
+        )?;
+
+        let facts = stmt
+            .query_map([id.to_string()], Self::row_to_fact)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(facts)
+    }
+
+    /// Facts the given fact extends
+    pub fn get_extended(&self, id: &Ulid) -> Result<Vec<Fact>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT f.* FROM fact_links l
+            JOIN facts f ON f.id = l.target
+            WHERE l.source_id = ?1 AND l.kind = 'extends'
+            ORDER BY f.created_at DESC
+            "#,
+        )?;
+
+        let facts = stmt
+            .query_map([id.to_string()], Self::row_to_fact)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(facts)
+    }
+
+    fn query_links(&self, sql: &str, params: impl rusqlite::Params) -> Result<Vec<FactLink>> {
+        let mut stmt = self.conn.prepare(sql)?;
+        let links = stmt
+            .query_map(params, |row| {
+                let source: String = row.get("source_id")?;
+                let kind: String = row.get("kind")?;
+                let created: String = row.get("created_at")?;
+                Ok((source, row.get::<_, String>("target")?, kind, created))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(links
+            .into_iter()
+            .filter_map(|(source, target, kind, created)| {
+                Some(FactLink {
+                    source_id: Ulid::from_string(&source).ok()?,
+                    target,
+                    kind: LinkKind::parse_str(&kind)?,
+                    created_at: chrono::DateTime::parse_from_rfc3339(&created)
+                        .map(|dt| dt.with_timezone(&chrono::Utc))
+                        .unwrap_or_else(|_| chrono::Utc::now()),
+                })
+            })
+            .collect())
+    }
+
+    /// The fact a reference points at: the fact with that ID, or the latest
+    /// fact or correction at that path (active ones first; extensions,
+    /// warnings and notices don't count). `None` if nothing matches.
+    pub fn resolve_reference(&self, target: &str) -> Result<Option<Fact>> {
+        if !target.starts_with('@') {
+            return match Ulid::from_string(target) {
+                Ok(id) => self.get_by_id(&id),
+                Err(_) => Ok(None),
+            };
+        }
+
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT * FROM facts
+            WHERE path = ?1 AND namespace = ?2 AND fact_type IN ('fact', 'correction')
+            ORDER BY status = 'active' DESC, created_at DESC
+            LIMIT 1
+            "#,
+        )?;
+
+        match stmt.query_row([target, &self.namespace], Self::row_to_fact) {
+            Ok(fact) => Ok(Some(fact)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Outgoing references of a fact with what they resolve to
+    pub fn references_from(&self, id: &Ulid) -> Result<Vec<(String, Option<Fact>)>> {
+        self.links_from(id, Some(LinkKind::References))?
+            .into_iter()
+            .map(|link| {
+                let resolved = self.resolve_reference(&link.target)?;
+                Ok((link.target, resolved))
+            })
+            .collect()
+    }
+
+    /// Active facts whose content references this fact (by ID or path)
+    pub fn backlinks(&self, fact: &Fact) -> Result<Vec<Fact>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT DISTINCT f.* FROM fact_links l
+            JOIN facts f ON f.id = l.source_id
+            WHERE l.kind = 'references' AND l.target IN (?1, ?2)
+            AND f.status = 'active' AND f.id != ?1 AND f.namespace = ?3
+            ORDER BY f.created_at DESC
+            "#,
+        )?;
+
+        let facts = stmt
+            .query_map(
+                params![fact.id.to_string(), fact.path, self.namespace],
+                Self::row_to_fact,
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(facts)
+    }
+
+    /// References from active facts to IDs or paths that don't exist or
+    /// only hold deprecated/archived facts
+    ///
+    /// One query: each reference is resolved like `resolve_reference` does
+    /// (by ID, or the best fact or correction at the path) and joined to
+    /// what it resolves to.
+    pub fn broken_references(&self) -> Result<Vec<BrokenReference>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT s.id, s.path, l.target, t.status
+            FROM fact_links l
+            JOIN facts s ON s.id = l.source_id
+            LEFT JOIN facts t ON t.id = CASE
+                WHEN l.target LIKE '@%' THEN (
+                    SELECT p.id FROM facts p
+                    WHERE p.path = l.target AND p.namespace = s.namespace
+                    AND p.fact_type IN ('fact', 'correction')
+                    ORDER BY p.status = 'active' DESC, p.created_at DESC
+                    LIMIT 1
+                )
+                ELSE l.target
+            END
+            WHERE l.kind = 'references' AND s.status = 'active' AND s.namespace = ?1
+            AND (t.id IS NULL OR t.status IN ('deprecated', 'archived'))
+            ORDER BY l.created_at
+            "#,
+        )?;
+
+        let rows = stmt.query_map([&self.namespace], |row| {
+            let source_id: String = row.get(0)?;
+            let status: Option<String> = row.get(3)?;
+            Ok((source_id, row.get(1)?, row.get(2)?, status))
+        })?;
+
+        let mut broken = Vec::new();
+        for row in rows {
+            let (source_id, source_path, target, status) = row?;
+            broken.push(BrokenReference {
+                source_id: Ulid::from_string(&source_id)?,
+                source_path,
+                target,
+                reason: match status {
+                    None => BrokenReason::Missing,
+                    Some(_) => BrokenReason::Deprecated,
+                },
+            });
+        }
+
+        Ok(broken)
+    }
+
+    /// Active warnings attached to a fact (see `Fact::warning`), newest first
+    pub fn get_warnings(&self, id: &Ulid) -> Result<Vec<Fact>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT f.* FROM fact_links l
+            JOIN facts f ON f.id = l.source_id
+            WHERE l.target = ?1 AND l.kind = 'extends'
+            AND f.fact_type = 'warning' AND f.status = 'active'
+            ORDER BY f.created_at DESC
+            "#,
+        )?;
+
+        let facts = stmt
+            .query_map([id.to_string()], Self::row_to_fact)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(facts)
+    }
+
+    /// Get a fact by ID
+    ///
+    /// `accessed_at` is taken from `fact_access` when the fact has been read.
+    pub fn get_by_id(&self, id: &Ulid) -> Result<Option<Fact>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT f.id, f.path, f.title, f.content, f.summary, f.tags, f.source,
+                   f.namespace, f.trust_score, f.status, f.fact_type, f.supersedes,
+                   f.extends, f.author_type, f.author_id, f.created_at, f.updated_at,
+                   f.properties, f.valid_until, f.review_by, f.merged_from,
+                   COALESCE(a.last_accessed_at, f.accessed_at) AS accessed_at
+            FROM facts f
+            LEFT JOIN fact_access a ON a.fact_id = f.id
+            WHERE f.id = ?1
+            "#,
+        )?;
+
+        let result = stmt.query_row([id.to_string()], Self::row_to_fact);
+
+        match result {
+            Ok(fact) => Ok(Some(fact)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Get facts by path (exact match)
+    ///
+    /// Warnings share the path of the fact they are attached to but never
+    /// stand in for it; fetch them with `get_warnings`.
+    pub fn get_by_path(&self, path: &str) -> Result<Vec<Fact>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT * FROM facts
+            WHERE path = ?1 AND namespace = ?2
+            AND status = 'active' AND fact_type != 'warning'
+            ORDER BY created_at DESC
+            "#,
+        )?;
+
+        let facts = stmt
+            .query_map([path, &self.namespace], Self::row_to_fact)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(facts)
+    }
+
+    /// Get facts by path prefix
+    pub fn get_by_path_prefix(&self, prefix: &str) -> Result<Vec<Fact>> {
+        let pattern = format!("{}%", prefix.trim_end_matches('/'));
+
+        let mut stmt = self.conn.prepare(
+            "SELECT * FROM facts WHERE path LIKE ?1 AND namespace = ?2 AND status = 'active' ORDER BY path, created_at DESC"
+        )?;
+
+        let facts = stmt
+            .query_map([&pattern, &self.namespace], Self::row_to_fact)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(facts)
+    }
+
+    /// Facts whose path matches a glob (`*` = one segment, `**` = any
+    /// number, see `Path::matches_pattern`), with one of `statuses` and
+    /// every tag in `tags`, by path
+    ///
+    /// The segments before the first wildcard become a range on the path
+    /// index, so `@products/*/api/**` only reads paths under `@products/`.
+    /// `GLOB` (and the depth, without `**`) narrows the rest in SQL; the
+    /// exact segment match runs on the rows left.
+    pub fn find_by_pattern(
+        &self,
+        pattern: &str,
+        statuses: &[Status],
+        tags: &[String],
+        limit: usize,
+    ) -> Result<Vec<Fact>> {
+        Path::validate_pattern(pattern)?;
+        let mut values: Vec<SqlValue> = Vec::new();
+        let sql = self.find_sql(pattern, statuses, tags, &mut values);
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let facts = stmt
+            .query_map(rusqlite::params_from_iter(values), Self::row_to_fact)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(facts
+            .into_iter()
+            .filter(|f| Path::parse(&f.path).is_ok_and(|p| p.matches_pattern(pattern)))
+            .take(limit)
+            .collect())
+    }
+
+    fn find_sql(
+        &self,
+        pattern: &str,
+        statuses: &[Status],
+        tags: &[String],
+        values: &mut Vec<SqlValue>,
+    ) -> String {
+        let pattern = pattern.trim().trim_start_matches('/').trim_end_matches('/');
+        let mut conditions = vec![
+            format!(
+                "f.namespace = {}",
+                bind(values, self.namespace.clone().into())
+            ),
+            "f.fact_type != 'warning'".to_string(),
+        ];
+
+        if let Some(prefix) = Path::pattern_prefix(pattern) {
+            // '0' sorts right after '/': the range is "everything under"
+            let p = bind(values, prefix.clone().into());
+            let low = bind(values, format!("{}/", prefix).into());
+            let high = bind(values, format!("{}0", prefix).into());
+            conditions.push(format!(
+                "(f.path = {p} OR (f.path >= {low} AND f.path < {high}))"
+            ));
+        }
+
+        // `*` in GLOB also crosses '/', and `**` may match no segment at
+        // all, so this over-matches; the segment match decides
+        let glob = format!("/{}/", pattern)
+            .replace("/**/", "*")
+            .trim_start_matches('/')
+            .trim_end_matches('/')
+            .to_string();
+        conditions.push(format!("f.path GLOB {}", bind(values, glob.into())));
+        if !pattern.contains("**") {
+            let depth = pattern.split('/').count() as i64 - 1;
+            conditions.push(format!(
+                "length(f.path) - length(replace(f.path, '/', '')) = {}",
+                bind(values, depth.into())
+            ));
+        }
+
+        let statuses = if statuses.is_empty() {
+            &[Status::Active][..]
+        } else {
+            statuses
+        };
+        conditions.push(format!(
+            "f.status IN ({})",
+            statuses
+                .iter()
+                .map(|s| bind(values, s.as_str().to_string().into()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        for tag in tags {
+            conditions.push(format!(
+                "EXISTS (SELECT 1 FROM json_each(f.tags) WHERE value = {})",
+                bind(values, tag.clone().into())
+            ));
+        }
+
+        format!(
+            "SELECT f.* FROM facts f WHERE {} ORDER BY f.path, f.created_at DESC",
+            conditions.join(" AND ")
+        )
+    }
+
+    /// List child paths (for browse/ls) with pagination
+    /// Returns (paths, has_more) tuple
+    ///
+    /// Warnings are not counted: they belong to the fact they warn about.
+    pub fn list_children(
+        &self,
+        parent: &str,
+        limit: i64,
+        cursor: Option<&str>,
+    ) -> Result<(Vec<PathInfo>, bool)> {
+        // Handle root "@" specially - it matches all paths starting with "@"
+        // For "@meh", we want to find children like "@meh/architecture"
+        let (prefix, pattern) = if parent.is_empty() || parent == "/" || parent == "@" {
+            // Root: match all paths starting with @, group by first segment
+            ("@".to_string(), "@%".to_string())
+        } else {
+            // Non-root: match paths that start with parent + "/"
+            let p = format!("{}/", parent.trim_end_matches('/'));
+            let pat = format!("{}%", p);
+            (p, pat)
+        };
+        let cursor_path = cursor.unwrap_or("");
+
+        // Fetch limit+1 to know if there are more results
+        let fetch_limit = limit + 1;
+
+        let mut stmt = self.conn.prepare(
             r#"
             SELECT 
                 grouped_path,
@@ -245,7 +1293,8 @@ impl Storage {
                     END as grouped_path,
                     COUNT(*) as cnt
                 FROM facts 
-                WHERE path LIKE ?1 AND status = 'active'
+                WHERE path LIKE ?1 AND namespace = ?5 AND status = 'active'
+                AND fact_type != 'warning'
                 GROUP BY path
             )
             WHERE grouped_path > ?3
@@ -255,81 +1304,543 @@ impl Storage {
             "#
         )?;
 
-        let mut results: Vec<PathInfo> = stmt
+        let mut results: Vec<PathInfo> = stmt
+            .query_map(
+                rusqlite::params![&pattern, &prefix, cursor_path, fetch_limit, &self.namespace],
+                |row| {
+                    Ok(PathInfo {
+                        path: row.get(0)?,
+                        fact_count: row.get::<_, i64>(1)? as usize,
+                    })
+                },
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Check if there are more results
+        let has_more = results.len() > limit as usize;
+        if has_more {
+            results.pop(); // Remove the extra item
+        }
+
+        Ok((results, has_more))
+    }
+
+    /// List child paths without pagination (for CLI backwards compatibility)
+    pub fn list_children_all(&self, parent: &str) -> Result<Vec<PathInfo>> {
+        let (results, _) = self.list_children(parent, 10000, None)?;
+        Ok(results)
+    }
+
+    /// Escape and prepare query for FTS5
+    /// Converts natural language query to FTS5 syntax with OR between words
+    fn escape_fts_query(query: &str) -> String {
+        // Split into words and quote each one (to handle special chars like hyphens)
+        // Use OR so any matching word returns results (more forgiving for AI queries)
+        let words: Vec<String> = query
+            .split_whitespace()
+            .filter(|w| !w.is_empty())
+            .map(|w| {
+                // Escape quotes and wrap in quotes to handle special chars
+                let escaped = w.replace('"', "\"\"");
+                format!("\"{}\"", escaped)
+            })
+            .collect();
+
+        if words.is_empty() {
+            return String::new();
+        }
+
+        // Join with OR - finds documents with ANY of the words
+        // This is more forgiving for natural language queries from AI
+        words.join(" OR ")
+    }
+
+    /// Full-text search in this storage's namespace
+    pub fn search(&self, query: &str, limit: i64) -> Result<Vec<Fact>> {
+        self.search_in(query, limit, Some(&self.namespace))
+    }
+
+    /// Full-text search in one namespace, or in all of them (`None`)
+    pub fn search_in(&self, query: &str, limit: i64, namespace: Option<&str>) -> Result<Vec<Fact>> {
+        let fts_query = Self::escape_fts_query(query);
+
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT f.* 
+            FROM facts f
+            JOIN facts_fts fts ON f.id = fts.id
+            WHERE facts_fts MATCH ?1 AND f.status = 'active'
+            AND (?3 IS NULL OR f.namespace = ?3)
+            ORDER BY bm25(facts_fts, 0, 10.0, 5.0, 1.0, 1.0, 1.0)
+            LIMIT ?2
+            "#,
+        )?;
+
+        let facts = stmt
+            .query_map(params![fts_query, limit as i32, namespace], |row| {
+                Self::row_to_fact(row)
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(facts)
+    }
+
+    /// Search with every `SearchQuery` filter applied in SQL
+    ///
+    /// Returns each fact with its BM25 score (lower is better; `0.0`
+    /// without text, where facts come by trust, then newest first).
+    pub fn search_query(&self, query: &SearchQuery) -> Result<Vec<(Fact, f64)>> {
+        let fts = Self::fts_match(query).map(|m| {
+            (
+                "facts_fts",
+                "bm25(facts_fts, 0, 10.0, 5.0, 1.0, 1.0, 1.0)",
+                m,
+            )
+        });
+
+        self.search_filtered(query, fts, query.limit)
+    }
+
+    /// FTS5 `MATCH` expression of a query (`None` without text)
+    fn fts_match(query: &SearchQuery) -> Option<String> {
+        query
+            .fts_match
+            .clone()
+            .or_else(|| query.text.as_deref().map(Self::escape_fts_query))
+            .filter(|q| !q.is_empty())
+    }
+
+    /// FTS5 snippets of the content of the facts `ids` for the text of
+    /// `query`: up to `words` words (at most 64) around the best match, each
+    /// matched word between `HIGHLIGHT_START` and `HIGHLIGHT_END`
+    ///
+    /// Facts whose content does not match (e.g. only the title or path
+    /// does) get no snippet.
+    pub fn snippets(
+        &self,
+        query: &SearchQuery,
+        ids: &[Ulid],
+        words: usize,
+    ) -> Result<HashMap<Ulid, String>> {
+        let Some(fts_match) = Self::fts_match(query) else {
+            return Ok(HashMap::new());
+        };
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut values: Vec<SqlValue> = vec![
+            HIGHLIGHT_START.to_string().into(),
+            HIGHLIGHT_END.to_string().into(),
+            (words.clamp(1, 64) as i64).into(),
+            fts_match.into(),
+        ];
+        let ids = ids
+            .iter()
+            .map(|id| bind(&mut values, id.to_string().into()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let sql = format!(
+            "SELECT id, snippet(facts_fts, 3, ?1, ?2, '…', ?3) FROM facts_fts \
+             WHERE facts_fts MATCH ?4 AND id IN ({})",
+            ids
+        );
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(values), |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(rows
+            .into_iter()
+            .filter(|(_, snippet)| snippet.contains(HIGHLIGHT_START))
+            .filter_map(|(id, snippet)| Some((id.parse().ok()?, snippet)))
+            .collect())
+    }
+
+    /// Candidates for a fuzzy search: `trigram_match` against the trigram
+    /// index, with every `SearchQuery` filter applied, best BM25 first
+    ///
+    /// Fails if the index does not exist (see `with_fuzzy`).
+    pub fn search_trigram(
+        &self,
+        query: &SearchQuery,
+        trigram_match: &str,
+        limit: usize,
+    ) -> Result<Vec<(Fact, f64)>> {
+        let fts = (
+            "facts_trigram",
+            "bm25(facts_trigram, 0, 2.0, 1.0)",
+            trigram_match.to_string(),
+        );
+        self.search_filtered(query, Some(fts), limit)
+    }
+
+    /// Filtered search, optionally matching `(fts table, score, MATCH)`
+    fn search_filtered(
+        &self,
+        query: &SearchQuery,
+        fts: Option<(&str, &str, String)>,
+        limit: usize,
+    ) -> Result<Vec<(Fact, f64)>> {
+        let mut conditions: Vec<String> = Vec::new();
+        let mut values: Vec<SqlValue> = Vec::new();
+        if let Some((table, _, expr)) = &fts {
+            conditions.push(format!(
+                "{} MATCH {}",
+                table,
+                bind(&mut values, expr.clone().into())
+            ));
+        }
+        conditions.extend(self.filter_conditions(query, &mut values));
+        let limit = bind(&mut values, (limit as i64).into());
+
+        let sql = if let Some((table, score, _)) = &fts {
+            format!(
+                r#"
+                SELECT f.*, {score} AS score
+                FROM facts f
+                JOIN {table} fts ON f.id = fts.id
+                WHERE {}
+                ORDER BY score
+                LIMIT {}
+                "#,
+                conditions.join(" AND "),
+                limit
+            )
+        } else {
+            format!(
+                r#"
+                SELECT f.*, 0.0 AS score
+                FROM facts f
+                WHERE {}
+                ORDER BY f.trust_score DESC, f.created_at DESC
+                LIMIT {}
+                "#,
+                conditions.join(" AND "),
+                limit
+            )
+        };
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let results = stmt
+            .query_map(rusqlite::params_from_iter(values), |row| {
+                Ok((Self::row_to_fact(row)?, row.get::<_, f64>("score")?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(results)
+    }
+
+    /// Searchable (active or deprecated) facts without an embedding from
+    /// `model`, in any namespace, oldest first
+    pub fn facts_without_embedding(&self, model: &str, limit: usize) -> Result<Vec<Fact>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT f.* FROM facts f
+            WHERE f.status IN ('active', 'deprecated')
+            AND NOT EXISTS (
+                SELECT 1 FROM fact_embeddings e WHERE e.fact_id = f.id AND e.model = ?1
+            )
+            ORDER BY f.created_at
+            LIMIT ?2
+            "#,
+        )?;
+        let facts = stmt
+            .query_map(params![model, limit as i64], Self::row_to_fact)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(facts)
+    }
+
+    /// Store embeddings from `model`, replacing older ones of those facts
+    pub fn set_embeddings(&self, model: &str, vectors: &[(Ulid, Vec<f32>)]) -> Result<()> {
+        self.transaction(|s| {
+            let mut stmt = s.conn.prepare(
+                "INSERT OR REPLACE INTO fact_embeddings (fact_id, model, vector) VALUES (?1, ?2, ?3)",
+            )?;
+            for (id, vector) in vectors {
+                let blob: Vec<u8> = vector.iter().flat_map(|v| v.to_le_bytes()).collect();
+                stmt.execute(params![id.to_string(), model, blob])?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Drop every stored embedding, returning how many there were
+    pub fn clear_embeddings(&self) -> Result<usize> {
+        Ok(self.conn.execute("DELETE FROM fact_embeddings", [])?)
+    }
+
+    /// Facts passing every `SearchQuery` filter except the text, with their
+    /// embedding from `model` (facts without one are skipped)
+    pub fn embedded_facts(
+        &self,
+        query: &SearchQuery,
+        model: &str,
+    ) -> Result<Vec<(Fact, Vec<f32>)>> {
+        let mut values: Vec<SqlValue> = Vec::new();
+        let model = bind(&mut values, model.to_string().into());
+        let conditions = self.filter_conditions(query, &mut values);
+        let sql = format!(
+            r#"
+            SELECT f.*, e.vector AS vector
+            FROM facts f
+            JOIN fact_embeddings e ON e.fact_id = f.id AND e.model = {}
+            WHERE {}
+            "#,
+            model,
+            conditions.join(" AND ")
+        );
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let results = stmt
+            .query_map(rusqlite::params_from_iter(values), |row| {
+                let blob: Vec<u8> = row.get("vector")?;
+                let vector = blob
+                    .chunks_exact(4)
+                    .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                    .collect();
+                Ok((Self::row_to_fact(row)?, vector))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(results)
+    }
+
+    /// SQL conditions (on `facts f`) for every `SearchQuery` filter except
+    /// the text, with their values appended to `values`
+    fn filter_conditions(&self, query: &SearchQuery, values: &mut Vec<SqlValue>) -> Vec<String> {
+        let mut conditions: Vec<String> = Vec::new();
+        conditions.push(if query.include_deprecated {
+            "f.status IN ('active', 'deprecated')".to_string()
+        } else {
+            "f.status = 'active'".to_string()
+        });
+        if !query.all_namespaces {
+            conditions.push(format!(
+                "f.namespace = {}",
+                bind(values, self.namespace.clone().into())
+            ));
+        }
+        // At or under the path, segment-wise: @api is not a prefix of @apiv2
+        let prefix = query
+            .path_prefix
+            .as_deref()
+            .map(|p| p.trim_end_matches('/'));
+        if let Some(prefix) = prefix.filter(|p| !p.is_empty()) {
+            let p = bind(values, prefix.to_string().into());
+            conditions.push(format!(
+                "(f.path = {p} OR substr(f.path, 1, length({p}) + 1) = {p} || '/')"
+            ));
+        }
+        if let Some(min) = query.min_trust {
+            conditions.push(format!(
+                "f.trust_score >= {}",
+                bind(values, (min as f64).into())
+            ));
+        }
+        if let Some(author) = query.author_type {
+            conditions.push(format!(
+                "f.author_type = {}",
+                bind(values, format!("{:?}", author).to_lowercase().into())
+            ));
+        }
+        for tag in &query.tags {
+            conditions.push(format!(
+                "EXISTS (SELECT 1 FROM json_each(f.tags) WHERE value = {})",
+                bind(values, tag.clone().into())
+            ));
+        }
+        for tag in &query.not_tags {
+            conditions.push(format!(
+                "NOT EXISTS (SELECT 1 FROM json_each(f.tags) WHERE value = {})",
+                bind(values, tag.clone().into())
+            ));
+        }
+        for (key, value) in &query.properties {
+            let k = bind(values, key.clone().into());
+            let v = bind(values, value.clone().into());
+            conditions.push(format!(
+                "EXISTS (SELECT 1 FROM fact_properties p WHERE p.fact_id = f.id AND p.key = {k} AND p.value = {v})"
+            ));
+        }
+        if !query.include_expired {
+            // Expired facts linger until the next expiry pass deprecates them
+            conditions.push(format!(
+                "(f.valid_until IS NULL OR f.valid_until > {})",
+                bind(values, chrono::Utc::now().to_rfc3339().into())
+            ));
+        }
+        conditions
+    }
+
+    /// `record_access` for read paths: usage tracking must never fail a
+    /// read, so errors are ignored
+    pub fn record_usage_best_effort(&self, ids: &[Ulid]) {
+        let _ = self.record_access(ids);
+    }
+
+    /// Record that facts were read (shown, fetched or returned by a search)
+    ///
+    /// All IDs are counted in one transaction; duplicates count once.
+    pub fn record_access(&self, ids: &[Ulid]) -> Result<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        let now = chrono::Utc::now().to_rfc3339();
+        let mut unique: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+        unique.sort();
+        unique.dedup();
+
+        self.transaction(|tx| {
+            let mut stmt = tx.conn.prepare_cached(
+                r#"
+                INSERT INTO fact_access (fact_id, access_count, last_accessed_at)
+                VALUES (?1, 1, ?2)
+                ON CONFLICT(fact_id) DO UPDATE SET
+                    access_count = access_count + 1,
+                    last_accessed_at = excluded.last_accessed_at
+                "#,
+            )?;
+            for id in &unique {
+                stmt.execute(params![id, now])?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Read counters for one fact (`None` if it was never read)
+    pub fn access_of(&self, id: &Ulid) -> Result<Option<FactUsage>> {
+        let mut usage = self.query_usage(
+            r#"
+            SELECT f.id, f.path, f.title, a.access_count, a.last_accessed_at
+            FROM fact_access a JOIN facts f ON f.id = a.fact_id
+            WHERE a.fact_id = ?1
+            "#,
+            params![id.to_string()],
+        )?;
+        Ok(usage.pop())
+    }
+
+    /// Active facts read most often (most recent first on ties)
+    pub fn most_accessed(&self, limit: i64) -> Result<Vec<FactUsage>> {
+        self.query_usage(
+            r#"
+            SELECT f.id, f.path, f.title, a.access_count, a.last_accessed_at
+            FROM fact_access a JOIN facts f ON f.id = a.fact_id
+            WHERE f.status = 'active' AND f.namespace = ?2
+            ORDER BY a.access_count DESC, a.last_accessed_at DESC
+            LIMIT ?1
+            "#,
+            params![limit, self.namespace],
+        )
+    }
+
+    /// Active facts that were never read (oldest first)
+    pub fn never_accessed(&self, limit: i64) -> Result<Vec<FactUsage>> {
+        self.query_usage(
+            r#"
+            SELECT f.id, f.path, f.title, 0, NULL
+            FROM facts f
+            WHERE f.status = 'active' AND f.namespace = ?2
+            AND NOT EXISTS (SELECT 1 FROM fact_access a WHERE a.fact_id = f.id)
+            ORDER BY f.created_at ASC
+            LIMIT ?1
+            "#,
+            params![limit, self.namespace],
+        )
+    }
+
+    fn query_usage(&self, sql: &str, params: impl rusqlite::Params) -> Result<Vec<FactUsage>> {
+        let mut stmt = self.conn.prepare(sql)?;
+        let usage = stmt
+            .query_map(params, |row| {
+                Ok(FactUsage {
+                    id: row.get(0)?,
+                    path: row.get(1)?,
+                    title: row.get(2)?,
+                    access_count: row.get::<_, i64>(3)? as u64,
+                    last_accessed_at: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(usage)
+    }
+
+    /// Active facts with a property set (to `value`, if given), newest first
+    pub fn get_by_property(&self, key: &str, value: Option<&str>, limit: i64) -> Result<Vec<Fact>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT f.* FROM fact_properties p
+            JOIN facts f ON f.id = p.fact_id
+            WHERE p.key = ?1 AND (?2 IS NULL OR p.value = ?2)
+            AND f.status = 'active' AND f.namespace = ?4
+            ORDER BY f.created_at DESC
+            LIMIT ?3
+            "#,
+        )?;
+
+        let facts = stmt
             .query_map(
-                rusqlite::params![&pattern, &prefix, cursor_path, fetch_limit],
-                |row| {
-                    Ok(PathInfo {
-                        path: row.get(0)?,
-                        fact_count: row.get::<_, i64>(1)? as usize,
-                    })
-                },
+                params![key, value, limit, self.namespace],
+                Self::row_to_fact,
             )?
             .collect::<Result<Vec<_>, _>>()?;
 
-        // Check if there are more results
-        let has_more = results.len() > limit as usize;
-        if has_more {
-            results.pop(); // Remove the extra item
-        }
-
-        Ok((results, has_more))
-    }
-
-    /// List child paths without pagination (for CLI backwards compatibility)
-    pub fn list_children_all(&self, parent: &str) -> Result<Vec<PathInfo>> {
-        let (results, _) = self.list_children(parent, 10000, None)?;
-        Ok(results)
+        Ok(facts)
     }
 
-    /// Escape and prepare query for FTS5
-    /// Converts natural language query to FTS5 syntax with OR between words
-    fn escape_fts_query(query: &str) -> String {
-        // Split into words and quote each one (to handle special chars like hyphens)
-        // Use OR so any matching word returns results (more forgiving for AI queries)
-        let words: Vec<String> = query
-            .split_whitespace()
-            .filter(|w| !w.is_empty())
-            .map(|w| {
-                // Escape quotes and wrap in quotes to handle special chars
-                let escaped = w.replace('"', "\"\"");
-                format!("\"{}\"", escaped)
-            })
-            .collect();
+    /// Active facts whose `valid_until` is at or before `now`
+    ///
+    /// Not scoped by namespace: expiry is a property of the fact, not of
+    /// whoever happens to run the expiry pass.
+    pub fn get_expired(&self, now: chrono::DateTime<chrono::Utc>) -> Result<Vec<Fact>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT * FROM facts
+            WHERE status = 'active' AND valid_until IS NOT NULL AND valid_until <= ?1
+            ORDER BY valid_until
+            "#,
+        )?;
 
-        if words.is_empty() {
-            return String::new();
-        }
+        let facts = stmt
+            .query_map([now.to_rfc3339()], Self::row_to_fact)?
+            .collect::<Result<Vec<_>, _>>()?;
 
-        // Join with OR - finds documents with ANY of the words
-        // This is more forgiving for natural language queries from AI
-        words.join(" OR ")
+        Ok(facts)
     }
 
-    /// Full-text search
-    pub fn search(&self, query: &str, limit: i64) -> Result<Vec<Fact>> {
-        let fts_query = Self::escape_fts_query(query);
-
+    /// Active facts whose `review_by` has passed and that were not yet
+    /// reported (see `mark_review_notified`), in all namespaces
+    pub fn get_review_due(&self, now: chrono::DateTime<chrono::Utc>) -> Result<Vec<Fact>> {
         let mut stmt = self.conn.prepare(
             r#"
-            SELECT f.* 
-            FROM facts f
-            JOIN facts_fts fts ON f.id = fts.id
-            WHERE facts_fts MATCH ?1 AND f.status = 'active'
-            ORDER BY bm25(facts_fts, 0, 10.0, 5.0, 1.0, 1.0, 1.0)
-            LIMIT ?2
+            SELECT * FROM facts
+            WHERE status = 'active' AND review_by IS NOT NULL AND review_by <= ?1
+            AND review_notified_at IS NULL
+            ORDER BY review_by
             "#,
         )?;
 
         let facts = stmt
-            .query_map(params![fts_query, limit as i32], |row| {
-                Self::row_to_fact(row)
-            })?
+            .query_map([now.to_rfc3339()], Self::row_to_fact)?
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(facts)
     }
 
+    /// Remember that the review reminder for a fact was sent
+    pub fn mark_review_notified(&self, id: &Ulid) -> Result<()> {
+        self.conn.execute(
+            "UPDATE facts SET review_notified_at = ?2 WHERE id = ?1",
+            params![id.to_string(), chrono::Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
     /// Mark a fact as superseded
     pub fn mark_superseded(&self, id: &Ulid) -> Result<()> {
         // Note: This is one of the few "updates" allowed - status change
@@ -355,19 +1866,17 @@ impl Storage {
     /// - extends contains the target fact ID
     /// - author_id starts with the given session prefix (e.g., "mcp-")
     pub fn has_session_voted(&self, fact_id: &Ulid, session_prefix: &str) -> Result<bool> {
-        // extends is stored as JSON array, so we search for the ID string within it
         let count: i64 = self.conn.query_row(
             r#"
-            SELECT COUNT(*) FROM facts 
-            WHERE fact_type = 'extension' 
-            AND extends LIKE ?1
-            AND author_id LIKE ?2
-            AND status != 'deprecated'
+            SELECT COUNT(*) FROM fact_links l
+            JOIN facts f ON f.id = l.source_id
+            WHERE l.target = ?1
+            AND l.kind = 'extends'
+            AND f.fact_type = 'extension'
+            AND f.author_id LIKE ?2
+            AND f.status != 'deprecated'
             "#,
-            params![
-                format!("%{}%", fact_id.to_string()),
-                format!("{}%", session_prefix)
-            ],
+            params![fact_id.to_string(), format!("{}%", session_prefix)],
             |row| row.get(0),
         )?;
         Ok(count > 0)
@@ -384,8 +1893,11 @@ impl Storage {
         if let Some(id_str) = id_or_path.strip_prefix("meh-") {
             if let Ok(ulid) = Ulid::from_string(id_str) {
                 if let Some(fact) = self.get_by_id(&ulid)? {
-                    // Check if this fact is superseded
-                    if fact.status == crate::core::fact::Status::Superseded {
+                    // Check if this fact is superseded (archived originals
+                    // keep their successors)
+                    if fact.status == crate::core::fact::Status::Superseded
+                        || fact.status == crate::core::fact::Status::Archived
+                    {
                         // Follow chain forward to find latest
                         let chain = self.get_superseding_facts(&ulid)?;
                         if let Some(latest) = chain
@@ -395,7 +1907,9 @@ impl Storage {
                             return Ok(Some((latest, true)));
                         }
                         // No active version found in chain
-                        return Ok(None);
+                        if fact.status == crate::core::fact::Status::Superseded {
+                            return Ok(None);
+                        }
                     }
                     return Ok(Some((fact, false)));
                 }
@@ -434,13 +1948,21 @@ impl Storage {
 
     /// Get all facts that supersede a given fact (follow supersedes forward)
     /// Returns list of facts from oldest to newest
+    ///
+    /// Follows `supersedes` links, so a source of a merge leads to the
+    /// merged fact.
     pub fn get_superseding_facts(&self, id: &Ulid) -> Result<Vec<Fact>> {
         let mut chain = Vec::new();
 
-        // Find facts where supersedes = current id
-        let mut stmt = self
-            .conn
-            .prepare("SELECT * FROM facts WHERE supersedes = ?1")?;
+        // Find facts superseding the current id
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT f.* FROM fact_links l
+            JOIN facts f ON f.id = l.source_id
+            WHERE l.target = ?1 AND l.kind = 'supersedes'
+            ORDER BY f.created_at
+            "#,
+        )?;
 
         let mut current_facts: Vec<Fact> = stmt
             .query_map([id.to_string()], Self::row_to_fact)?
@@ -475,6 +1997,15 @@ impl Storage {
         let updated_str: String = row.get("updated_at")?;
         let accessed_str: Option<String> = row.get("accessed_at")?;
         let supersedes_str: Option<String> = row.get("supersedes")?;
+        let properties_json: Option<String> = row.get("properties")?;
+        let valid_until_str: Option<String> = row.get("valid_until")?;
+        let review_by_str: Option<String> = row.get("review_by")?;
+        let merged_from_json: Option<String> = row.get("merged_from")?;
+        let parse_time = |s: String| {
+            chrono::DateTime::parse_from_rfc3339(&s)
+                .map(|dt| dt.with_timezone(&chrono::Utc))
+                .ok()
+        };
 
         Ok(Fact {
             id: Ulid::from_string(&id_str).unwrap_or_else(|_| Ulid::new()),
@@ -483,6 +2014,9 @@ impl Storage {
             content: row.get("content")?,
             summary: row.get("summary")?,
             tags: serde_json::from_str(&tags_json).unwrap_or_default(),
+            properties: properties_json
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
             source: source_str.parse().unwrap_or_default(),
             namespace: row.get("namespace")?,
             trust_score: row.get("trust_score")?,
@@ -503,6 +2037,9 @@ impl Storage {
                 _ => FactType::Fact,
             },
             supersedes: supersedes_str.and_then(|s| Ulid::from_string(&s).ok()),
+            merged_from: merged_from_json
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
             extends: serde_json::from_str(&extends_json).unwrap_or_default(),
             author_type: match author_type_str.as_str() {
                 "human" => AuthorType::Human,
@@ -517,35 +2054,47 @@ impl Storage {
             updated_at: chrono::DateTime::parse_from_rfc3339(&updated_str)
                 .map(|dt| dt.with_timezone(&chrono::Utc))
                 .unwrap_or_else(|_| chrono::Utc::now()),
-            accessed_at: accessed_str.and_then(|s| {
-                chrono::DateTime::parse_from_rfc3339(&s)
-                    .map(|dt| dt.with_timezone(&chrono::Utc))
-                    .ok()
-            }),
+            accessed_at: accessed_str.and_then(parse_time),
+            valid_until: valid_until_str.and_then(parse_time),
+            review_by: review_by_str.and_then(parse_time),
         })
     }
 
-    /// Get database statistics
+    /// Get database statistics (for this storage's namespace)
     pub fn stats(&self) -> Result<StorageStats> {
-        let total: i64 = self
-            .conn
-            .query_row("SELECT COUNT(*) FROM facts", [], |row| row.get(0))?;
+        let total: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM facts WHERE namespace = ?1",
+            [&self.namespace],
+            |row| row.get(0),
+        )?;
 
         let active: i64 = self.conn.query_row(
-            "SELECT COUNT(*) FROM facts WHERE status = 'active'",
-            [],
+            "SELECT COUNT(*) FROM facts WHERE status = 'active' AND namespace = ?1",
+            [&self.namespace],
             |row| row.get(0),
         )?;
 
         let deprecated: i64 = self.conn.query_row(
-            "SELECT COUNT(*) FROM facts WHERE status = 'deprecated'",
-            [],
+            "SELECT COUNT(*) FROM facts WHERE status = 'deprecated' AND namespace = ?1",
+            [&self.namespace],
+            |row| row.get(0),
+        )?;
+
+        let superseded: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM facts WHERE status = 'superseded' AND namespace = ?1",
+            [&self.namespace],
+            |row| row.get(0),
+        )?;
+
+        let archived: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM facts WHERE status = 'archived' AND namespace = ?1",
+            [&self.namespace],
             |row| row.get(0),
         )?;
 
         let pending_review: i64 = self.conn.query_row(
-            "SELECT COUNT(*) FROM facts WHERE status = 'pending_review'",
-            [],
+            "SELECT COUNT(*) FROM facts WHERE status = 'pending_review' AND namespace = ?1",
+            [&self.namespace],
             |row| row.get(0),
         )?;
 
@@ -554,21 +2103,84 @@ impl Storage {
             total_facts: total as usize,
             active_facts: active as usize,
             deprecated_facts: deprecated as usize,
+            superseded_facts: superseded as usize,
+            archived_facts: archived as usize,
             pending_review_facts: pending_review as usize,
         })
     }
 
-    /// Approve a pending_review fact (set status to active)
-    pub fn approve_fact(&self, id: &Ulid) -> Result<()> {
-        let updated = self.conn.execute(
-            "UPDATE facts SET status = 'active', updated_at = ?2 WHERE id = ?1 AND status = 'pending_review'",
-            params![id.to_string(), chrono::Utc::now().to_rfc3339()],
+    /// Namespaces in the database with their fact counts (all namespaces,
+    /// default namespace first)
+    pub fn namespaces(&self) -> Result<Vec<NamespaceInfo>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT namespace, COUNT(*), SUM(status = 'active')
+            FROM facts
+            GROUP BY namespace
+            ORDER BY namespace
+            "#,
         )?;
 
-        if updated == 0 {
-            anyhow::bail!("Fact {} not found or not pending review", id);
+        let namespaces = stmt
+            .query_map([], |row| {
+                Ok(NamespaceInfo {
+                    name: row.get(0)?,
+                    total_facts: row.get::<_, i64>(1)? as usize,
+                    active_facts: row.get::<_, i64>(2)? as usize,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(namespaces)
+    }
+
+    /// Move every fact at or under `path` (any status, so history, notices
+    /// and warnings travel along) from this storage's namespace to `to`
+    ///
+    /// # Returns
+    /// The number of facts moved
+    pub fn move_to_namespace(&self, path: &str, to: &str) -> Result<usize> {
+        if to == self.namespace {
+            anyhow::bail!("Facts are already in namespace '{}'", to);
         }
-        Ok(())
+
+        // A range, not LIKE: `_` and `%` are literal in paths.
+        // '0' sorts right after '/': the range is "everything under"
+        let path = path.trim_end_matches('/');
+        let moved = self.conn.execute(
+            r#"
+            UPDATE facts SET namespace = ?3
+            WHERE namespace = ?2
+            AND (path = ?1 OR (path >= ?1 || '/' AND path < ?1 || '0'))
+            "#,
+            params![path, self.namespace, to],
+        )?;
+
+        Ok(moved)
+    }
+
+    /// Approve a pending_review fact (set status to active)
+    ///
+    /// If the fact is a correction, the fact it supersedes is marked
+    /// superseded in the same transaction.
+    pub fn approve_fact(&self, id: &Ulid) -> Result<()> {
+        self.transaction(|tx| {
+            let fact = match tx.get_by_id(id)? {
+                Some(fact) if fact.status == Status::PendingReview => fact,
+                _ => anyhow::bail!("Fact {} not found or not pending review", id),
+            };
+
+            tx.conn.execute(
+                "UPDATE facts SET status = 'active', updated_at = ?2 WHERE id = ?1",
+                params![id.to_string(), chrono::Utc::now().to_rfc3339()],
+            )?;
+
+            for original in fact.superseded_ids() {
+                tx.mark_superseded(&original)?;
+            }
+
+            Ok(())
+        })
     }
 
     /// Reject a pending_review fact (delete it)
@@ -582,20 +2194,18 @@ impl Storage {
             anyhow::bail!("Fact {} not found or not pending review", id);
         }
 
-        // Rebuild FTS
-        self.conn
-            .execute("INSERT INTO facts_fts(facts_fts) VALUES('rebuild')", [])?;
         Ok(())
     }
 
     /// Get all pending_review facts
     pub fn get_pending_review(&self) -> Result<Vec<Fact>> {
         let mut stmt = self.conn.prepare(
-            "SELECT * FROM facts WHERE status = 'pending_review' ORDER BY created_at DESC",
+            "SELECT * FROM facts WHERE status = 'pending_review' AND namespace = ?1
+             ORDER BY created_at DESC",
         )?;
 
         let facts = stmt
-            .query_map([], Self::row_to_fact)?
+            .query_map([&self.namespace], Self::row_to_fact)?
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(facts)
@@ -603,80 +2213,470 @@ impl Storage {
 
     /// Garbage collect old deprecated/superseded facts
     ///
-    /// Removes facts that are:
+    /// Candidates are facts that are:
     /// - status = 'deprecated' AND updated_at older than retention_days
     /// - superseded_by IS NOT NULL (someone created a correction) AND updated_at older than retention_days
     ///
+    /// In `GcMode::Archive` candidates get status 'archived': search and path
+    /// queries skip them, but `get_by_id` and history chains still read them.
+    /// In `GcMode::Purge` candidates (and previously archived facts) are deleted.
+    /// Only facts of this storage's namespace are collected.
+    ///
     /// # Arguments
     /// * `retention_days` - How many days to keep deprecated facts (default: 30)
-    /// * `dry_run` - If true, only return candidates without deleting
+    /// * `mode` - Archive or purge
+    /// * `dry_run` - If true, only return candidates without changing anything
     ///
     /// # Returns
-    /// GcResult with count of deleted facts and list of candidates
-    pub fn garbage_collect(&self, retention_days: u32, dry_run: bool) -> Result<GcResult> {
+    /// GcResult with counts of archived/deleted facts and list of candidates
+    pub fn garbage_collect(
+        &self,
+        retention_days: u32,
+        mode: GcMode,
+        dry_run: bool,
+    ) -> Result<GcResult> {
         let cutoff = chrono::Utc::now() - chrono::Duration::days(retention_days as i64);
         let cutoff_str = cutoff.to_rfc3339();
 
+        // Archiving never touches already archived facts; purging also
+        // clears out the archive. Facts superseded only by a correction or
+        // merge awaiting review are still live.
+        let candidate_filter = match mode {
+            GcMode::Archive => {
+                r#"status != 'archived' AND (status = 'deprecated' OR id IN (
+                    SELECT l.target FROM fact_links l
+                    JOIN facts s ON s.id = l.source_id
+                    WHERE l.kind = 'supersedes' AND s.status != 'pending_review'
+                ))"#
+            }
+            GcMode::Purge => {
+                r#"(status IN ('deprecated', 'archived') OR id IN (
+                    SELECT l.target FROM fact_links l
+                    JOIN facts s ON s.id = l.source_id
+                    WHERE l.kind = 'supersedes' AND s.status != 'pending_review'
+                ))"#
+            }
+        };
+
         // Find candidates
-        let mut stmt = self.conn.prepare(
+        let mut stmt = self.conn.prepare(&format!(
             r#"
             SELECT id, path, status, updated_at,
                    CASE 
                        WHEN status = 'deprecated' THEN 'deprecated'
+                       WHEN status = 'archived' THEN 'archived'
                        ELSE 'superseded'
                    END as reason
             FROM facts 
-            WHERE (status = 'deprecated' OR id IN (
-                SELECT supersedes FROM facts WHERE supersedes IS NOT NULL
-            ))
-            AND updated_at < ?1
+            WHERE {}
+            AND updated_at < ?1 AND namespace = ?2
             ORDER BY updated_at ASC
             "#,
-        )?;
+            candidate_filter
+        ))?;
 
         let candidates: Vec<GcCandidate> = stmt
-            .query_map([&cutoff_str], |row| {
+            .query_map([&cutoff_str, &self.namespace], |row| {
                 let reason_str: String = row.get(4)?;
                 Ok(GcCandidate {
                     id: row.get(0)?,
                     path: row.get(1)?,
-                    reason: if reason_str == "deprecated" {
-                        GcReason::Deprecated
-                    } else {
-                        GcReason::Superseded
+                    reason: match reason_str.as_str() {
+                        "deprecated" => GcReason::Deprecated,
+                        "archived" => GcReason::Archived,
+                        _ => GcReason::Superseded,
                     },
                     updated_at: row.get(3)?,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
 
-        if dry_run {
-            return Ok(GcResult {
-                deleted_count: 0,
-                candidates,
-            });
+        if dry_run {
+            return Ok(GcResult {
+                deleted_count: 0,
+                archived_count: 0,
+                candidates,
+            });
+        }
+
+        let mut result = GcResult {
+            deleted_count: 0,
+            archived_count: 0,
+            candidates,
+        };
+
+        match mode {
+            GcMode::Archive => {
+                // Status-only change: the FTS index is untouched, search
+                // filters on status
+                result.archived_count = self.conn.execute(
+                    &format!(
+                        "UPDATE facts SET status = 'archived', updated_at = ?2
+                         WHERE {} AND updated_at < ?1 AND namespace = ?3",
+                        candidate_filter
+                    ),
+                    params![
+                        &cutoff_str,
+                        chrono::Utc::now().to_rfc3339(),
+                        &self.namespace
+                    ],
+                )?;
+            }
+            GcMode::Purge => {
+                // facts_ad trigger removes FTS entries
+                result.deleted_count = self.conn.execute(
+                    &format!(
+                        "DELETE FROM facts WHERE {} AND updated_at < ?1 AND namespace = ?2",
+                        candidate_filter
+                    ),
+                    [&cutoff_str, &self.namespace],
+                )?;
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Archived facts, most recently archived first
+    pub fn get_archived(&self, path_prefix: Option<&str>, limit: i64) -> Result<Vec<Fact>> {
+        let pattern = format!("{}%", path_prefix.unwrap_or("").trim_end_matches('/'));
+
+        let mut stmt = self.conn.prepare(
+            "SELECT * FROM facts WHERE status = 'archived' AND path LIKE ?1 AND namespace = ?3
+             ORDER BY updated_at DESC LIMIT ?2",
+        )?;
+
+        let facts = stmt
+            .query_map(params![pattern, limit, self.namespace], Self::row_to_fact)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(facts)
+    }
+
+    /// Bring an archived fact back
+    ///
+    /// Facts that a newer fact supersedes return as 'superseded'; anything
+    /// else (typically a deprecated fact) returns as 'active'.
+    ///
+    /// # Returns
+    /// The status the fact was restored to
+    pub fn restore_archived(&self, id: &Ulid) -> Result<Status> {
+        let superseded: bool = self.conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM fact_links WHERE target = ?1 AND kind = 'supersedes')",
+            [id.to_string()],
+            |row| row.get(0),
+        )?;
+        let status = if superseded {
+            Status::Superseded
+        } else {
+            Status::Active
+        };
+
+        let updated = self.conn.execute(
+            "UPDATE facts SET status = ?2, updated_at = ?3 WHERE id = ?1 AND status = 'archived'",
+            params![
+                id.to_string(),
+                status.as_str(),
+                chrono::Utc::now().to_rfc3339()
+            ],
+        )?;
+
+        if updated == 0 {
+            anyhow::bail!("Fact {} not found or not archived", id);
+        }
+
+        Ok(status)
+    }
+
+    /// Rebuild the full-text index from the facts table
+    ///
+    /// Normally not needed: triggers keep `facts_fts` in sync. Use this to
+    /// recover from a corrupted or out-of-sync index.
+    pub fn reindex(&self) -> Result<()> {
+        self.conn
+            .execute("INSERT INTO facts_fts(facts_fts) VALUES('rebuild')", [])?;
+        self.conn
+            .execute("INSERT INTO facts_fts(facts_fts) VALUES('optimize')", [])?;
+        if self.has_fuzzy_index()? {
+            self.conn.execute(
+                "INSERT INTO facts_trigram(facts_trigram) VALUES('rebuild')",
+                [],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Whether the trigram index for fuzzy search exists
+    pub fn has_fuzzy_index(&self) -> Result<bool> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'facts_trigram'",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
+    /// Create (and fill) or drop the trigram index for fuzzy search
+    ///
+    /// Creating it indexes every fact once; afterwards triggers keep it in
+    /// sync.
+    fn set_fuzzy_index(&self, enabled: bool) -> Result<()> {
+        if enabled == self.has_fuzzy_index()? {
+            return Ok(());
+        }
+        let sql = if enabled {
+            FUZZY_INDEX
+        } else {
+            DROP_FUZZY_INDEX
+        };
+        self.transaction(|s| Ok(s.conn.execute_batch(sql)?))
+            .context("Failed to update the fuzzy search index")
+    }
+
+    /// Check database integrity and fact consistency
+    ///
+    /// `row_to_fact` is lenient (bad IDs, statuses and timestamps fall back
+    /// to defaults), so this reads rows strictly to surface corruption.
+    pub fn check(&self) -> Result<CheckReport> {
+        let mut issues = Vec::new();
+
+        // 1. SQLite page-level integrity
+        let mut stmt = self.conn.prepare("PRAGMA integrity_check")?;
+        for message in stmt.query_map([], |row| row.get::<_, String>(0))? {
+            let message = message?;
+            if message != "ok" {
+                issues.push(CheckIssue::new(CheckKind::Integrity, None, message));
+            }
+        }
+        drop(stmt);
+
+        // The other checks need the current schema
+        let current = migrations::current_version(&self.conn)?;
+        let latest = migrations::latest_version(MIGRATIONS);
+        if current < latest {
+            issues.push(CheckIssue::new(
+                CheckKind::OutdatedSchema,
+                None,
+                format!(
+                    "schema v{} / v{}: migrate (`meh db check --fix`) to check the facts",
+                    current, latest
+                ),
+            ));
+            return Ok(CheckReport {
+                facts_checked: 0,
+                issues,
+            });
+        }
+
+        // 2. FTS index matches the facts table
+        if let Err(e) = self.conn.execute(
+            "INSERT INTO facts_fts(facts_fts, rank) VALUES('integrity-check', 1)",
+            [],
+        ) {
+            issues.push(CheckIssue::new(
+                CheckKind::FtsMismatch,
+                None,
+                format!("facts_fts does not match facts: {}", e),
+            ));
+        }
+
+        // 3. Strict row parsing
+        let mut stmt = self.conn.prepare(
+            "SELECT id, status, fact_type, author_type, tags, extends, supersedes,
+                    created_at, updated_at, accessed_at
+             FROM facts",
+        )?;
+        let mut rows = stmt.query([])?;
+        let mut facts_checked = 0;
+        while let Some(row) = rows.next()? {
+            facts_checked += 1;
+            let id: String = row.get(0)?;
+            for problem in Self::strict_parse_problems(row)? {
+                issues.push(CheckIssue::new(
+                    CheckKind::InvalidRow,
+                    Some(id.clone()),
+                    problem,
+                ));
+            }
+        }
+        drop(rows);
+        drop(stmt);
+
+        // 4. Extensions of missing facts
+        let mut stmt = self.conn.prepare(
+            "SELECT source_id, target FROM fact_links l
+             WHERE kind = 'extends'
+             AND NOT EXISTS (SELECT 1 FROM facts t WHERE t.id = l.target)",
+        )?;
+        for row in stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })? {
+            let (id, target) = row?;
+            issues.push(CheckIssue::new(
+                CheckKind::DanglingExtends,
+                Some(id),
+                format!("extends missing fact {}", target),
+            ));
+        }
+
+        // 5. Supersede pointers to missing facts
+        let mut stmt = self.conn.prepare(
+            "SELECT id, supersedes FROM facts f
+             WHERE supersedes IS NOT NULL
+             AND NOT EXISTS (SELECT 1 FROM facts o WHERE o.id = f.supersedes)",
+        )?;
+        for row in stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })? {
+            let (id, target) = row?;
+            issues.push(CheckIssue::new(
+                CheckKind::DanglingSupersedes,
+                Some(id),
+                format!("supersedes missing fact {}", target),
+            ));
+        }
+
+        // 6. Forked chains: one fact superseded by several live corrections
+        let mut stmt = self.conn.prepare(
+            "SELECT supersedes, GROUP_CONCAT(id, ', ') FROM facts
+             WHERE supersedes IS NOT NULL AND status IN ('active', 'superseded')
+             GROUP BY supersedes HAVING COUNT(*) > 1",
+        )?;
+        for row in stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })? {
+            let (original, successors) = row?;
+            issues.push(CheckIssue::new(
+                CheckKind::ForkedChain,
+                Some(original),
+                format!("superseded by several facts: {}", successors),
+            ));
+        }
+
+        // 7. Originals still active although an active correction supersedes them
+        //    (left behind by interrupted non-atomic corrections)
+        let mut stmt = self.conn.prepare(
+            "SELECT o.id, c.id FROM facts o
+             JOIN facts c ON c.supersedes = o.id
+             WHERE o.status = 'active' AND c.status = 'active'",
+        )?;
+        for row in stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })? {
+            let (original, correction) = row?;
+            issues.push(CheckIssue::new(
+                CheckKind::UnsupersededOriginal,
+                Some(original),
+                format!("still active but superseded by {}", correction),
+            ));
+        }
+
+        Ok(CheckReport {
+            facts_checked,
+            issues,
+        })
+    }
+
+    /// Apply the safe repairs for a check report
+    ///
+    /// Rebuilds the FTS index and marks stale originals as superseded.
+    /// Other issues need a human decision and are left alone.
+    ///
+    /// # Returns
+    /// Number of issues repaired
+    pub fn repair(&self, report: &CheckReport) -> Result<usize> {
+        self.transaction(|tx| {
+            let mut repaired = 0;
+
+            for issue in report.issues.iter().filter(|i| i.kind.is_fixable()) {
+                match issue.kind {
+                    CheckKind::FtsMismatch => {
+                        tx.conn
+                            .execute("INSERT INTO facts_fts(facts_fts) VALUES('rebuild')", [])?;
+                    }
+                    CheckKind::UnsupersededOriginal => {
+                        let id = issue.fact_id.as_deref().unwrap_or_default();
+                        tx.conn.execute(
+                            "UPDATE facts SET status = 'superseded', updated_at = ?2 WHERE id = ?1",
+                            params![id, chrono::Utc::now().to_rfc3339()],
+                        )?;
+                    }
+                    _ => continue,
+                }
+                repaired += 1;
+            }
+
+            Ok(repaired)
+        })
+    }
+
+    /// Everything `row_to_fact` would silently paper over
+    /// (columns as selected in `check`)
+    fn strict_parse_problems(row: &rusqlite::Row) -> rusqlite::Result<Vec<String>> {
+        let mut problems = Vec::new();
+
+        let id: String = row.get(0)?;
+        if Ulid::from_string(&id).is_err() {
+            problems.push(format!("invalid id '{}'", id));
+        }
+
+        let status: String = row.get(1)?;
+        if !matches!(
+            status.as_str(),
+            "active" | "superseded" | "deprecated" | "archived" | "pending_review"
+        ) {
+            problems.push(format!("unknown status '{}'", status));
+        }
+
+        let fact_type: String = row.get(2)?;
+        if !matches!(
+            fact_type.as_str(),
+            "fact" | "correction" | "extension" | "warning" | "deprecation"
+        ) {
+            problems.push(format!("unknown fact_type '{}'", fact_type));
+        }
+
+        let author_type: String = row.get(3)?;
+        if !matches!(author_type.as_str(), "human" | "ai" | "system") {
+            problems.push(format!("unknown author_type '{}'", author_type));
+        }
+
+        let tags: Option<String> = row.get(4)?;
+        if tags
+            .as_deref()
+            .is_none_or(|json| serde_json::from_str::<Vec<String>>(json).is_err())
+        {
+            problems.push("tags is not a JSON string array".to_string());
+        }
+
+        let extends: Option<String> = row.get(5)?;
+        match extends.as_deref().map(serde_json::from_str::<Vec<String>>) {
+            Some(Ok(ids)) => {
+                for bad in ids.iter().filter(|i| Ulid::from_string(i).is_err()) {
+                    problems.push(format!("invalid extends id '{}'", bad));
+                }
+            }
+            _ => problems.push("extends is not a JSON string array".to_string()),
         }
 
-        // Actually delete (also from FTS)
-        let deleted_count = self.conn.execute(
-            r#"
-            DELETE FROM facts 
-            WHERE (status = 'deprecated' OR id IN (
-                SELECT supersedes FROM facts WHERE supersedes IS NOT NULL
-            ))
-            AND updated_at < ?1
-            "#,
-            [&cutoff_str],
-        )?;
+        let supersedes: Option<String> = row.get(6)?;
+        if let Some(s) = supersedes.filter(|s| Ulid::from_string(s).is_err()) {
+            problems.push(format!("invalid supersedes id '{}'", s));
+        }
 
-        // Rebuild FTS index after deletion
-        self.conn
-            .execute("INSERT INTO facts_fts(facts_fts) VALUES('rebuild')", [])?;
+        for (idx, column) in [(7, "created_at"), (8, "updated_at"), (9, "accessed_at")] {
+            let value: Option<String> = row.get(idx)?;
+            let required = column != "accessed_at";
+            match value {
+                Some(v) if chrono::DateTime::parse_from_rfc3339(&v).is_err() => {
+                    problems.push(format!("invalid {} '{}'", column, v));
+                }
+                None if required => problems.push(format!("missing {}", column)),
+                _ => {}
+            }
+        }
 
-        Ok(GcResult {
-            deleted_count,
-            candidates,
-        })
+        Ok(problems)
     }
 }
 
@@ -694,14 +2694,86 @@ pub struct StorageStats {
     pub total_facts: usize,
     pub active_facts: usize,
     pub deprecated_facts: usize,
+    pub superseded_facts: usize,
+    pub archived_facts: usize,
     pub pending_review_facts: usize,
 }
 
+/// A namespace and how many facts it holds
+#[derive(Debug, Clone)]
+pub struct NamespaceInfo {
+    /// Namespace name (`""` is the default namespace)
+    pub name: String,
+    pub total_facts: usize,
+    pub active_facts: usize,
+}
+
+/// Facts with identical normalized content
+#[derive(Debug, Clone)]
+pub struct DuplicateCluster {
+    pub content_hash: String,
+    pub facts: Vec<Fact>,
+}
+
+/// Why a reference is broken
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrokenReason {
+    /// No fact with that ID or path
+    Missing,
+    /// Only deprecated or archived facts match
+    Deprecated,
+}
+
+impl BrokenReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BrokenReason::Missing => "missing",
+            BrokenReason::Deprecated => "deprecated",
+        }
+    }
+}
+
+/// A `references` link that points nowhere useful
+#[derive(Debug, Clone)]
+pub struct BrokenReference {
+    /// Fact whose content holds the reference
+    pub source_id: Ulid,
+    pub source_path: String,
+    /// Referenced ID or path
+    pub target: String,
+    pub reason: BrokenReason,
+}
+
+/// Read counters for a fact
+#[derive(Debug, Clone)]
+pub struct FactUsage {
+    pub id: String,
+    pub path: String,
+    pub title: String,
+    pub access_count: u64,
+    /// RFC 3339 timestamp of the last read (`None` if never read)
+    pub last_accessed_at: Option<String>,
+}
+
+/// What garbage collection does with old deprecated/superseded facts
+/// (`[core] gc_mode`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum GcMode {
+    /// Set status to archived: hidden from search, kept for history
+    #[default]
+    Archive,
+    /// Permanently delete the rows
+    Purge,
+}
+
 /// Result of garbage collection
 #[derive(Debug, Clone)]
 pub struct GcResult {
-    /// Number of facts deleted
+    /// Number of facts deleted (purge mode)
     pub deleted_count: usize,
+    /// Number of facts archived (archive mode)
+    pub archived_count: usize,
     /// Facts that would be deleted (dry-run)
     pub candidates: Vec<GcCandidate>,
 }
@@ -720,12 +2792,151 @@ pub struct GcCandidate {
 pub enum GcReason {
     Deprecated,
     Superseded,
+    /// Already archived (only purged in purge mode)
+    Archived,
+}
+
+/// Result of `Storage::check`
+#[derive(Debug, Clone, Default)]
+pub struct CheckReport {
+    /// Number of fact rows inspected
+    pub facts_checked: usize,
+    pub issues: Vec<CheckIssue>,
+}
+
+impl CheckReport {
+    /// Whether any issue can be repaired by `Storage::repair`
+    pub fn has_fixable(&self) -> bool {
+        self.issues.iter().any(|i| i.kind.is_fixable())
+    }
+}
+
+/// A single problem found by `Storage::check`
+#[derive(Debug, Clone)]
+pub struct CheckIssue {
+    pub kind: CheckKind,
+    /// Affected fact (if any)
+    pub fact_id: Option<String>,
+    pub detail: String,
+}
+
+impl CheckIssue {
+    fn new(kind: CheckKind, fact_id: Option<String>, detail: impl Into<String>) -> Self {
+        Self {
+            kind,
+            fact_id,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Category of a check issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckKind {
+    /// SQLite `integrity_check` failure
+    Integrity,
+    /// `facts_fts` out of sync with `facts`
+    FtsMismatch,
+    /// Row that fails strict parsing
+    InvalidRow,
+    /// `supersedes` points to a missing fact (often a GC'd original)
+    DanglingSupersedes,
+    /// `extends` references a missing fact
+    DanglingExtends,
+    /// Several live facts supersede the same original
+    ForkedChain,
+    /// Original still active although an active correction supersedes it
+    UnsupersededOriginal,
+    /// Migrations not applied yet (the other checks are skipped)
+    OutdatedSchema,
+}
+
+impl CheckKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CheckKind::Integrity => "integrity",
+            CheckKind::FtsMismatch => "fts_mismatch",
+            CheckKind::InvalidRow => "invalid_row",
+            CheckKind::DanglingSupersedes => "dangling_supersedes",
+            CheckKind::DanglingExtends => "dangling_extends",
+            CheckKind::ForkedChain => "forked_chain",
+            CheckKind::UnsupersededOriginal => "unsuperseded_original",
+            CheckKind::OutdatedSchema => "outdated_schema",
+        }
+    }
+
+    /// Whether `meh db check --fix` can fix this safely (`Storage::repair`,
+    /// or migrating for an outdated schema)
+    pub fn is_fixable(&self) -> bool {
+        matches!(
+            self,
+            CheckKind::FtsMismatch | CheckKind::UnsupersededOriginal | CheckKind::OutdatedSchema
+        )
+    }
+
+    /// Errors mean data is wrong; warnings may be normal after GC
+    pub fn is_error(&self) -> bool {
+        !matches!(
+            self,
+            CheckKind::DanglingSupersedes | CheckKind::DanglingExtends
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_check_leaves_outdated_schema_alone() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let db_path = dir.path().join("data.db");
+        Connection::open(&db_path)?.execute_batch(SCHEMA_V1)?;
+
+        let storage = Storage::open_unmigrated(&db_path)?;
+        let report = storage.check()?;
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].kind, CheckKind::OutdatedSchema);
+        assert_eq!(migrations::current_version(&storage.conn)?, 0);
+
+        storage.migrate()?;
+        assert!(storage.check()?.issues.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_unversioned_database_is_adopted() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let db_path = dir.path().join("data.db");
+
+        // Database created before schema versioning existed
+        {
+            let conn = Connection::open(&db_path)?;
+            conn.execute_batch(SCHEMA_V1)?;
+        }
+        {
+            let storage = Storage::open(&db_path)?;
+            storage.insert(&Fact::new("@a", "A", "Content"))?;
+        }
+
+        let conn = Connection::open(&db_path)?;
+        assert_eq!(
+            migrations::current_version(&conn)?,
+            migrations::latest_version(MIGRATIONS)
+        );
+
+        // A database from a newer meh is refused
+        conn.execute(
+            "INSERT INTO schema_version (version, description, applied_at) VALUES (999, 'future', '')",
+            [],
+        )?;
+        drop(conn);
+        let err = Storage::open(&db_path).err().unwrap();
+        assert!(err.to_string().contains("schema version 999"));
+
+        Ok(())
+    }
+
     #[test]
     fn test_create_and_query() -> Result<()> {
         let storage = Storage::open_memory()?;
@@ -781,6 +2992,69 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_find_by_pattern() -> Result<()> {
+        let storage = Storage::open_memory()?;
+        storage.insert(&Fact::new("@products/alpha/api/timeout", "T1", "C1"))?;
+        storage.insert(&Fact::new("@products/alpha/api/v2/rate", "T2", "C2"))?;
+        storage.insert(&Fact::new("@products/beta/api", "T3", "C3"))?;
+        storage.insert(&Fact::new("@products/beta/docs/api", "T4", "C4"))?;
+        storage.insert(&Fact::new("@productsx/alpha/api/x", "T5", "C5"))?;
+        let mut tagged = Fact::new("@products/gamma/api/auth", "T6", "C6");
+        tagged.tags = vec!["auth".into()];
+        storage.insert(&tagged)?;
+        let deprecated = Fact::new("@products/old/api/timeout", "T7", "C7");
+        storage.insert(&deprecated)?;
+        storage.mark_deprecated(&deprecated.id)?;
+
+        let paths = |pattern: &str, statuses: &[Status], tags: &[String]| -> Result<Vec<String>> {
+            Ok(storage
+                .find_by_pattern(pattern, statuses, tags, 100)?
+                .into_iter()
+                .map(|f| f.path)
+                .collect())
+        };
+        assert_eq!(
+            paths("@products/*/api/**", &[], &[])?,
+            vec![
+                "@products/alpha/api/timeout",
+                "@products/alpha/api/v2/rate",
+                "@products/beta/api",
+                "@products/gamma/api/auth",
+            ]
+        );
+        assert_eq!(
+            paths("@products/*/api", &[], &[])?,
+            vec!["@products/beta/api"]
+        );
+        assert_eq!(
+            paths("**/api", &[], &[])?,
+            vec!["@products/beta/api", "@products/beta/docs/api"]
+        );
+        assert_eq!(
+            paths("@products/**", &[], &["auth".into()])?,
+            vec!["@products/gamma/api/auth"]
+        );
+        assert_eq!(
+            paths("@products/*/api/timeout", &[Status::Deprecated], &[])?,
+            vec!["@products/old/api/timeout"]
+        );
+        assert!(storage
+            .find_by_pattern("@products/al*", &[], &[], 10)
+            .is_err());
+
+        // The literal prefix is an index range, not a full scan
+        let mut values = Vec::new();
+        let sql = storage.find_sql("@products/*/api/**", &[], &[], &mut values);
+        let plan: Vec<String> = storage
+            .conn
+            .prepare(&format!("EXPLAIN QUERY PLAN {}", sql))?
+            .query_map(rusqlite::params_from_iter(values), |row| row.get(3))?
+            .collect::<Result<_, _>>()?;
+        assert!(plan.iter().any(|p| p.contains("USING INDEX")), "{:?}", plan);
+        Ok(())
+    }
+
     #[test]
     fn test_garbage_collect_deprecated() -> Result<()> {
         let storage = Storage::open_memory()?;
@@ -795,12 +3069,12 @@ mod tests {
         storage.insert(&Fact::new("@test/active", "Active", "Keep this"))?;
 
         // Dry run should find the deprecated fact
-        let dry_result = storage.garbage_collect(30, true)?;
+        let dry_result = storage.garbage_collect(30, GcMode::Purge, true)?;
         assert_eq!(dry_result.candidates.len(), 1);
         assert_eq!(dry_result.deleted_count, 0); // Dry run doesn't delete
 
         // Actually delete
-        let result = storage.garbage_collect(30, false)?;
+        let result = storage.garbage_collect(30, GcMode::Purge, false)?;
         assert_eq!(result.deleted_count, 1);
 
         // Active fact should still exist
@@ -826,7 +3100,7 @@ mod tests {
         old.updated_at = chrono::Utc::now() - chrono::Duration::days(60);
         storage.insert(&old)?;
 
-        let result = storage.garbage_collect(30, false)?;
+        let result = storage.garbage_collect(30, GcMode::Purge, false)?;
         assert_eq!(result.deleted_count, 1); // Only old one
 
         // Recent should still exist (check by ID since search filters by status)
@@ -844,10 +3118,549 @@ mod tests {
     fn test_garbage_collect_empty_db() -> Result<()> {
         let storage = Storage::open_memory()?;
 
-        let result = storage.garbage_collect(30, false)?;
+        let result = storage.garbage_collect(30, GcMode::Purge, false)?;
         assert_eq!(result.deleted_count, 0);
         assert!(result.candidates.is_empty());
 
         Ok(())
     }
+
+    #[test]
+    fn test_garbage_collect_archives() -> Result<()> {
+        let storage = Storage::open_memory()?;
+
+        let mut original = Fact::new("@test/timeout", "Timeout", "Zanzibar 30s");
+        original.updated_at = chrono::Utc::now() - chrono::Duration::days(60);
+        storage.insert(&original)?;
+        let correction = Fact::correction(&original, "Zanzibar 60s");
+        storage.insert(&correction)?;
+        storage.conn.execute(
+            "UPDATE facts SET status = 'superseded' WHERE id = ?1",
+            [original.id.to_string()],
+        )?;
+
+        assert_eq!(storage.stats()?.superseded_facts, 1);
+        let result = storage.garbage_collect(30, GcMode::Archive, false)?;
+        assert_eq!(result.archived_count, 1);
+        assert_eq!(result.deleted_count, 0);
+        let stats = storage.stats()?;
+        assert_eq!((stats.superseded_facts, stats.archived_facts), (0, 1));
+
+        // Hidden from search, still readable through history
+        let archived = storage.get_by_id(&original.id)?.unwrap();
+        assert_eq!(archived.status, Status::Archived);
+        assert_eq!(storage.search("zanzibar", 10)?.len(), 1);
+        assert_eq!(storage.get_history_chain(&correction.id)?.len(), 2);
+        let (latest, resolved) = storage
+            .resolve_to_latest(&format!("meh-{}", original.id))?
+            .unwrap();
+        assert_eq!(latest.id, correction.id);
+        assert!(resolved);
+
+        // Archived facts are not archived again
+        let again = storage.garbage_collect(0, GcMode::Archive, true)?;
+        assert!(again.candidates.is_empty());
+
+        assert_eq!(storage.get_archived(None, 10)?.len(), 1);
+        assert_eq!(storage.restore_archived(&original.id)?, Status::Superseded);
+        assert!(storage.get_archived(Some("@test"), 10)?.is_empty());
+        assert!(storage.restore_archived(&original.id).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_duplicates() -> Result<()> {
+        let storage = Storage::open_memory()?;
+
+        let elsewhere = Fact::new("@other/timeout", "Timeout", "API timeout is 30s.");
+        storage.insert(&elsewhere)?;
+        let same_path = Fact::new("@api/timeout", "Timeout", "**API timeout** is 30s.");
+        storage.insert(&same_path)?;
+        storage.insert(&Fact::new("@api/retries", "Retries", "3 retries"))?;
+
+        // Votes never count as duplicates
+        storage.insert_vote(&Fact::vote(&same_path, "+1", "agree"))?;
+        storage.insert_vote(&Fact::vote(&same_path, "+1", "agree"))?;
+
+        let candidate = Fact::new("@api/timeout", "Timeout", "api timeout is 30s.");
+        let duplicates = storage.find_duplicates(&candidate)?;
+        assert_eq!(duplicates.len(), 2);
+        assert_eq!(duplicates[0].id, same_path.id);
+        assert_eq!(
+            storage
+                .links_to(&same_path.id.to_string(), Some(LinkKind::Vote))?
+                .len(),
+            2
+        );
+
+        let clusters = storage.duplicate_clusters()?;
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].facts[0].id, elsewhere.id);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_attachments_follow_corrections() -> Result<()> {
+        let storage = Storage::open_memory()?;
+
+        let mut original = Fact::new("@bugs/crash", "Crash", "Segfault on start");
+        original.status = Status::Deprecated;
+        original.updated_at = chrono::Utc::now() - chrono::Duration::days(60);
+        storage.insert(&original)?;
+        storage.add_attachment(&original.id, "trace.log", "text/plain", b"frame 0")?;
+        storage.add_attachment(&original.id, "trace.log", "text/plain", b"frame 1")?;
+        storage.add_attachment(&original.id, "trace.log", "text/plain", b"frame 1")?;
+
+        let correction = Fact::correction(&original, "Segfault on start (fixed in 1.2)");
+        storage.insert(&correction)?;
+
+        let attachments = storage.list_attachments(&correction.id)?;
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].size, 7);
+        let (_, data) = storage
+            .get_attachment(&correction.id, "trace.log")?
+            .unwrap();
+        assert_eq!(data, b"frame 1");
+
+        // Replaced and shared blobs are only dropped when unreferenced
+        let blobs = |s: &Storage| -> Result<i64> {
+            Ok(s.conn
+                .query_row("SELECT COUNT(*) FROM blobs", [], |r| r.get(0))?)
+        };
+        assert_eq!(blobs(&storage)?, 1);
+        storage.garbage_collect(30, GcMode::Purge, false)?;
+        assert!(storage.list_attachments(&original.id)?.is_empty());
+        assert_eq!(blobs(&storage)?, 1);
+        assert!(storage.remove_attachment(&correction.id, "trace.log")?);
+        assert_eq!(blobs(&storage)?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_properties_roundtrip() -> Result<()> {
+        let storage = Storage::open_memory()?;
+
+        let bug = Fact::new("@bugs/crash", "Crash", "Segfault")
+            .with_property("severity", "high")
+            .with_property("component", "billing");
+        storage.insert(&bug)?;
+        storage
+            .insert(&Fact::new("@bugs/typo", "Typo", "Label").with_property("severity", "low"))?;
+        storage.insert(&Fact::new("@notes", "Notes", "No properties"))?;
+
+        let loaded = storage.get_by_id(&bug.id)?.unwrap();
+        assert_eq!(loaded.properties, bug.properties);
+
+        assert_eq!(storage.get_by_property("severity", None, 10)?.len(), 2);
+        let high = storage.get_by_property("severity", Some("high"), 10)?;
+        assert_eq!(high.len(), 1);
+        assert_eq!(high[0].id, bug.id);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_warnings() -> Result<()> {
+        let storage = Storage::open_memory()?;
+
+        let fact = Fact::new("@test/api", "API", "Timeout is 30s");
+        storage.insert(&fact)?;
+        let warning = Fact::warning(&fact, "Not on the /health endpoint");
+        storage.insert(&warning)?;
+        storage.insert(&Fact::extension(&fact, "Also for websockets"))?;
+
+        let warnings = storage.get_warnings(&fact.id)?;
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(storage.get_by_path("@test/api")?.len(), 2);
+        assert_eq!(warnings[0].id, warning.id);
+        assert_eq!(warnings[0].fact_type, FactType::Warning);
+        // The target stays current
+        assert_eq!(storage.get_by_id(&fact.id)?.unwrap().status, Status::Active);
+
+        storage.mark_deprecated(&warning.id)?;
+        assert!(storage.get_warnings(&fact.id)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fts_stays_in_sync_on_delete() -> Result<()> {
+        let storage = Storage::open_memory()?;
+
+        let mut pending = Fact::new("@test/pending", "Pending", "Zanzibar pending fact");
+        pending.status = Status::PendingReview;
+        storage.insert(&pending)?;
+
+        let mut old = Fact::new("@test/old", "Old", "Zanzibar deprecated fact");
+        old.status = Status::Deprecated;
+        old.updated_at = chrono::Utc::now() - chrono::Duration::days(60);
+        storage.insert(&old)?;
+
+        let kept = Fact::new("@test/kept", "Kept", "Zanzibar active fact");
+        storage.insert(&kept)?;
+
+        storage.reject_fact(&pending.id)?;
+        storage.garbage_collect(30, GcMode::Purge, false)?;
+
+        // 'integrity-check' with rank=1 compares the index against the facts table
+        storage.conn.execute(
+            "INSERT INTO facts_fts(facts_fts, rank) VALUES('integrity-check', 1)",
+            [],
+        )?;
+
+        let fts_rows: i64 = storage.conn.query_row(
+            "SELECT COUNT(*) FROM facts_fts WHERE facts_fts MATCH 'zanzibar'",
+            [],
+            |row| row.get(0),
+        )?;
+        assert_eq!(fts_rows, 1);
+
+        storage.reindex()?;
+        let results = storage.search("zanzibar", 10)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, kept.id);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_transaction_rolls_back_on_error() -> Result<()> {
+        let storage = Storage::open_memory()?;
+        let original = Fact::new("@test/timeout", "Timeout", "30s");
+        storage.insert(&original)?;
+
+        let correction = Fact::correction(&original, "60s");
+        let result: Result<()> = storage.transaction(|tx| {
+            tx.insert(&correction)?;
+            tx.mark_superseded(&original.id)?;
+            anyhow::bail!("simulated failure")
+        });
+        assert!(result.is_err());
+
+        // Neither write happened
+        assert!(storage.get_by_id(&correction.id)?.is_none());
+        let facts = storage.get_by_path("@test/timeout")?;
+        assert_eq!(facts.len(), 1);
+        assert_eq!(facts[0].id, original.id);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_approve_correction_supersedes_original() -> Result<()> {
+        let storage = Storage::open_memory()?;
+        let original = Fact::new("@test/timeout", "Timeout", "30s");
+        storage.insert(&original)?;
+
+        let mut correction = Fact::correction(&original, "60s");
+        correction.status = Status::PendingReview;
+        storage.insert(&correction)?;
+        assert_eq!(storage.get_pending_review()?.len(), 1);
+
+        storage.approve_fact(&correction.id)?;
+
+        let facts = storage.get_by_path("@test/timeout")?;
+        assert_eq!(facts.len(), 1);
+        assert_eq!(facts[0].id, correction.id);
+        assert_eq!(
+            storage.get_by_id(&original.id)?.unwrap().status,
+            Status::Superseded
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_supersedes_all_sources() -> Result<()> {
+        let storage = Storage::open_memory()?;
+        let mut first = Fact::new("@ops/deploy", "Deploy", "Deploy with make");
+        first.tags = vec!["ops".to_string()];
+        let mut second = Fact::new("@ops/release", "Release", "Tag before deploy");
+        second.tags = vec!["release".to_string()];
+        let third = Fact::new("@ops/rollback", "Rollback", "Rollback with make undo");
+        for fact in [&first, &second, &third] {
+            storage.insert(fact)?;
+        }
+
+        let sources = [first.clone(), second.clone(), third.clone()];
+        let merged = Fact::merge(&sources, "@ops/deploy", "Tag, make, make undo");
+        assert_eq!(merged.title, "Tag, make, make undo");
+        storage.insert_merge(&merged)?;
+
+        let stored = storage.get_by_id(&merged.id)?.unwrap();
+        assert_eq!(stored.merged_from.len(), 3);
+        assert_eq!(stored.tags, vec!["ops", "release"]);
+
+        for source in &sources {
+            assert_eq!(
+                storage.get_by_id(&source.id)?.unwrap().status,
+                Status::Superseded
+            );
+            let (latest, resolved) = storage
+                .resolve_to_latest(&format!("meh-{}", source.id))?
+                .unwrap();
+            assert_eq!(latest.id, merged.id);
+            assert!(resolved);
+        }
+
+        // A pending merge supersedes its sources once approved
+        let other = Fact::new("@ops/cache", "Cache", "Cache for 5m");
+        let another = Fact::new("@ops/ttl", "TTL", "TTL is 5m");
+        storage.insert(&other)?;
+        storage.insert(&another)?;
+        let mut pending = Fact::merge(&[other.clone(), another.clone()], "@ops/cache", "5m");
+        pending.status = Status::PendingReview;
+        storage.insert_merge(&pending)?;
+        assert_eq!(
+            storage.get_by_id(&another.id)?.unwrap().status,
+            Status::Active
+        );
+        let gc = storage.garbage_collect(0, GcMode::Purge, false)?;
+        assert!(gc.candidates.iter().all(|c| c.id != other.id.to_string()));
+        assert!(storage.get_by_id(&other.id)?.is_some());
+        assert!(storage.get_by_id(&another.id)?.is_some());
+        storage.approve_fact(&pending.id)?;
+        assert_eq!(
+            storage.get_by_id(&another.id)?.unwrap().status,
+            Status::Superseded
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_finds_and_repairs_issues() -> Result<()> {
+        let storage = Storage::open_memory()?;
+
+        let original = Fact::new("@test/timeout", "Timeout", "30s");
+        storage.insert(&original)?;
+        assert!(storage.check()?.issues.is_empty());
+
+        // Interrupted correction: original left active
+        let correction = Fact::correction(&original, "60s");
+        storage.insert(&correction)?;
+
+        // Corrupted row and dangling extension
+        let mut orphan = Fact::new("@test/orphan", "Orphan", "Extends nothing");
+        orphan.extends = vec![Ulid::new()];
+        storage.insert(&orphan)?;
+        storage.conn.execute(
+            "UPDATE facts SET status = 'bogus' WHERE id = ?1",
+            [orphan.id.to_string()],
+        )?;
+
+        let report = storage.check()?;
+        let kinds: Vec<CheckKind> = report.issues.iter().map(|i| i.kind).collect();
+        assert_eq!(report.facts_checked, 3);
+        assert!(kinds.contains(&CheckKind::UnsupersededOriginal));
+        assert!(kinds.contains(&CheckKind::InvalidRow));
+        assert!(kinds.contains(&CheckKind::DanglingExtends));
+
+        assert_eq!(storage.repair(&report)?, 1);
+        assert_eq!(
+            storage.get_by_id(&original.id)?.unwrap().status,
+            Status::Superseded
+        );
+        assert!(!storage.check()?.has_fixable());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_namespaces_are_isolated() -> Result<()> {
+        let mut storage = Storage::open_memory()?;
+        storage.insert(&Fact::new("@api/timeout", "Timeout", "Timeout is 30s"))?;
+        storage.set_namespace("billing");
+        storage.insert(&Fact::new("@api/timeout", "Timeout", "Timeout is 90s"))?;
+        storage.insert(&Fact::new(
+            "@invoices/due",
+            "Due",
+            "Invoices due in 30 days",
+        ))?;
+
+        // Only the billing facts are visible
+        assert_eq!(
+            storage.get_by_path("@api/timeout")?[0].content,
+            "Timeout is 90s"
+        );
+        assert_eq!(storage.search("timeout", 10)?.len(), 1);
+        assert_eq!(storage.search_in("timeout", 10, None)?.len(), 2);
+        assert_eq!(storage.list_children_all("@")?.len(), 2);
+        assert_eq!(storage.stats()?.total_facts, 2);
+
+        let namespaces = storage.namespaces()?;
+        assert_eq!(namespaces[0].name, "");
+        assert_eq!(namespaces[1].name, "billing");
+        assert_eq!(namespaces[1].active_facts, 2);
+
+        // Moving takes everything under the path along
+        assert_eq!(storage.move_to_namespace("@invoices", "finance")?, 1);
+        assert!(storage.get_by_path("@invoices/due")?.is_empty());
+        storage.set_namespace("finance");
+        assert_eq!(storage.get_by_path("@invoices/due")?.len(), 1);
+
+        // `_` in the path is not a wildcard
+        storage.insert(&Fact::new("@tax_rules/eu", "EU", "VAT applies"))?;
+        storage.insert(&Fact::new("@taxXrules/us", "US", "Sales tax"))?;
+        assert_eq!(storage.move_to_namespace("@tax_rules", "billing")?, 1);
+        assert_eq!(storage.get_by_path("@taxXrules/us")?.len(), 1);
+
+        storage.set_namespace("");
+        assert_eq!(
+            storage.get_by_path("@api/timeout")?[0].content,
+            "Timeout is 30s"
+        );
+        assert_eq!(storage.stats()?.total_facts, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_references_and_backlinks() -> Result<()> {
+        let storage = Storage::open_memory()?;
+
+        let limits = Fact::new("@api/limits", "Limits", "Rate limit is 100/s");
+        storage.insert(&limits)?;
+        let old = Fact::new("@api/old", "Old", "Old endpoint");
+        storage.insert(&old)?;
+        storage.mark_deprecated(&old.id)?;
+
+        let runbook = Fact::new(
+            "@ops/runbook",
+            "Runbook",
+            format!(
+                "Check [[@api/limits]] first, see meh-{}, [[@api/old]] and [[@api/gone]]",
+                limits.id
+            ),
+        );
+        storage.insert(&runbook)?;
+
+        let refs = storage.references_from(&runbook.id)?;
+        assert_eq!(refs.len(), 4);
+        assert!(refs.iter().any(|(t, f)| t == "@api/gone" && f.is_none()));
+
+        // Referenced both by path and by ID, listed once
+        let backlinks = storage.backlinks(&limits)?;
+        assert_eq!(backlinks.len(), 1);
+        assert_eq!(backlinks[0].id, runbook.id);
+
+        let mut broken = storage.broken_references()?;
+        broken.sort_by(|a, b| a.target.cmp(&b.target));
+        assert_eq!(broken.len(), 2);
+        assert_eq!(broken[0].target, "@api/gone");
+        assert_eq!(broken[0].reason, BrokenReason::Missing);
+        assert_eq!(broken[1].target, "@api/old");
+        assert_eq!(broken[1].reason, BrokenReason::Deprecated);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_links_recorded_on_insert() -> Result<()> {
+        let storage = Storage::open_memory()?;
+
+        let original = Fact::new("@test/api", "API", "v1");
+        storage.insert(&original)?;
+        let correction = Fact::correction(&original, "v2");
+        storage.insert(&correction)?;
+        let mut extension = Fact::extension(&correction, "More detail");
+        extension.author_id = "mcp-session1".to_string();
+        storage.insert(&extension)?;
+
+        let supersedes = storage.links_to(&original.id.to_string(), Some(LinkKind::Supersedes))?;
+        assert_eq!(supersedes.len(), 1);
+        assert_eq!(supersedes[0].source_id, correction.id);
+
+        let extensions = storage.get_extensions(&correction.id)?;
+        assert_eq!(extensions.len(), 1);
+        assert_eq!(extensions[0].id, extension.id);
+
+        let extended = storage.get_extended(&extension.id)?;
+        assert_eq!(extended[0].id, correction.id);
+        assert_eq!(storage.links_from(&extension.id, None)?.len(), 1);
+        assert_eq!(storage.links_of_kind(LinkKind::Extends)?.len(), 1);
+
+        assert!(storage.has_session_voted(&correction.id, "mcp-session1")?);
+        assert!(!storage.has_session_voted(&original.id, "mcp-session1")?);
+        assert!(!storage.has_session_voted(&correction.id, "mcp-other")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_links_backfilled_by_migration() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let db_path = dir.path().join("data.db");
+
+        let original = Fact::new("@test/api", "API", "v1");
+        let mut vote = Fact::extension(&original, "## 🗳️ Vote\n+1");
+        vote.title = "Vote: API".to_string();
+
+        // Facts written before fact_links existed
+        {
+            let conn = Connection::open(&db_path)?;
+            migrations::migrate(&conn, "test", &MIGRATIONS[..3])?;
+            for fact in [&original, &vote] {
+                conn.execute(
+                    "INSERT INTO facts (id, path, title, content, tags, extends, fact_type, created_at, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, '[]', ?5, ?6, ?7, ?7)",
+                    params![
+                        fact.id.to_string(),
+                        fact.path,
+                        fact.title,
+                        fact.content,
+                        serde_json::to_string(&fact.extends)?,
+                        format!("{:?}", fact.fact_type).to_lowercase(),
+                        fact.created_at.to_rfc3339(),
+                    ],
+                )?;
+            }
+        }
+
+        let storage = Storage::open(&db_path)?;
+        let links = storage.links_to(&original.id.to_string(), None)?;
+        let kinds: Vec<LinkKind> = links.iter().map(|l| l.kind).collect();
+        assert!(kinds.contains(&LinkKind::Extends));
+        assert!(kinds.contains(&LinkKind::Vote));
+
+        // Content hashes are backfilled too
+        assert_eq!(
+            storage.find_duplicates(&Fact::new("@x", "X", "V1"))?.len(),
+            1
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_access() -> Result<()> {
+        let storage = Storage::open_memory()?;
+
+        let read = Fact::new("@test/read", "Read", "Often read");
+        let unread = Fact::new("@test/unread", "Unread", "Never read");
+        storage.insert(&read)?;
+        storage.insert(&unread)?;
+        assert!(storage.get_by_id(&read.id)?.unwrap().accessed_at.is_none());
+
+        storage.record_access(&[read.id, read.id])?;
+        storage.record_access(&[read.id])?;
+
+        let usage = storage.access_of(&read.id)?.unwrap();
+        assert_eq!(usage.access_count, 2);
+        assert!(storage.get_by_id(&read.id)?.unwrap().accessed_at.is_some());
+        assert!(storage.access_of(&unread.id)?.is_none());
+
+        let most = storage.most_accessed(10)?;
+        assert_eq!(most.len(), 1);
+        assert_eq!(most[0].id, read.id.to_string());
+
+        let never = storage.never_accessed(10)?;
+        assert_eq!(never.len(), 1);
+        assert_eq!(never[0].id, unread.id.to_string());
+
+        Ok(())
+    }
 }
@@ -4,6 +4,7 @@
 //! - Author type (human > AI > system)
 //! - Age decay (older facts lose trust over time)
 //! - Validation boosts (corrections, confirmations)
+//! - Warning penalties (caveats attached with `meh warn`)
 //! - Source reputation
 
 use chrono::{DateTime, Utc};
@@ -40,6 +41,8 @@ pub struct TrustConfig {
     pub confirmation_boost: f32,
     /// Penalty for being superseded
     pub superseded_penalty: f32,
+    /// Penalty for each active warning attached to a fact
+    pub warning_penalty: f32,
 }
 
 impl Default for TrustConfig {
@@ -64,6 +67,7 @@ impl Default for TrustConfig {
             // Boost/penalty
             confirmation_boost: 0.1,
             superseded_penalty: 0.3,
+            warning_penalty: 0.1,
         }
     }
 }
@@ -126,6 +130,11 @@ impl TrustCalculator {
         (trust - self.config.superseded_penalty).max(0.0)
     }
 
+    /// Apply penalty for warnings attached to a fact
+    pub fn apply_warning_penalty(&self, trust: f32, warning_count: usize) -> f32 {
+        (trust - self.config.warning_penalty * warning_count as f32).max(0.0)
+    }
+
     /// Calculate effective trust considering all factors
     pub fn effective_trust(
         &self,
@@ -213,6 +222,20 @@ mod tests {
         assert!((trust - 0.2).abs() < 0.01); // Floor is 0.2
     }
 
+    #[test]
+    fn test_warning_penalty() {
+        let calc = TrustCalculator::new();
+        assert!((calc.apply_warning_penalty(0.8, 0) - 0.8).abs() < 0.01);
+        assert!((calc.apply_warning_penalty(0.8, 2) - 0.6).abs() < 0.01);
+        assert_eq!(calc.apply_warning_penalty(0.2, 5), 0.0);
+
+        let calc = TrustCalculator::with_config(TrustConfig {
+            warning_penalty: 0.25,
+            ..Default::default()
+        });
+        assert!((calc.apply_warning_penalty(0.8, 1) - 0.55).abs() < 0.01);
+    }
+
     #[test]
     fn test_confirmation_boost() {
         let calc = TrustCalculator::new();
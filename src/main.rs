@@ -21,6 +21,11 @@ async fn main() -> Result<()> {
     // Parse CLI arguments
     let cli = Cli::parse();
 
+    // Commands read the namespace from their config (see Config::namespace)
+    if let Some(namespace) = &cli.namespace {
+        meh::config::set_namespace_override(namespace)?;
+    }
+
     // Check if this command should show notifications hint
     let show_hint = should_show_notifications_hint(&cli.command);
 
@@ -29,12 +34,23 @@ async fn main() -> Result<()> {
         Commands::Add(args) => meh::cli::add::run(args),
         Commands::Show(args) => meh::cli::show::run(args).await,
         Commands::Search(args) => meh::cli::search::run(args).await,
+        Commands::Find(args) => meh::cli::find::run(args).await,
         Commands::Ls(args) => meh::cli::browse::run_ls(args),
         Commands::Tree(args) => meh::cli::browse::run_tree(args),
         Commands::Correct(args) => meh::cli::correct::run(args),
+        Commands::Merge(args) => meh::cli::merge::run(args),
         Commands::Extend(args) => meh::cli::extend::run(args),
+        Commands::Warn(args) => meh::cli::warn::run(args),
+        Commands::Links(args) => meh::cli::links::run(args),
+        Commands::Related(args) => meh::cli::related::run(args),
         Commands::Deprecate(args) => meh::cli::deprecate::run(args),
+        Commands::Attach(args) => run_attach(args),
+        Commands::Attachment(args) => meh::cli::attach::run_attachment(args),
         Commands::Gc(args) => run_gc(args),
+        Commands::Archive(args) => run_archive(args),
+        Commands::Dedupe(args) => run_dedupe(args),
+        Commands::Namespace(args) => run_namespace(args),
+        Commands::Template(args) => run_template(args),
         Commands::Init(args) => meh::cli::init::run(args),
         Commands::Config(args) => meh::cli::config::run(args),
         Commands::Context(args) => meh::cli::context::run(args),
@@ -43,6 +59,10 @@ async fn main() -> Result<()> {
         Commands::Stats(args) => meh::cli::stats::execute(args),
         Commands::Serve(args) => run_serve(args).await,
         Commands::Kbs(args) => meh::cli::kbs::execute(args).await,
+        Commands::Db(args) => run_db(args),
+        Commands::Reindex(args) => run_reindex(args),
+        Commands::Backup(args) => run_backup(args),
+        Commands::Restore(args) => run_restore(args),
     };
 
     // Show notifications hint if appropriate
@@ -62,6 +82,11 @@ fn should_show_notifications_hint(command: &Commands) -> bool {
             | Commands::Context(_)
             | Commands::Config(_)
             | Commands::Init(_)
+            | Commands::Db(_)
+            | Commands::Reindex(_)
+            | Commands::Backup(_)
+            | Commands::Restore(_)
+            | Commands::Template(_)
     )
 }
 
@@ -153,6 +178,31 @@ fn run_gc(args: meh::cli::gc::GcArgs) -> Result<()> {
     meh::cli::gc::run(args, &config)
 }
 
+fn run_attach(args: meh::cli::attach::AttachArgs) -> Result<()> {
+    let config = meh::config::Config::load()?;
+    meh::cli::attach::run_attach(args, &config)
+}
+
+fn run_archive(args: meh::cli::archive::ArchiveArgs) -> Result<()> {
+    let config = meh::config::Config::load()?;
+    meh::cli::archive::execute(args, &config)
+}
+
+fn run_dedupe(args: meh::cli::dedupe::DedupeArgs) -> Result<()> {
+    let config = meh::config::Config::load()?;
+    meh::cli::dedupe::run(args, &config)
+}
+
+fn run_namespace(args: meh::cli::namespace::NamespaceArgs) -> Result<()> {
+    let config = meh::config::Config::load()?;
+    meh::cli::namespace::execute(args, &config)
+}
+
+fn run_template(args: meh::cli::template::TemplateArgs) -> Result<()> {
+    let config = meh::config::Config::load()?;
+    meh::cli::template::run(args, &config)
+}
+
 fn run_notifications(args: meh::cli::notifications::NotificationsArgs) -> Result<()> {
     let config = meh::config::Config::load()?;
     meh::cli::notifications::execute(args, &config)
@@ -162,3 +212,23 @@ fn run_pending(args: meh::cli::pending::PendingArgs) -> Result<()> {
     let config = meh::config::Config::load()?;
     meh::cli::pending::execute(args, &config)
 }
+
+fn run_db(args: meh::cli::db::DbArgs) -> Result<()> {
+    let config = meh::config::Config::load()?;
+    meh::cli::db::execute(args, &config)
+}
+
+fn run_reindex(args: meh::cli::db::ReindexArgs) -> Result<()> {
+    let config = meh::config::Config::load()?;
+    meh::cli::db::run_reindex(args, &config)
+}
+
+fn run_backup(args: meh::cli::backup::BackupArgs) -> Result<()> {
+    let config = meh::config::Config::load()?;
+    meh::cli::backup::run_backup(args, &config)
+}
+
+fn run_restore(args: meh::cli::backup::RestoreArgs) -> Result<()> {
+    let config = meh::config::Config::load()?;
+    meh::cli::backup::run_restore(args, &config)
+}
@@ -1,15 +1,22 @@
-//! Fact management handlers for MCP (get_fact, browse, add, correct, extend, deprecate)
+//! Fact management handlers for MCP (get_fact, browse, find, related, add, correct, extend, deprecate)
 
 use serde_json::Value;
 use ulid::Ulid;
 
 use super::ToolResult;
-use crate::config::{Config, WritePolicy};
-use crate::core::fact::{Fact, FactType, Status};
+use crate::config::{Config, DedupePolicy, WritePolicy};
+use crate::core::attachment::format_size;
+use crate::core::embed;
+use crate::core::expiry::parse_expiry;
+use crate::core::fact::{reference_label, Fact, FactType, Status};
+use crate::core::related::{related, RelatedFact};
+use crate::core::schema::{self, SchemaAction, SchemaViolation};
+use crate::core::trust::TrustCalculator;
 use crate::core::PendingWrite;
 use crate::mcp::state::ServerState;
 use crate::mcp::tools::{
-    MehAddTool, MehBrowseTool, MehCorrectTool, MehDeprecateTool, MehExtendTool, MehGetFactTool,
+    MehAddTool, MehBrowseTool, MehCorrectTool, MehDeprecateTool, MehExtendTool, MehFindTool,
+    MehGetFactTool, MehMergeTool, MehRelatedTool, MehWarnTool,
 };
 use crate::remote::BlockingRemoteClient;
 
@@ -80,12 +87,18 @@ pub fn do_get_fact(state: &ServerState, args: &Value) -> ToolResult {
             .next()
     };
 
-    let fact = fact.ok_or_else(|| format!("Fact not found: {}", tool_args.id_or_path))?;
+    let mut fact = fact.ok_or_else(|| format!("Fact not found: {}", tool_args.id_or_path))?;
 
-    let mut result = format!(
-        "# {} (meh-{})\n\n**Path:** {}\n**Status:** {:?}\n**Trust:** {:.2}\n**Author:** {:?} ({})\n**Created:** {}\n\n## Content\n\n{}\n",
-        fact.title,
-        fact.id,
+    state.storage.record_usage_best_effort(&[fact.id]);
+
+    // Warnings go right under the title and lower the shown trust
+    let warnings = state.storage.get_warnings(&fact.id).unwrap_or_default();
+    fact.trust_score = trust_calculator().apply_warning_penalty(fact.trust_score, warnings.len());
+
+    let mut result = format!("# {} (meh-{})\n\n", fact.title, fact.id);
+    result.push_str(&format_warnings(&warnings));
+    result.push_str(&format!(
+        "**Path:** {}\n**Status:** {:?}\n**Trust:** {:.2}\n**Author:** {:?} ({})\n**Created:** {}\n\n## Content\n\n{}\n",
         fact.path,
         fact.status,
         fact.trust_score,
@@ -93,13 +106,55 @@ pub fn do_get_fact(state: &ServerState, args: &Value) -> ToolResult {
         fact.author_id,
         fact.created_at.format("%Y-%m-%d %H:%M"),
         fact.content
-    );
+    ));
 
     if !fact.tags.is_empty() {
         result.push_str(&format!("\n**Tags:** {}\n", fact.tags.join(", ")));
     }
 
-    if let Some(ref sup) = fact.supersedes {
+    if !fact.properties.is_empty() {
+        let props: Vec<String> = fact
+            .properties
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect();
+        result.push_str(&format!("\n**Properties:** {}\n", props.join(", ")));
+    }
+
+    let now = chrono::Utc::now();
+    if let Some(until) = fact.valid_until {
+        let flag = if fact.is_expired(now) {
+            " ⏰ EXPIRED - no longer valid"
+        } else {
+            ""
+        };
+        result.push_str(&format!(
+            "\n**Valid until:** {}{}\n",
+            until.format("%Y-%m-%d %H:%M UTC"),
+            flag
+        ));
+    }
+    if let Some(by) = fact.review_by {
+        let flag = if fact.is_review_due(now) {
+            " 🔎 review due"
+        } else {
+            ""
+        };
+        result.push_str(&format!(
+            "\n**Review by:** {}{}\n",
+            by.format("%Y-%m-%d"),
+            flag
+        ));
+    }
+
+    if !fact.merged_from.is_empty() {
+        let sources: Vec<String> = fact
+            .merged_from
+            .iter()
+            .map(|u| format!("meh-{}", u))
+            .collect();
+        result.push_str(&format!("\n**Merged from:** {}\n", sources.join(", ")));
+    } else if let Some(ref sup) = fact.supersedes {
         result.push_str(&format!("\n**Supersedes:** meh-{}\n", sup));
     }
 
@@ -108,9 +163,153 @@ pub fn do_get_fact(state: &ServerState, args: &Value) -> ToolResult {
         result.push_str(&format!("\n**Extends:** {}\n", extends_str.join(", ")));
     }
 
+    result.push_str(&format_links(state, &fact));
+    result.push_str(&format_attachments(state, &fact.id));
+    if tool_args.include_related {
+        result.push_str(&format_related_brief(state, &fact));
+    }
+
     Ok(result)
 }
 
+/// Most related facts considered for `get` with `include_related`
+const GET_RELATED_LIMIT: usize = 5;
+
+/// Token budget of the related list appended by `get`
+const GET_RELATED_TOKEN_BUDGET: usize = 150;
+
+/// Short "Related" list for `get`: one line per fact until the budget is
+/// used up (~4 chars per token); linked facts are already listed above
+fn format_related_brief(state: &ServerState, fact: &Fact) -> String {
+    let related = related(&state.storage, fact, GET_RELATED_LIMIT, true).unwrap_or_default();
+    let mut lines = String::new();
+    let mut tokens = 0;
+    for r in &related {
+        let line = format!("- {} — {} (meh-{})\n", r.fact.path, r.fact.title, r.fact.id);
+        tokens += line.len() / 4;
+        if tokens > GET_RELATED_TOKEN_BUDGET {
+            break;
+        }
+        lines.push_str(&line);
+    }
+    if lines.is_empty() {
+        return String::new();
+    }
+    format!("\n## Related\n\n{}", lines)
+}
+
+/// Outgoing `[[...]]` / `meh-ID` references, "Referenced by" backlinks and
+/// "Extended by" (active extensions other than warnings, shown above)
+fn format_links(state: &ServerState, fact: &Fact) -> String {
+    let references = state.storage.references_from(&fact.id).unwrap_or_default();
+    let backlinks = state.storage.backlinks(fact).unwrap_or_default();
+    let extensions: Vec<Fact> = state
+        .storage
+        .get_extensions(&fact.id)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|f| f.status == Status::Active && f.fact_type != FactType::Warning)
+        .collect();
+    let mut result = String::new();
+
+    if !references.is_empty() {
+        result.push_str("\n## Links\n\n");
+        for (target, resolved) in &references {
+            match resolved {
+                Some(f) if f.status == Status::Active => result.push_str(&format!(
+                    "- {} — {} (meh-{})\n",
+                    reference_label(target),
+                    f.title,
+                    f.id
+                )),
+                Some(f) => result.push_str(&format!(
+                    "- {} — {} (meh-{}, ⚠️ {})\n",
+                    reference_label(target),
+                    f.title,
+                    f.id,
+                    f.status.as_str()
+                )),
+                None => result.push_str(&format!("- {} — ❌ not found\n", reference_label(target))),
+            }
+        }
+    }
+
+    if !backlinks.is_empty() {
+        result.push_str("\n## Referenced by\n\n");
+        for f in &backlinks {
+            result.push_str(&format!("- {} — {} (meh-{})\n", f.path, f.title, f.id));
+        }
+    }
+
+    if !extensions.is_empty() {
+        result.push_str("\n## Extended by\n\n");
+        for f in &extensions {
+            result.push_str(&format!("- {} — {} (meh-{})\n", f.path, f.title, f.id));
+        }
+    }
+
+    result
+}
+
+/// Trust calculator from config (`[trust] warning_penalty`), defaults if unavailable
+pub(crate) fn trust_calculator() -> TrustCalculator {
+    Config::load()
+        .map(|c| c.trust.calculator())
+        .unwrap_or_default()
+}
+
+/// Warning block shown next to a fact (empty if it has none)
+pub(crate) fn format_warnings(warnings: &[Fact]) -> String {
+    let mut result = String::new();
+    for warning in warnings {
+        result.push_str(&format!(
+            "> ⚠️ **WARNING** (meh-{}): {}\n",
+            warning.id,
+            warning.content.trim().replace('\n', "\n> ")
+        ));
+    }
+    if !result.is_empty() {
+        result.push('\n');
+    }
+    result
+}
+
+/// Largest text attachment returned inline by `get`
+const INLINE_ATTACHMENT_MAX_BYTES: u64 = 16 * 1024;
+
+/// Attachment list for `get`; small text attachments are shown inline
+fn format_attachments(state: &ServerState, fact_id: &Ulid) -> String {
+    let attachments = match state.storage.list_attachments(fact_id) {
+        Ok(a) if !a.is_empty() => a,
+        _ => return String::new(),
+    };
+
+    let mut result = format!("\n## Attachments ({})\n\n", attachments.len());
+    for a in &attachments {
+        result.push_str(&format!(
+            "- **{}** ({}, {})\n",
+            a.name,
+            a.mime_type,
+            format_size(a.size)
+        ));
+    }
+
+    for a in attachments
+        .iter()
+        .filter(|a| a.is_text() && a.size <= INLINE_ATTACHMENT_MAX_BYTES)
+    {
+        if let Ok(Some((_, data))) = state.storage.get_attachment(fact_id, &a.name) {
+            result.push_str(&format!(
+                "\n### {}\n\n```\n{}\n```\n",
+                a.name,
+                String::from_utf8_lossy(&data).trim_end()
+            ));
+        }
+    }
+
+    result
+}
+
 /// Browse facts by path
 pub fn do_browse(state: &ServerState, args: &Value) -> ToolResult {
     let tool_args: MehBrowseTool =
@@ -147,6 +346,108 @@ pub fn do_browse(state: &ServerState, args: &Value) -> ToolResult {
     Ok(result)
 }
 
+/// Find facts whose path matches a glob
+pub fn do_find(state: &ServerState, args: &Value) -> ToolResult {
+    let tool_args: MehFindTool =
+        serde_json::from_value(args.clone()).map_err(|e| format!("Invalid params: {}", e))?;
+
+    let statuses = tool_args
+        .status
+        .iter()
+        .map(|s| s.parse::<Status>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Invalid params: {}", e))?;
+    // One more than asked for tells whether there are more
+    let limit = tool_args.limit.max(0) as usize;
+    let mut facts = state
+        .storage
+        .find_by_pattern(&tool_args.pattern, &statuses, &tool_args.tags, limit + 1)
+        .map_err(|e| format!("Find error: {}", e))?;
+    let limited = facts.len() > limit;
+    facts.truncate(limit);
+
+    if facts.is_empty() {
+        return Ok(format!("No facts match: {}", tool_args.pattern));
+    }
+
+    let mut result = format!("{} fact(s) match {}:\n\n", facts.len(), tool_args.pattern);
+    for fact in &facts {
+        let status = if fact.status == Status::Active {
+            String::new()
+        } else {
+            format!(" [{}]", fact.status.as_str())
+        };
+        result.push_str(&format!(
+            "- {}{} — {} (meh-{}) | Trust: {:.2}\n",
+            fact.path, status, fact.title, fact.id, fact.trust_score
+        ));
+    }
+    if limited {
+        result.push_str(&format!(
+            "\n[Limited to {} results; there may be more. Narrow the pattern or increase `limit`.]",
+            limit
+        ));
+    }
+    Ok(result)
+}
+
+/// Facts related to a fact: links, shared tags, nearby paths, similar text
+pub fn do_related(state: &ServerState, args: &Value) -> ToolResult {
+    let tool_args: MehRelatedTool =
+        serde_json::from_value(args.clone()).map_err(|e| format!("Invalid params: {}", e))?;
+
+    let fact = if let Some(ulid_str) = tool_args.id_or_path.strip_prefix("meh-") {
+        let ulid = Ulid::from_string(ulid_str).map_err(|e| format!("Invalid ULID: {}", e))?;
+        state
+            .storage
+            .get_by_id(&ulid)
+            .map_err(|e| format!("Error: {}", e))?
+    } else {
+        state
+            .storage
+            .get_by_path(&tool_args.id_or_path)
+            .map_err(|e| format!("Error: {}", e))?
+            .into_iter()
+            .next()
+    };
+    let fact = fact.ok_or_else(|| format!("Fact not found: {}", tool_args.id_or_path))?;
+
+    let related = related(
+        &state.storage,
+        &fact,
+        tool_args.limit.max(0) as usize,
+        false,
+    )
+    .map_err(|e| format!("Related error: {}", e))?;
+    if related.is_empty() {
+        return Ok(format!("No related facts for {}", fact.path));
+    }
+
+    let mut result = format!(
+        "{} fact(s) related to {} (meh-{}):\n\n",
+        related.len(),
+        fact.path,
+        fact.id
+    );
+    for RelatedFact {
+        fact,
+        score,
+        reasons,
+        ..
+    } in &related
+    {
+        result.push_str(&format!(
+            "- {} — {} (meh-{}) | Score: {:.2} | {}\n",
+            fact.path,
+            fact.title,
+            fact.id,
+            score,
+            reasons.join("; ")
+        ));
+    }
+    Ok(result)
+}
+
 /// Add a new fact
 pub fn do_add(state: &mut ServerState, args: &Value) -> ToolResult {
     // Check write policy
@@ -155,8 +456,34 @@ pub fn do_add(state: &mut ServerState, args: &Value) -> ToolResult {
     let tool_args: MehAddTool =
         serde_json::from_value(args.clone()).map_err(|e| format!("Invalid params: {}", e))?;
 
+    let title = tool_args
+        .content
+        .lines()
+        .next()
+        .unwrap_or(&tool_args.content)
+        .chars()
+        .take(50)
+        .collect::<String>();
+
+    // Create new fact
+    let mut fact = Fact::new(&tool_args.path, &title, &tool_args.content);
+    fact.tags = tool_args.tags.clone();
+    fact.properties = tool_args
+        .properties
+        .iter()
+        .map(|(k, v)| (k.trim().to_lowercase(), v.trim().to_string()))
+        .collect();
+    if fact.properties.contains_key("") {
+        return Err("Invalid property: key is empty".to_string());
+    }
+
+    // Schemas apply to every KB; "review" violations go to a human
+    let schema_violation = check_schemas(&fact)?;
+    let schema_note = schema_note(&schema_violation);
+
     // If remote KB with "ask" policy, queue locally instead of writing to remote
-    if state.is_remote_kb && state.write_policy == WritePolicy::Ask {
+    if state.is_remote_kb && (state.write_policy == WritePolicy::Ask || schema_violation.is_some())
+    {
         let queue = state.open_pending_queue()?;
         let pending = PendingWrite::new_add(
             &state.kb_name,
@@ -171,8 +498,8 @@ pub fn do_add(state: &mut ServerState, args: &Value) -> ToolResult {
             .map_err(|e| format!("Queue error: {}", e))?;
 
         return Ok(format!(
-            "⏳ Queued for remote KB '{}' (pending approval): queue-{}\n  Path: {}\n  ℹ️ Human review required. Run `meh pending -i` for interactive review",
-            state.kb_name, id, tool_args.path
+            "⏳ Queued for remote KB '{}' (pending approval): queue-{}\n  Path: {}{}\n  ℹ️ Human review required. Run `meh pending -i` for interactive review",
+            state.kb_name, id, tool_args.path, schema_note
         ));
     }
 
@@ -189,45 +516,112 @@ pub fn do_add(state: &mut ServerState, args: &Value) -> ToolResult {
         ));
     }
 
-    // Local KB: create fact locally
-    let title = tool_args
-        .content
-        .lines()
-        .next()
-        .unwrap_or(&tool_args.content)
-        .chars()
-        .take(50)
-        .collect::<String>();
-
-    // Create new fact
-    let mut fact = Fact::new(&tool_args.path, &title, &tool_args.content);
-    fact.tags = tool_args.tags.clone();
-
-    // If write policy is "ask" (local KB), set status to pending_review
-    let is_pending = state.write_policy == WritePolicy::Ask;
+    // Local KB: store the fact locally
+    let now = chrono::Utc::now();
+    fact.valid_until = tool_args
+        .expires
+        .as_deref()
+        .map(|s| parse_expiry(s, now))
+        .transpose()
+        .map_err(|e| format!("Invalid 'expires': {}", e))?;
+    fact.review_by = tool_args
+        .review_by
+        .as_deref()
+        .map(|s| parse_expiry(s, now))
+        .transpose()
+        .map_err(|e| format!("Invalid 'review_by': {}", e))?;
+
+    // If write policy is "ask" (local KB) or a schema asks for review,
+    // set status to pending_review
+    let is_pending = state.write_policy == WritePolicy::Ask || schema_violation.is_some();
     if is_pending {
         fact.status = Status::PendingReview;
     }
 
+    // Identical content already stored (same path first, then KB-wide)?
+    let mut duplicate_note = String::new();
+    let duplicate = state
+        .storage
+        .find_duplicates(&fact)
+        .map_err(|e| format!("Add error: {}", e))?
+        .into_iter()
+        .next();
+
+    if let Some(existing) = duplicate {
+        let location = if existing.path == fact.path {
+            "at this path".to_string()
+        } else {
+            format!("at {}", existing.path)
+        };
+        let policy = Config::load().map(|c| c.core.dedupe).unwrap_or_default();
+
+        match policy {
+            DedupePolicy::Refuse => {
+                return Err(format!(
+                    "Identical fact already exists {}: meh-{}. Use `extend` to add new information or `correct` to change it.",
+                    location, existing.id
+                ));
+            }
+            DedupePolicy::Vote => return add_duplicate_vote(state, &existing, &location),
+            DedupePolicy::Warn => {
+                duplicate_note = format!(
+                    "\n  ⚠️ Identical fact already exists {}: meh-{}",
+                    location, existing.id
+                );
+            }
+        }
+    }
+
     let id = fact.id;
     state
         .storage
         .insert(&fact)
         .map_err(|e| format!("Add error: {}", e))?;
+    embed_written(state, &[&fact]);
 
     if is_pending {
         Ok(format!(
-            "⏳ Created fact (pending review): meh-{}\n  Path: {}\n  ℹ️ Human review required. Run `meh pending -i` for interactive review",
-            id, tool_args.path
+            "⏳ Created fact (pending review): meh-{}\n  Path: {}{}{}\n  ℹ️ Human review required. Run `meh pending -i` for interactive review",
+            id, tool_args.path, duplicate_note, schema_note
         ))
     } else {
         Ok(format!(
-            "✓ Created fact: meh-{}\n  Path: {}",
-            id, tool_args.path
+            "✓ Created fact: meh-{}\n  Path: {}{}",
+            id, tool_args.path, duplicate_note
         ))
     }
 }
 
+/// Turn a duplicate add into a +1 vote on the existing fact
+fn add_duplicate_vote(state: &ServerState, existing: &Fact, location: &str) -> ToolResult {
+    let already_voted = state
+        .storage
+        .has_session_voted(&existing.id, &state.session_id)
+        .map_err(|e| format!("Add error: {}", e))?;
+    if already_voted {
+        return Ok(format!(
+            "ℹ️ Identical fact already exists {}: meh-{} (already confirmed by this session, nothing added)",
+            location, existing.id
+        ));
+    }
+
+    let mut vote = Fact::vote(existing, "+1", "re-added identical content");
+    vote.author_id = state.session_id.clone();
+    if state.write_policy == WritePolicy::Ask {
+        vote.status = Status::PendingReview;
+    }
+
+    state
+        .storage
+        .insert_vote(&vote)
+        .map_err(|e| format!("Add error: {}", e))?;
+
+    Ok(format!(
+        "🗳️ Identical fact already exists {}: meh-{}\n  Recorded a +1 vote instead: meh-{}",
+        location, existing.id, vote.id
+    ))
+}
+
 /// Correct (supersede) an existing fact
 pub fn do_correct(state: &mut ServerState, args: &Value) -> ToolResult {
     state.check_write_allowed()?;
@@ -255,8 +649,20 @@ pub fn do_correct(state: &mut ServerState, args: &Value) -> ToolResult {
         String::new()
     };
 
+    // Create correction fact
+    let title = format!("Correction: {}", original.title);
+    let mut correction = Fact::new(&original.path, &title, &tool_args.new_content);
+    correction.supersedes = Some(original_ulid);
+    correction.fact_type = FactType::Correction;
+    correction.properties = original.properties.clone();
+
+    // Schemas apply to every KB; "review" violations go to a human
+    let schema_violation = check_schemas(&correction)?;
+    let schema_note = schema_note(&schema_violation);
+
     // If remote KB with "ask" policy, queue locally
-    if state.is_remote_kb && state.write_policy == WritePolicy::Ask {
+    if state.is_remote_kb && (state.write_policy == WritePolicy::Ask || schema_violation.is_some())
+    {
         let queue = state.open_pending_queue()?;
         let pending = PendingWrite::new_correct(
             &state.kb_name,
@@ -271,8 +677,8 @@ pub fn do_correct(state: &mut ServerState, args: &Value) -> ToolResult {
             .map_err(|e| format!("Queue error: {}", e))?;
 
         return Ok(format!(
-            "⏳ Queued correction for remote KB '{}' (pending approval): queue-{}\n  Will supersede: {}{}\n  ℹ️ Human review required. Run `meh pending -i` for interactive review",
-            state.kb_name, id, original_id_str, resolve_note
+            "⏳ Queued correction for remote KB '{}' (pending approval): queue-{}\n  Will supersede: {}{}{}\n  ℹ️ Human review required. Run `meh pending -i` for interactive review",
+            state.kb_name, id, original_id_str, resolve_note, schema_note
         ));
     }
 
@@ -289,36 +695,30 @@ pub fn do_correct(state: &mut ServerState, args: &Value) -> ToolResult {
         ));
     }
 
-    // Create correction fact
-    let title = format!("Correction: {}", original.title);
-    let mut correction = Fact::new(&original.path, &title, &tool_args.new_content);
-    correction.supersedes = Some(original_ulid);
-    correction.fact_type = FactType::Correction;
-
-    let is_pending = state.write_policy == WritePolicy::Ask;
+    let is_pending = state.write_policy == WritePolicy::Ask || schema_violation.is_some();
     if is_pending {
         correction.status = Status::PendingReview;
     }
 
     let new_id = correction.id;
 
+    // Insert and supersede atomically (pending corrections supersede on approval)
     state
         .storage
-        .insert(&correction)
-        .map_err(|e| format!("Insert error: {}", e))?;
-
-    // Only mark original as superseded if not pending
-    if !is_pending {
-        state
-            .storage
-            .mark_superseded(&original_ulid)
-            .map_err(|e| format!("Update error: {}", e))?;
-    }
+        .transaction(|tx| {
+            tx.insert(&correction)?;
+            if !is_pending {
+                tx.mark_superseded(&original_ulid)?;
+            }
+            Ok(())
+        })
+        .map_err(|e| format!("Correct error: {}", e))?;
+    embed_written(state, &[&correction]);
 
     if is_pending {
         Ok(format!(
-            "⏳ Created correction (pending review): meh-{}\n  Will supersede: {}{}\n  ℹ️ Human review required. Run `meh pending -i` for interactive review",
-            new_id, original_id_str, resolve_note
+            "⏳ Created correction (pending review): meh-{}\n  Will supersede: {}{}{}\n  ℹ️ Human review required. Run `meh pending -i` for interactive review",
+            new_id, original_id_str, resolve_note, schema_note
         ))
     } else {
         Ok(format!(
@@ -328,6 +728,42 @@ pub fn do_correct(state: &mut ServerState, args: &Value) -> ToolResult {
     }
 }
 
+/// Check a write against the per-path schemas in config
+///
+/// Refusals become the error; a violation that only asks for review is
+/// returned so the write can be stored as pending.
+fn check_schemas(fact: &Fact) -> Result<Option<SchemaViolation>, String> {
+    let schemas = Config::load()
+        .map_err(|e| format!("Config error: {}", e))?
+        .schemas;
+    match schema::check(&schemas, fact) {
+        Some(violation) if violation.action == SchemaAction::Reject => Err(format!(
+            "Rejected by schema. {}\nFix the content and try again.",
+            violation.explanation()
+        )),
+        violation => Ok(violation),
+    }
+}
+
+/// Embed facts just written for semantic search (best effort: the write
+/// stands, `meh db reindex --embeddings` catches up)
+fn embed_written(state: &ServerState, facts: &[&Fact]) {
+    if let Ok(config) = Config::load() {
+        let _ = embed::embed_written(&state.storage, &config.search.embeddings, facts);
+    }
+}
+
+/// Note shown with writes sent to review by a schema
+fn schema_note(violation: &Option<SchemaViolation>) -> String {
+    match violation {
+        Some(violation) => format!(
+            "\n  ⚠️ Sent to review by schema. {}",
+            violation.explanation().replace('\n', "\n  ")
+        ),
+        None => String::new(),
+    }
+}
+
 /// Extend an existing fact with additional information
 pub fn do_extend(state: &mut ServerState, args: &Value) -> ToolResult {
     state.check_write_allowed()?;
@@ -407,6 +843,7 @@ pub fn do_extend(state: &mut ServerState, args: &Value) -> ToolResult {
         .storage
         .insert(&extension)
         .map_err(|e| format!("Insert error: {}", e))?;
+    embed_written(state, &[&extension]);
 
     if is_pending {
         Ok(format!(
@@ -421,6 +858,143 @@ pub fn do_extend(state: &mut ServerState, args: &Value) -> ToolResult {
     }
 }
 
+/// Attach a warning (caveat) to a fact without superseding it
+pub fn do_warn(state: &mut ServerState, args: &Value) -> ToolResult {
+    state.check_write_allowed()?;
+
+    let tool_args: MehWarnTool =
+        serde_json::from_value(args.clone()).map_err(|e| format!("Invalid params: {}", e))?;
+
+    if state.is_remote_kb {
+        return Err(
+            "Warnings are not supported for remote KBs yet. Use 'extend' instead.".to_string(),
+        );
+    }
+
+    // Resolve to latest version (handles both ID and path, auto-follows supersede chain)
+    let (original, was_resolved) = state
+        .storage
+        .resolve_to_latest(&tool_args.fact_id)
+        .map_err(|e| format!("Error: {}", e))?
+        .ok_or_else(|| format!("Fact not found: {}", tool_args.fact_id))?;
+
+    let resolve_note = if was_resolved {
+        format!(
+            "\n  ⚠️ Note: Original was superseded, warning attached to latest version: meh-{}",
+            original.id
+        )
+    } else {
+        String::new()
+    };
+
+    let mut warning = Fact::warning(&original, &tool_args.warning);
+    warning.author_id = state.session_id.clone();
+    warning.generate_summary(150);
+
+    let is_pending = state.write_policy == WritePolicy::Ask;
+    if is_pending {
+        warning.status = Status::PendingReview;
+    }
+
+    state
+        .storage
+        .insert(&warning)
+        .map_err(|e| format!("Insert error: {}", e))?;
+
+    if is_pending {
+        Ok(format!(
+            "⏳ Created warning (pending review): meh-{}\n  On: meh-{}{}\n  ℹ️ Human review required. Run `meh pending -i` for interactive review",
+            warning.id, original.id, resolve_note
+        ))
+    } else {
+        Ok(format!(
+            "⚠️ Created warning: meh-{}\n  On: meh-{}{}",
+            warning.id, original.id, resolve_note
+        ))
+    }
+}
+
+/// Merge several facts into one consolidated fact that supersedes them all
+pub fn do_merge(state: &mut ServerState, args: &Value) -> ToolResult {
+    state.check_write_allowed()?;
+
+    let tool_args: MehMergeTool =
+        serde_json::from_value(args.clone()).map_err(|e| format!("Invalid params: {}", e))?;
+
+    if state.is_remote_kb {
+        return Err(
+            "Merge is not supported for remote KBs yet. Use 'correct' and 'deprecate' instead."
+                .to_string(),
+        );
+    }
+
+    // Resolve each source to its latest version, skipping repeats
+    let mut sources: Vec<Fact> = Vec::new();
+    let mut resolve_notes = String::new();
+    for fact_id in &tool_args.fact_ids {
+        let (fact, was_resolved) = state
+            .storage
+            .resolve_to_latest(fact_id)
+            .map_err(|e| format!("Error: {}", e))?
+            .ok_or_else(|| format!("Fact not found: {}", fact_id))?;
+        if fact.status != Status::Active {
+            return Err(format!(
+                "meh-{} is {}; only active facts can be merged",
+                fact.id,
+                fact.status.as_str()
+            ));
+        }
+        if was_resolved {
+            resolve_notes.push_str(&format!(
+                "\n  ⚠️ Note: {} was superseded, merging latest version: meh-{}",
+                fact_id, fact.id
+            ));
+        }
+        if !sources.iter().any(|s| s.id == fact.id) {
+            sources.push(fact);
+        }
+    }
+    if sources.len() < 2 {
+        return Err("Merge needs at least two distinct facts in 'fact_ids'".to_string());
+    }
+
+    let mut merged = Fact::merge(&sources, &tool_args.path, &tool_args.content);
+    merged.author_id = state.session_id.clone();
+    merged.generate_summary(150);
+
+    let schema_violation = check_schemas(&merged)?;
+    let schema_note = schema_note(&schema_violation);
+    let is_pending = state.write_policy == WritePolicy::Ask || schema_violation.is_some();
+    if is_pending {
+        merged.status = Status::PendingReview;
+    }
+
+    // Pending merges supersede their sources on approval
+    state
+        .storage
+        .insert_merge(&merged)
+        .map_err(|e| format!("Merge error: {}", e))?;
+    embed_written(state, &[&merged]);
+
+    let source_ids: Vec<String> = sources.iter().map(|f| format!("meh-{}", f.id)).collect();
+    if is_pending {
+        Ok(format!(
+            "⏳ Created merged fact (pending review): meh-{}\n  Will supersede: {}{}{}\n  ℹ️ Human review required. Run `meh pending -i` for interactive review",
+            merged.id,
+            source_ids.join(", "),
+            resolve_notes,
+            schema_note
+        ))
+    } else {
+        Ok(format!(
+            "✓ Created merged fact: meh-{}\n  Supersedes: {}{}",
+            merged.id,
+            source_ids.join(", "),
+            resolve_notes
+        ))
+    }
+}
+
 /// Mark a fact as deprecated
 pub fn do_deprecate(state: &mut ServerState, args: &Value) -> ToolResult {
     state.check_write_allowed()?;
@@ -1,11 +1,11 @@
-//! KB management handlers for MCP (list_kbs, switch_kb, bulk_vote)
+//! KB management handlers for MCP (list_kbs, switch_kb, namespaces, bulk_vote)
 
 use serde_json::Value;
 use ulid::Ulid;
 
 use super::ToolResult;
 use crate::config::{Config, WritePolicy};
-use crate::core::fact::{Fact, FactType, Status};
+use crate::core::fact::{namespace_label, parse_namespace, Fact, Status};
 use crate::core::PendingWrite;
 use crate::mcp::state::ServerState;
 use crate::mcp::tools::{MehBulkVoteTool, MehListKbsTool, MehSwitchContextTool, MehSwitchKbTool};
@@ -89,6 +89,60 @@ pub fn do_switch_kb(state: &mut ServerState, args: &Value) -> ToolResult {
     ))
 }
 
+/// List the namespaces of the current (local) KB
+pub fn do_list_namespaces(state: &ServerState) -> ToolResult {
+    if state.is_remote_kb {
+        return Err("Namespaces are only available for local KBs".to_string());
+    }
+
+    let namespaces = state
+        .storage
+        .namespaces()
+        .map_err(|e| format!("Error: {}", e))?;
+    if namespaces.is_empty() {
+        return Ok("No facts yet.".to_string());
+    }
+
+    let mut result = format!(
+        "🗂️ Namespaces (current: {})\n\n",
+        namespace_label(state.storage.namespace())
+    );
+    for ns in &namespaces {
+        let marker = if ns.name == state.storage.namespace() {
+            "▶ "
+        } else {
+            "  "
+        };
+        result.push_str(&format!(
+            "{}{} ({} active / {} total)\n",
+            marker,
+            namespace_label(&ns.name),
+            ns.active_facts,
+            ns.total_facts
+        ));
+    }
+    result.push_str(
+        "\n💡 Use meh_context({\"action\": \"switch_namespace\", \"namespace\": \"<name>\"}) to switch.",
+    );
+
+    Ok(result)
+}
+
+/// Switch the namespace of the current (local) KB for this session
+pub fn do_switch_namespace(state: &mut ServerState, namespace: &str) -> ToolResult {
+    if state.is_remote_kb {
+        return Err("Namespaces are only available for local KBs".to_string());
+    }
+
+    let namespace = parse_namespace(namespace).map_err(|e| e.to_string())?;
+    state.storage.set_namespace(namespace);
+
+    Ok(format!(
+        "✓ Switched to namespace '{}'",
+        namespace_label(state.storage.namespace())
+    ))
+}
+
 /// Record multiple votes in a single call
 pub fn do_bulk_vote(state: &mut ServerState, args: &Value) -> ToolResult {
     state.check_write_allowed()?;
@@ -119,7 +173,7 @@ pub fn do_bulk_vote(state: &mut ServerState, args: &Value) -> ToolResult {
     }
 
     // For each vote, create an extension fact referencing the original
-    let mut created: Vec<String> = Vec::new();
+    let mut extensions: Vec<Fact> = Vec::new();
 
     for v in &tool_args.votes {
         // Parse original fact ID
@@ -136,14 +190,8 @@ pub fn do_bulk_vote(state: &mut ServerState, args: &Value) -> ToolResult {
             .map_err(|e| format!("Error: {}", e))?
             .ok_or_else(|| format!("Original fact not found: {}", v.fact_id))?;
 
-        // Compose extension content
         let reason = v.reason.as_deref().unwrap_or("");
-        let extension_content = format!("## 🗳️ Vote\n{} — {}\n", v.vote, reason);
-
-        let title = format!("Vote: {}", original.title);
-        let mut extension = Fact::new(&original.path, &title, &extension_content);
-        extension.extends = vec![original_ulid];
-        extension.fact_type = FactType::Extension;
+        let mut extension = Fact::vote(&original, &v.vote, reason);
         extension.author_id = state.session_id.clone();
 
         // If write policy is ask (local), set pending
@@ -152,15 +200,22 @@ pub fn do_bulk_vote(state: &mut ServerState, args: &Value) -> ToolResult {
             extension.status = Status::PendingReview;
         }
 
-        let new_id = extension.id;
-        state
-            .storage
-            .insert(&extension)
-            .map_err(|e| format!("Insert error: {}", e))?;
-
-        created.push(format!("meh-{}", new_id));
+        extensions.push(extension);
     }
 
+    // All votes are recorded together or not at all
+    state
+        .storage
+        .transaction(|tx| {
+            for extension in &extensions {
+                tx.insert_vote(extension)?;
+            }
+            Ok(())
+        })
+        .map_err(|e| format!("Insert error: {}", e))?;
+
+    let created: Vec<String> = extensions.iter().map(|e| format!("meh-{}", e.id)).collect();
+
     Ok(format!(
         "✓ Recorded {} vote(s): {}",
         created.len(),
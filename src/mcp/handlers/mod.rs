@@ -4,10 +4,10 @@
 //!
 //! # Merged Tools (v2)
 //! To reduce tool count from 17 to 4:
-//! - `meh_facts` → search, get, browse, federated_search
+//! - `meh_facts` → search, get, browse, find, related, federated_search
 //! - `meh_write` → add, correct, extend, deprecate, bulk_vote
 //! - `meh_notify` → get, ack, subscribe
-//! - `meh_context` → list_kbs, switch_kb, switch_context, show
+//! - `meh_context` → list_kbs, switch_kb, switch_context, list_namespaces, switch_namespace, show
 
 pub mod facts;
 pub mod kbs;
@@ -75,7 +75,10 @@ fn dispatch_facts(state: &mut ServerState, args: &Value) -> ToolResult {
             let legacy_args = serde_json::json!({
                 "query": query,
                 "path_filter": tool_args.path_filter,
-                "limit": tool_args.limit
+                "limit": tool_args.limit,
+                "all_namespaces": tool_args.all_namespaces,
+                "fuzzy": tool_args.fuzzy,
+                "semantic": tool_args.semantic
             });
             search::do_search(state, &legacy_args)
         }
@@ -85,7 +88,8 @@ fn dispatch_facts(state: &mut ServerState, args: &Value) -> ToolResult {
                 .ok_or("Missing 'id_or_path' for get action")?;
             let legacy_args = serde_json::json!({
                 "id_or_path": id_or_path,
-                "include_history": tool_args.include_history
+                "include_history": tool_args.include_history,
+                "include_related": tool_args.include_related
             });
             facts::do_get_fact(state, &legacy_args)
         }
@@ -99,6 +103,28 @@ fn dispatch_facts(state: &mut ServerState, args: &Value) -> ToolResult {
             });
             facts::do_browse(state, &legacy_args)
         }
+        "find" => {
+            let pattern = tool_args
+                .pattern
+                .ok_or("Missing 'pattern' for find action")?;
+            let legacy_args = serde_json::json!({
+                "pattern": pattern,
+                "status": tool_args.status,
+                "tags": tool_args.tags,
+                "limit": tool_args.limit
+            });
+            facts::do_find(state, &legacy_args)
+        }
+        "related" => {
+            let id_or_path = tool_args
+                .id_or_path
+                .ok_or("Missing 'id_or_path' for related action")?;
+            let legacy_args = serde_json::json!({
+                "id_or_path": id_or_path,
+                "limit": tool_args.limit
+            });
+            facts::do_related(state, &legacy_args)
+        }
         "federated_search" => {
             let query = tool_args
                 .query
@@ -106,12 +132,13 @@ fn dispatch_facts(state: &mut ServerState, args: &Value) -> ToolResult {
             let legacy_args = serde_json::json!({
                 "query": query,
                 "kbs": tool_args.kbs,
-                "limit_per_kb": tool_args.limit_per_kb.unwrap_or(10)
+                "limit_per_kb": tool_args.limit_per_kb.unwrap_or(10),
+                "limit": tool_args.limit
             });
             search::do_federated_search(state, &legacy_args)
         }
         _ => Err(format!(
-            "Unknown action '{}' for meh_facts. Use: search, get, browse, federated_search",
+            "Unknown action '{}' for meh_facts. Use: search, get, browse, find, related, federated_search",
             tool_args.action
         )),
     }
@@ -131,7 +158,10 @@ fn dispatch_write(state: &mut ServerState, args: &Value) -> ToolResult {
             let legacy_args = serde_json::json!({
                 "path": path,
                 "content": content,
-                "tags": tool_args.tags
+                "tags": tool_args.tags,
+                "properties": tool_args.properties,
+                "expires": tool_args.expires,
+                "review_by": tool_args.review_by
             });
             facts::do_add(state, &legacy_args)
         }
@@ -162,6 +192,19 @@ fn dispatch_write(state: &mut ServerState, args: &Value) -> ToolResult {
             });
             facts::do_extend(state, &legacy_args)
         }
+        "warn" => {
+            let fact_id = tool_args
+                .fact_id
+                .ok_or("Missing 'fact_id' for warn action")?;
+            let warning = tool_args
+                .warning
+                .ok_or("Missing 'warning' for warn action")?;
+            let legacy_args = serde_json::json!({
+                "fact_id": fact_id,
+                "warning": warning
+            });
+            facts::do_warn(state, &legacy_args)
+        }
         "deprecate" => {
             let fact_id = tool_args
                 .fact_id
@@ -172,6 +215,18 @@ fn dispatch_write(state: &mut ServerState, args: &Value) -> ToolResult {
             });
             facts::do_deprecate(state, &legacy_args)
         }
+        "merge" => {
+            let path = tool_args.path.ok_or("Missing 'path' for merge action")?;
+            let content = tool_args
+                .content
+                .ok_or("Missing 'content' for merge action")?;
+            let legacy_args = serde_json::json!({
+                "fact_ids": tool_args.fact_ids,
+                "path": path,
+                "content": content
+            });
+            facts::do_merge(state, &legacy_args)
+        }
         "bulk_vote" => {
             let legacy_args = serde_json::json!({
                 "votes": tool_args.votes
@@ -179,7 +234,7 @@ fn dispatch_write(state: &mut ServerState, args: &Value) -> ToolResult {
             kbs::do_bulk_vote(state, &legacy_args)
         }
         _ => Err(format!(
-            "Unknown action '{}' for meh_write. Use: add, correct, extend, deprecate, bulk_vote",
+            "Unknown action '{}' for meh_write. Use: add, correct, extend, warn, deprecate, merge, bulk_vote",
             tool_args.action
         )),
     }
@@ -250,9 +305,16 @@ fn dispatch_context(state: &mut ServerState, args: &Value) -> ToolResult {
             });
             kbs::do_switch_context(state, &legacy_args)
         }
+        "list_namespaces" => kbs::do_list_namespaces(state),
+        "switch_namespace" => {
+            let namespace = tool_args
+                .namespace
+                .ok_or("Missing 'namespace' for switch_namespace action")?;
+            kbs::do_switch_namespace(state, &namespace)
+        }
         "show" => kbs::do_show_context(state, &serde_json::json!({})),
         _ => Err(format!(
-            "Unknown action '{}' for meh_context. Use: list_kbs, switch_kb, switch_context, show",
+            "Unknown action '{}' for meh_context. Use: list_kbs, switch_kb, switch_context, list_namespaces, switch_namespace, show",
             tool_args.action
         )),
     }
@@ -1,15 +1,15 @@
 //! Search handlers for MCP
 
-use std::path::PathBuf;
-use std::time::Duration;
+use std::collections::{HashMap, HashSet};
 
 use serde_json::Value;
 use ulid::Ulid;
 
-use super::ToolResult;
+use super::{facts, ToolResult};
 use crate::config::Config;
-use crate::core::fact::Fact;
-use crate::core::storage::Storage;
+use crate::core::fact::{namespace_label, Fact};
+use crate::core::search::{self, render_snippet, SearchQuery};
+use crate::core::{embed, federated};
 use crate::mcp::state::ServerState;
 use crate::mcp::tools::{MehFederatedSearchTool, MehSearchTool};
 
@@ -18,10 +18,45 @@ pub fn do_search(state: &ServerState, args: &Value) -> ToolResult {
     let tool_args: MehSearchTool =
         serde_json::from_value(args.clone()).map_err(|e| format!("Invalid params: {}", e))?;
 
-    let facts = state
-        .storage
-        .search(&tool_args.query, tool_args.limit)
-        .map_err(|e| format!("Search error: {}", e))?;
+    let mut query = SearchQuery::parse(&tool_args.query)
+        .map_err(|e| format!("Invalid query: {}", e))?
+        .with_limit(tool_args.limit.max(0) as usize);
+    if tool_args.path_filter.is_some() {
+        query.path_prefix = tool_args.path_filter.clone();
+    }
+    query.all_namespaces = tool_args.all_namespaces;
+    query.fuzzy = tool_args.fuzzy;
+    // A broken config must not fail a search; use the default settings
+    let search_config = Config::load().map(|c| c.search).unwrap_or_default();
+    query.snippet_words = Some(search_config.snippet_words);
+    if tool_args.semantic {
+        let embedder = embed::from_config(&search_config.embeddings)
+            .map_err(|e| format!("Embedder error: {}", e))?;
+        query.embedder = Some(embedder);
+    }
+    let results =
+        search::search(&state.storage, &query).map_err(|e| format!("Search error: {}", e))?;
+    let fuzzy: HashSet<Ulid> = results
+        .iter()
+        .filter(|r| r.fuzzy)
+        .map(|r| r.fact.id)
+        .collect();
+    let mut snippets: HashMap<Ulid, String> = HashMap::new();
+    let facts: Vec<Fact> = results
+        .into_iter()
+        .map(|r| {
+            if let Some(snippet) = r.snippet {
+                snippets.insert(r.fact.id, snippet);
+            }
+            r.fact
+        })
+        .collect();
+
+    // Warnings are shown under their target; drop them as separate hits
+    let facts = search::fold_warnings(facts);
+
+    let ids: Vec<Ulid> = facts.iter().map(|f| f.id).collect();
+    state.storage.record_usage_best_effort(&ids);
 
     // Check for pending notifications and inject at the top
     let notification_header = get_notification_header(state);
@@ -43,14 +78,34 @@ pub fn do_search(state: &ServerState, args: &Value) -> ToolResult {
     result.push_str(&notification_header);
     result.push_str(&format!("Found {} facts:\n\n", facts.len()));
 
+    let trust = facts::trust_calculator();
     for fact in &facts {
+        let warnings = state.storage.get_warnings(&fact.id).unwrap_or_default();
+        let namespace = if tool_args.all_namespaces {
+            format!("**Namespace:** {}\n", namespace_label(&fact.namespace))
+        } else {
+            String::new()
+        };
+        let fuzzy_note = if fuzzy.contains(&fact.id) {
+            "**Match:** fuzzy (close spelling, not an exact word match)\n"
+        } else {
+            ""
+        };
+        // Matched words in bold, so the agent sees why the fact matched
+        let body = match snippets.get(&fact.id) {
+            Some(snippet) => render_snippet(snippet, |w| format!("**{}**", w)),
+            None => fact.summary.clone().unwrap_or_else(|| fact.content.clone()),
+        };
         result.push_str(&format!(
-            "## {} (meh-{})\n**Path:** {}\n**Trust:** {:.2}\n{}\n\n---\n\n",
+            "## {} (meh-{})\n{}**Path:** {}\n{}{}**Trust:** {:.2}\n{}\n\n---\n\n",
             fact.title,
             fact.id,
+            facts::format_warnings(&warnings),
             fact.path,
-            fact.trust_score,
-            fact.summary.as_deref().unwrap_or(&fact.content)
+            namespace,
+            fuzzy_note,
+            trust.apply_warning_penalty(fact.trust_score, warnings.len()),
+            body
         ));
     }
 
@@ -70,12 +125,16 @@ pub fn do_search(state: &ServerState, args: &Value) -> ToolResult {
     Ok(result)
 }
 
-/// Search across multiple KBs
-pub fn do_federated_search(_state: &ServerState, args: &Value) -> ToolResult {
+/// Search across multiple KBs: one list ranked across all of them
+pub fn do_federated_search(state: &ServerState, args: &Value) -> ToolResult {
     let tool_args: MehFederatedSearchTool =
         serde_json::from_value(args.clone()).map_err(|e| format!("Invalid params: {}", e))?;
 
     let config = Config::load().map_err(|e| format!("Config error: {}", e))?;
+    let query = SearchQuery::parse(&tool_args.query)
+        .map_err(|e| format!("Invalid query: {}", e))?
+        .with_limit(tool_args.limit_per_kb.max(0) as usize)
+        .with_snippets(config.search.snippet_words);
 
     // Determine which KBs to search
     let kbs_to_search: Vec<String> = if tool_args.kbs.is_empty() {
@@ -88,190 +147,66 @@ pub fn do_federated_search(_state: &ServerState, args: &Value) -> ToolResult {
         return Err("No KBs configured in search_order".to_string());
     }
 
-    let mut all_results: Vec<(String, Vec<Fact>)> = Vec::new();
-    let mut errors: Vec<String> = Vec::new();
-    let timeout = Duration::from_secs(config.search.federated_timeout_secs);
-
-    for kb_name in &kbs_to_search {
-        match search_single_kb(
-            &config,
-            kb_name,
-            &tool_args.query,
-            tool_args.limit_per_kb,
-            timeout,
-        ) {
-            Ok(facts) => all_results.push((kb_name.clone(), facts)),
-            Err(e) => errors.push(e),
-        }
-    }
+    let results = federated::search(
+        &config,
+        &kbs_to_search,
+        &query,
+        tool_args.limit.max(0) as usize,
+        state.storage.namespace(),
+    );
 
     // Build result
     let mut result = format!("🔍 Federated search for: \"{}\"\n\n", tool_args.query);
-    let total_count: usize = all_results.iter().map(|(_, facts)| facts.len()).sum();
 
-    if total_count == 0 && errors.is_empty() {
+    if results.hits.is_empty() && results.errors.is_empty() {
         result.push_str("No results found in any KB.\n");
     }
 
-    for (kb_name, facts) in &all_results {
-        if facts.is_empty() {
-            continue;
-        }
-        result.push_str(&format!("## 📚 {} ({} results)\n\n", kb_name, facts.len()));
-        for fact in facts {
-            result.push_str(&format!(
-                "- **{}** (meh-{})\n  Path: {} | Trust: {:.2}\n",
-                fact.title, fact.id, fact.path, fact.trust_score
-            ));
-            if !fact.content.is_empty() {
-                let summary = fact.summary.as_deref().unwrap_or(&fact.content);
-                let short = if summary.len() > 100 {
-                    &summary[..100]
-                } else {
-                    summary
-                };
-                result.push_str(&format!("  {}\n", short.replace('\n', " ")));
-            }
-            result.push('\n');
+    for (i, hit) in results.hits.iter().enumerate() {
+        let fact = &hit.fact;
+        let also_in = if hit.also_in.is_empty() {
+            String::new()
+        } else {
+            format!(" (also in: {})", hit.also_in.join(", "))
+        };
+        result.push_str(&format!(
+            "{}. **{}** (meh-{}) · 📚 {}{}\n   Path: {} | Trust: {:.2} | Score: {:.2}\n",
+            i + 1,
+            fact.title,
+            fact.id,
+            hit.kb,
+            also_in,
+            fact.path,
+            fact.trust_score,
+            hit.score
+        ));
+        let summary = fact.summary.as_deref().unwrap_or(&fact.content);
+        if let Some(snippet) = &hit.snippet {
+            let line = render_snippet(snippet, |w| format!("**{}**", w));
+            result.push_str(&format!("   {}\n", line));
+        } else if !summary.is_empty() {
+            let short: String = summary.chars().take(100).collect();
+            result.push_str(&format!("   {}\n", short.replace('\n', " ")));
         }
+        result.push('\n');
     }
 
-    if !errors.is_empty() {
+    if !results.errors.is_empty() {
         result.push_str("\n⚠️ Errors:\n");
-        for e in &errors {
+        for e in &results.errors {
             result.push_str(&format!("  - {}\n", e));
         }
     }
 
     result.push_str(&format!(
         "\n📊 Total: {} results from {} KB(s)",
-        total_count,
-        all_results.len()
+        results.hits.len(),
+        results.searched.len()
     ));
 
     Ok(result)
 }
 
-/// Search a single KB (helper for federated search)
-fn search_single_kb(
-    config: &Config,
-    kb_name: &str,
-    query: &str,
-    limit: i64,
-    timeout: Duration,
-) -> Result<Vec<Fact>, String> {
-    let kb_config = config
-        .get_kb(kb_name)
-        .ok_or_else(|| format!("{}: not found in config", kb_name))?;
-
-    match kb_config.kb_type.as_str() {
-        "sqlite" => search_sqlite_kb(config, kb_config, query, limit),
-        "remote" => search_remote_kb(config, kb_name, kb_config, query, limit, timeout),
-        other => Err(format!("{}: unknown type '{}'", kb_name, other)),
-    }
-}
-
-/// Search local SQLite KB
-fn search_sqlite_kb(
-    config: &Config,
-    kb_config: &crate::config::KbConfig,
-    query: &str,
-    limit: i64,
-) -> Result<Vec<Fact>, String> {
-    let db_path = if let Some(path) = &kb_config.path {
-        PathBuf::from(path)
-    } else {
-        config.data_dir()
-    };
-
-    let storage =
-        Storage::open(&db_path).map_err(|e| format!("{}: open error: {}", kb_config.name, e))?;
-
-    storage
-        .search(query, limit)
-        .map_err(|e| format!("{}: search error: {}", kb_config.name, e))
-}
-
-/// Search remote KB via HTTP
-fn search_remote_kb(
-    config: &Config,
-    kb_name: &str,
-    kb_config: &crate::config::KbConfig,
-    query: &str,
-    limit: i64,
-    timeout: Duration,
-) -> Result<Vec<Fact>, String> {
-    let server = config
-        .get_server_for_kb(kb_name)
-        .ok_or_else(|| format!("{}: no server configured", kb_name))?;
-
-    let slug = kb_config
-        .slug
-        .as_ref()
-        .ok_or_else(|| format!("{}: no slug configured", kb_name))?;
-
-    let client = reqwest::blocking::Client::builder()
-        .timeout(timeout)
-        .build()
-        .map_err(|e| format!("{}: client error: {}", kb_name, e))?;
-
-    let encoded_query = encode_query(query);
-    let url = format!(
-        "{}/api/v1/kbs/{}/search?q={}&limit={}",
-        server.url, slug, encoded_query, limit
-    );
-
-    let mut request = client.get(&url);
-    if let Some(ref key) = server.api_key {
-        request = request.header("X-API-Key", key);
-    }
-
-    let response = request
-        .send()
-        .map_err(|e| format!("{}: request error: {}", kb_name, e))?;
-
-    if !response.status().is_success() {
-        return Err(format!("{}: HTTP {}", kb_name, response.status()));
-    }
-
-    let json_facts: Vec<serde_json::Value> = response
-        .json()
-        .map_err(|e| format!("{}: parse error: {}", kb_name, e))?;
-
-    // Convert to Fact structs
-    let facts = json_facts
-        .iter()
-        .filter_map(|v| {
-            let id = v["id"].as_str()?;
-            let path = v["path"].as_str()?;
-            let title = v["title"].as_str()?;
-            let summary = v["summary"].as_str();
-            let trust = v["trust_score"].as_f64().unwrap_or(0.5) as f32;
-
-            let mut fact = Fact::new(path, title, summary.unwrap_or(""));
-            fact.trust_score = trust;
-            if let Ok(ulid) = Ulid::from_string(id.trim_start_matches("meh-")) {
-                fact.id = ulid;
-            }
-            Some(fact)
-        })
-        .collect();
-
-    Ok(facts)
-}
-
-/// Simple URL encoding for query string
-fn encode_query(query: &str) -> String {
-    query
-        .chars()
-        .map(|c| match c {
-            ' ' => "+".to_string(),
-            c if c.is_alphanumeric() || c == '-' || c == '_' || c == '.' => c.to_string(),
-            c => format!("%{:02X}", c as u8),
-        })
-        .collect()
-}
-
 /// Check if search results contain proposals that AI should vote on
 fn get_voting_hint(state: &ServerState, facts: &[Fact]) -> String {
     let proposal_paths = ["@meh/todo/", "@meh/board/", "@meh/rfc/", "@meh/proposal/"];
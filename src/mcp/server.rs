@@ -9,6 +9,8 @@ use std::io::{BufRead, BufReader, Write};
 use super::handlers;
 use super::jsonrpc::{JsonRpcRequest, JsonRpcResponse};
 use super::state::ServerState;
+use crate::core::expiry;
+use crate::core::notifications::NotificationStorage;
 use crate::core::storage::Storage;
 
 /// MCP Server - wrapper around ServerState
@@ -132,9 +134,18 @@ pub fn run_mcp_server(db_path: std::path::PathBuf) -> anyhow::Result<()> {
         eprintln!("meh MCP server starting...");
     }
 
+    // A broken config must not stop the server; the default namespace is ""
+    // and a fuzzy index is left as it is
     let storage = Storage::open(&db_path)?;
+    let storage = match crate::config::Config::load() {
+        Ok(config) => storage
+            .with_namespace(config.namespace()?)
+            .with_fuzzy(config.fuzzy_for(&db_path))?,
+        Err(_) => storage,
+    };
 
-    // Auto-GC on startup if enabled in config
+    // Deprecate expired facts, then auto-GC if enabled in config
+    run_expiry(&storage, &db_path);
     run_auto_gc(&storage);
 
     let mut server = MehMcpServer::new(storage);
@@ -181,6 +192,28 @@ pub fn run_mcp_server(db_path: std::path::PathBuf) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Deprecate expired facts and notify about passed review dates
+fn run_expiry(storage: &Storage, db_path: &std::path::Path) {
+    let notifications_db = db_path
+        .parent()
+        .map(|p| p.join("notifications.db"))
+        .unwrap_or_else(|| db_path.with_extension("notifications.db"));
+    let notifications = NotificationStorage::open(&notifications_db).ok();
+
+    match expiry::process(storage, notifications.as_ref(), chrono::Utc::now(), false) {
+        Ok(report) if !report.expired.is_empty() => {
+            eprintln!(
+                "⏰ Expiry: Deprecated {} expired fact(s)",
+                report.expired.len()
+            );
+        }
+        Ok(_) => {}
+        Err(e) => {
+            eprintln!("⚠️ Expiry check failed: {}", e);
+        }
+    }
+}
+
 /// Run automatic garbage collection if enabled
 fn run_auto_gc(storage: &Storage) {
     let config = match crate::config::Config::load() {
@@ -194,13 +227,19 @@ fn run_auto_gc(storage: &Storage) {
 
     let retention_days = config.core.gc_retention_days;
 
-    match storage.garbage_collect(retention_days, false) {
+    match storage.garbage_collect(retention_days, config.core.gc_mode, false) {
         Ok(result) if result.deleted_count > 0 => {
             eprintln!(
                 "🧹 Auto-GC: Cleaned {} deprecated/superseded fact(s) older than {} days",
                 result.deleted_count, retention_days
             );
         }
+        Ok(result) if result.archived_count > 0 => {
+            eprintln!(
+                "📦 Auto-GC: Archived {} deprecated/superseded fact(s) older than {} days",
+                result.archived_count, retention_days
+            );
+        }
         Ok(_) => {
             // Nothing to clean, don't log
         }
@@ -216,24 +255,31 @@ fn tool_definitions() -> Vec<Value> {
         // ====== MERGED TOOL 1: meh_facts ======
         json!({
             "name": "meh_facts",
-            "description": "Read facts from knowledge base. Actions: 'search' (find facts by query), 'get' (get single fact by ID/path), 'browse' (explore path structure like ls/tree), 'federated_search' (search across multiple KBs). Examples: meh_facts({\"action\": \"search\", \"query\": \"authentication\"}) or meh_facts({\"action\": \"browse\", \"path\": \"@meh\", \"mode\": \"tree\"})",
+            "description": "Read facts from knowledge base. Actions: 'search' (find facts by query), 'get' (get single fact by ID/path), 'browse' (explore path structure like ls/tree), 'find' (facts whose path matches a glob, e.g. '@products/*/api/**'), 'related' (facts related to one fact by links, tags, path and similar text), 'federated_search' (search all KBs in parallel, one ranked list annotated with the source KB). Examples: meh_facts({\"action\": \"search\", \"query\": \"authentication\"}) or meh_facts({\"action\": \"browse\", \"path\": \"@meh\", \"mode\": \"tree\"})",
             "inputSchema": {
                 "type": "object",
                 "properties": {
                     "action": {
                         "type": "string",
-                        "enum": ["search", "get", "browse", "federated_search"],
+                        "enum": ["search", "get", "browse", "find", "related", "federated_search"],
                         "description": "Action to perform"
                     },
-                    "query": { "type": "string", "description": "Search query (for 'search' and 'federated_search')" },
+                    "query": { "type": "string", "description": "Search query (for 'search' and 'federated_search'). Plain words match any; also \"exact phrase\", +required, -excluded, tag:x, -tag:x, path:@a/b/**, trust>0.6, author:human|ai|system, prop:key=value" },
                     "path_filter": { "type": "string", "description": "Limit search to path prefix (for 'search')" },
-                    "limit": { "type": "integer", "description": "Max results (default: 20)", "default": 20 },
-                    "id_or_path": { "type": "string", "description": "Fact ID or path (for 'get')" },
+                    "all_namespaces": { "type": "boolean", "description": "Search every namespace, not just the current one (for 'search')", "default": false },
+                    "semantic": { "type": "boolean", "description": "Rank by meaning as well as keywords, e.g. for questions like 'why do logins fail' (for 'search')", "default": false },
+                    "fuzzy": { "type": "boolean", "description": "Also match misspelled or partial words (for 'search'; needs fuzzy = true for the KB). Used automatically when few facts match", "default": false },
+                    "limit": { "type": "integer", "description": "Max results (default: 20; for 'federated_search', of the merged list)", "default": 20 },
+                    "id_or_path": { "type": "string", "description": "Fact ID or path (for 'get' and 'related')" },
                     "include_history": { "type": "boolean", "description": "Include history chain (for 'get')", "default": false },
+                    "include_related": { "type": "boolean", "description": "Append a short list of related facts (for 'get')", "default": false },
                     "path": { "type": "string", "description": "Path to browse (for 'browse')", "default": "@" },
                     "mode": { "type": "string", "enum": ["ls", "tree"], "description": "Browse mode (for 'browse')", "default": "ls" },
                     "depth": { "type": "integer", "description": "Tree depth (for 'browse')", "default": 3 },
                     "cursor": { "type": "string", "description": "Pagination cursor (for 'browse')" },
+                    "pattern": { "type": "string", "description": "Path glob (for 'find'): * = one segment, ** = any number, e.g. '@products/*/api/**'" },
+                    "status": { "type": "array", "items": { "type": "string" }, "description": "Statuses to include (for 'find'): active, deprecated, superseded, archived, pending_review. Default: active" },
+                    "tags": { "type": "array", "items": { "type": "string" }, "description": "Required tags (for 'find')" },
                     "kbs": { "type": "array", "items": { "type": "string" }, "description": "KBs to search (for 'federated_search')" },
                     "limit_per_kb": { "type": "integer", "description": "Results per KB (for 'federated_search')", "default": 10 }
                 },
@@ -243,22 +289,27 @@ fn tool_definitions() -> Vec<Value> {
         // ====== MERGED TOOL 2: meh_write ======
         json!({
             "name": "meh_write",
-            "description": "Write/modify facts in knowledge base. Actions: 'add' (create new fact), 'correct' (fix wrong fact, creates superseding), 'extend' (add info to existing fact), 'deprecate' (mark as outdated), 'bulk_vote' (vote on multiple facts). SEARCH FIRST before adding! Examples: meh_write({\"action\": \"add\", \"path\": \"@bugs/issue\", \"content\": \"# Bug...\"}) or meh_write({\"action\": \"extend\", \"fact_id\": \"meh-01ABC\", \"extension\": \"## Update...\"})",
+            "description": "Write/modify facts in knowledge base. Actions: 'add' (create new fact), 'correct' (fix wrong fact, creates superseding), 'extend' (add info to existing fact), 'warn' (attach a caveat to a fact that is still valid), 'deprecate' (mark as outdated), 'merge' (consolidate overlapping facts into one that supersedes them all), 'bulk_vote' (vote on multiple facts). SEARCH FIRST before adding! Examples: meh_write({\"action\": \"add\", \"path\": \"@bugs/issue\", \"content\": \"# Bug...\"}) or meh_write({\"action\": \"extend\", \"fact_id\": \"meh-01ABC\", \"extension\": \"## Update...\"})",
             "inputSchema": {
                 "type": "object",
                 "properties": {
                     "action": {
                         "type": "string",
-                        "enum": ["add", "correct", "extend", "deprecate", "bulk_vote"],
+                        "enum": ["add", "correct", "extend", "warn", "deprecate", "merge", "bulk_vote"],
                         "description": "Action to perform"
                     },
-                    "path": { "type": "string", "description": "Fact path (for 'add'). Start with @, use lowercase kebab-case" },
-                    "content": { "type": "string", "description": "Markdown content (for 'add'). First line = title" },
+                    "path": { "type": "string", "description": "Fact path (for 'add', 'merge'). Start with @, use lowercase kebab-case" },
+                    "content": { "type": "string", "description": "Markdown content (for 'add', 'merge'). First line = title" },
                     "tags": { "type": "array", "items": { "type": "string" }, "description": "Tags for categorization" },
+                    "properties": { "type": "object", "additionalProperties": { "type": "string" }, "description": "Structured metadata (for 'add'), e.g. {\"severity\": \"high\", \"component\": \"billing\"}" },
+                    "expires": { "type": "string", "description": "Expiry for time-bound facts (for 'add'): duration like '7d', '12h', '2w' or date '2026-07-01'. Expired facts are deprecated automatically" },
+                    "review_by": { "type": "string", "description": "Review date (for 'add'), same format as 'expires'. A notification is created once it passes" },
                     "fact_id": { "type": "string", "description": "Target fact: ID (meh-XXX) OR path (@path/to/fact). Auto-resolves to latest version if superseded." },
                     "new_content": { "type": "string", "description": "Replacement content (for 'correct')" },
                     "extension": { "type": "string", "description": "Additional content to append (for 'extend')" },
+                    "warning": { "type": "string", "description": "Caveat shown next to the fact (for 'warn'), e.g. 'Does not apply to /health'" },
                     "reason": { "type": "string", "description": "Reason for change (for 'correct', 'deprecate')" },
+                    "fact_ids": { "type": "array", "items": { "type": "string" }, "description": "Facts to merge, IDs or paths, at least two (for 'merge'). All are superseded by the merged fact" },
                     "votes": {
                         "type": "array",
                         "items": {
@@ -301,18 +352,19 @@ fn tool_definitions() -> Vec<Value> {
         // ====== MERGED TOOL 4: meh_context ======
         json!({
             "name": "meh_context",
-            "description": "Manage KB context and session. Actions: 'list_kbs' (show available KBs), 'switch_kb' (change active KB), 'switch_context' (local/remote), 'show' (current context info). Examples: meh_context({\"action\": \"show\"}) or meh_context({\"action\": \"switch_kb\", \"kb_name\": \"company\"})",
+            "description": "Manage KB context and session. Actions: 'list_kbs' (show available KBs), 'switch_kb' (change active KB), 'switch_context' (local/remote), 'list_namespaces' (namespaces of the local KB), 'switch_namespace' (change namespace for this session), 'show' (current context info). Examples: meh_context({\"action\": \"show\"}) or meh_context({\"action\": \"switch_namespace\", \"namespace\": \"billing\"})",
             "inputSchema": {
                 "type": "object",
                 "properties": {
                     "action": {
                         "type": "string",
-                        "enum": ["list_kbs", "switch_kb", "switch_context", "show"],
+                        "enum": ["list_kbs", "switch_kb", "switch_context", "list_namespaces", "switch_namespace", "show"],
                         "description": "Action to perform"
                     },
                     "detailed": { "type": "boolean", "description": "Show detailed KB info (for 'list_kbs')", "default": false },
                     "kb_name": { "type": "string", "description": "KB name to switch to (for 'switch_kb')" },
-                    "context": { "type": "string", "description": "Context: 'local' or URL (for 'switch_context')" }
+                    "context": { "type": "string", "description": "Context: 'local' or URL (for 'switch_context')" },
+                    "namespace": { "type": "string", "description": "Namespace, 'default' for the default one (for 'switch_namespace')" }
                 },
                 "required": ["action"]
             }
@@ -4,6 +4,7 @@ use std::path::PathBuf;
 use ulid::Ulid;
 
 use crate::config::{Config, WritePolicy};
+use crate::core::fact::namespace_label;
 use crate::core::notifications::NotificationStorage;
 use crate::core::pending_queue::PendingQueue;
 use crate::core::storage::Storage;
@@ -98,13 +99,16 @@ impl ServerState {
         }
         self.first_tool_call = false;
 
-        let context_info = format!(
+        let mut context_info = format!(
             "📍 **Current KB:** {} ({})\n   **Write:** {:?}\n   **Session:** {}\n",
             self.kb_name,
             if self.is_remote_kb { "remote" } else { "local" },
             self.write_policy,
             self.session_id
         );
+        if !self.is_remote_kb && !self.storage.namespace().is_empty() {
+            context_info.push_str(&format!("   **Namespace:** {}\n", self.storage.namespace()));
+        }
 
         let quick_start = r#"
 ## 🚀 Quick Start
@@ -234,8 +238,11 @@ mcp_meh_meh_context({"action": "switch_kb", "kb_name": "..."})
                 config.data_dir()
             };
 
-            self.storage =
-                Storage::open(&db_path).map_err(|e| format!("Failed to open KB: {}", e))?;
+            // The session keeps its namespace across KBs
+            self.storage = Storage::open(&db_path)
+                .and_then(|s| s.with_fuzzy(kb_config.fuzzy))
+                .map_err(|e| format!("Failed to open KB: {}", e))?
+                .with_namespace(self.storage.namespace());
         }
 
         Ok(())
@@ -270,7 +277,9 @@ mcp_meh_meh_context({"action": "switch_kb", "kb_name": "..."})
             let config = Config::load().map_err(|e| format!("Config error: {}", e))?;
             let db_path = config.data_dir();
             self.storage = Storage::open(&db_path)
-                .map_err(|e| format!("Failed to open local storage: {}", e))?;
+                .and_then(|s| s.with_fuzzy(config.fuzzy_for(&db_path)))
+                .map_err(|e| format!("Failed to open local storage: {}", e))?
+                .with_namespace(self.storage.namespace());
 
             self.kb_name = "local".to_string();
             self.write_policy = WritePolicy::Allow;
@@ -349,6 +358,10 @@ mcp_meh_meh_context({"action": "switch_kb", "kb_name": "..."})
             }
         } else {
             output.push_str("   Type:    local (SQLite)\n");
+            output.push_str(&format!(
+                "   Namespace: {}\n",
+                namespace_label(self.storage.namespace())
+            ));
         }
 
         output.push_str(&format!("   Write:   {:?}\n", self.write_policy));
@@ -4,10 +4,12 @@
 //!
 //! # Merged Tools (v2)
 //! To reduce tool count and avoid VS Code activation issues:
-//! - `meh_facts` → search, get, browse, federated_search
+//! - `meh_facts` → search, get, browse, find, related, federated_search
 //! - `meh_write` → add, correct, extend, deprecate, bulk_vote
 //! - `meh_notify` → get_notifications, ack_notifications, subscribe
-//! - `meh_context` → list_kbs, switch_kb, switch_context, show_context
+//! - `meh_context` → list_kbs, switch_kb, switch_context, list_namespaces, switch_namespace, show_context
+
+use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
@@ -15,10 +17,10 @@ use serde::{Deserialize, Serialize};
 // Merged Tools (v2) - 4 tools instead of 17
 // ============================================================================
 
-/// Unified facts/read tool - combines search, get, browse, federated_search
+/// Unified facts/read tool - combines search, get, browse, find, related, federated_search
 #[derive(Debug, Deserialize, Serialize)]
 pub struct MehFactsTool {
-    /// Action: "search", "get", "browse", "federated_search"
+    /// Action: "search", "get", "browse", "find", "related", "federated_search"
     pub action: String,
 
     // Search params
@@ -28,12 +30,20 @@ pub struct MehFactsTool {
     pub path_filter: Option<String>,
     #[serde(default = "default_limit")]
     pub limit: i64,
+    #[serde(default)]
+    pub all_namespaces: bool,
+    #[serde(default)]
+    pub fuzzy: bool,
+    #[serde(default)]
+    pub semantic: bool,
 
     // Get params
     #[serde(default)]
     pub id_or_path: Option<String>,
     #[serde(default)]
     pub include_history: bool,
+    #[serde(default)]
+    pub include_related: bool,
 
     // Browse params
     #[serde(default)]
@@ -45,6 +55,14 @@ pub struct MehFactsTool {
     #[serde(default)]
     pub cursor: Option<String>,
 
+    // Find params
+    #[serde(default)]
+    pub pattern: Option<String>,
+    #[serde(default)]
+    pub status: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+
     // Federated search params
     #[serde(default)]
     pub kbs: Vec<String>,
@@ -52,10 +70,10 @@ pub struct MehFactsTool {
     pub limit_per_kb: Option<i64>,
 }
 
-/// Unified write tool - combines add, correct, extend, deprecate, bulk_vote
+/// Unified write tool - combines add, correct, extend, warn, deprecate, bulk_vote
 #[derive(Debug, Deserialize, Serialize)]
 pub struct MehWriteTool {
-    /// Action: "add", "correct", "extend", "deprecate", "bulk_vote"
+    /// Action: "add", "correct", "extend", "warn", "deprecate", "merge", "bulk_vote"
     pub action: String,
 
     // Add params
@@ -65,6 +83,12 @@ pub struct MehWriteTool {
     pub content: Option<String>,
     #[serde(default)]
     pub tags: Vec<String>,
+    #[serde(default)]
+    pub properties: BTreeMap<String, String>,
+    #[serde(default)]
+    pub expires: Option<String>,
+    #[serde(default)]
+    pub review_by: Option<String>,
 
     // Correct/Extend/Deprecate params
     #[serde(default)]
@@ -74,8 +98,14 @@ pub struct MehWriteTool {
     #[serde(default)]
     pub extension: Option<String>,
     #[serde(default)]
+    pub warning: Option<String>,
+    #[serde(default)]
     pub reason: Option<String>,
 
+    // Merge params
+    #[serde(default)]
+    pub fact_ids: Vec<String>,
+
     // Bulk vote params
     #[serde(default)]
     pub votes: Vec<VoteInput>,
@@ -106,10 +136,12 @@ pub struct MehNotifyTool {
     pub show: bool,
 }
 
-/// Unified context tool - combines list_kbs, switch_kb, switch_context, show_context
+/// Unified context tool - combines list_kbs, switch_kb, switch_context,
+/// list_namespaces, switch_namespace, show_context
 #[derive(Debug, Deserialize, Serialize)]
 pub struct MehContextTool {
-    /// Action: "list_kbs", "switch_kb", "switch_context", "show"
+    /// Action: "list_kbs", "switch_kb", "switch_context", "list_namespaces",
+    /// "switch_namespace", "show"
     pub action: String,
 
     // List KBs params
@@ -123,6 +155,10 @@ pub struct MehContextTool {
     // Switch context params
     #[serde(default)]
     pub context: Option<String>,
+
+    // Switch namespace params
+    #[serde(default)]
+    pub namespace: Option<String>,
 }
 
 // ============================================================================
@@ -132,7 +168,8 @@ pub struct MehContextTool {
 /// Search the knowledge base for facts matching a query
 #[derive(Debug, Deserialize, Serialize)]
 pub struct MehSearchTool {
-    /// Search query - natural language or keywords
+    /// Search query - keywords plus optional query syntax (`"phrase"`,
+    /// `-word`, `tag:`, `path:`, `trust>`, `author:`, `prop:`)
     pub query: String,
     /// Optional path prefix filter (e.g. '@products/alpha')
     #[serde(default)]
@@ -140,6 +177,15 @@ pub struct MehSearchTool {
     /// Maximum number of results (default: 20)
     #[serde(default = "default_limit")]
     pub limit: i64,
+    /// Search every namespace of the database, not just the current one
+    #[serde(default)]
+    pub all_namespaces: bool,
+    /// Also return fuzzy (trigram) matches, e.g. for misspelled words
+    #[serde(default)]
+    pub fuzzy: bool,
+    /// Rank by meaning as well as keywords (embeddings)
+    #[serde(default)]
+    pub semantic: bool,
 }
 
 fn default_limit() -> i64 {
@@ -154,6 +200,9 @@ pub struct MehGetFactTool {
     /// Include superseded/extended facts chain
     #[serde(default)]
     pub include_history: bool,
+    /// Append a short list of related facts
+    #[serde(default)]
+    pub include_related: bool,
 }
 
 /// Browse the knowledge base path structure
@@ -176,6 +225,37 @@ pub struct MehBrowseTool {
     pub cursor: Option<String>,
 }
 
+/// Find facts whose path matches a glob
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MehFindTool {
+    /// Path pattern: `*` = one segment, `**` = any number
+    /// (e.g. '@products/*/api/**')
+    pub pattern: String,
+    /// Statuses to include (default: active)
+    #[serde(default)]
+    pub status: Vec<String>,
+    /// Required tags (AND logic)
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Maximum number of results (default: 100)
+    #[serde(default = "default_browse_limit")]
+    pub limit: i64,
+}
+
+/// Facts related to a fact ("more like this")
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MehRelatedTool {
+    /// Fact ID (meh-xxx) or path (@path/to/fact)
+    pub id_or_path: String,
+    /// Maximum number of results (default: 10)
+    #[serde(default = "default_related_limit")]
+    pub limit: i64,
+}
+
+fn default_related_limit() -> i64 {
+    10
+}
+
 fn default_path() -> String {
     "@".to_string()
 }
@@ -199,6 +279,15 @@ pub struct MehAddTool {
     /// Optional tags for categorization
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Optional structured metadata (e.g. {"severity": "high"})
+    #[serde(default)]
+    pub properties: BTreeMap<String, String>,
+    /// Optional expiry: duration ("7d", "12h") or date ("2026-07-01")
+    #[serde(default)]
+    pub expires: Option<String>,
+    /// Optional review date, same format as `expires`
+    #[serde(default)]
+    pub review_by: Option<String>,
 }
 
 /// Correct an existing fact
@@ -222,6 +311,26 @@ pub struct MehExtendTool {
     pub extension: String,
 }
 
+/// Attach a warning (caveat) to an existing fact
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MehWarnTool {
+    /// ID or path of the fact to warn about
+    pub fact_id: String,
+    /// The caveat
+    pub warning: String,
+}
+
+/// Merge several facts into one consolidated fact
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MehMergeTool {
+    /// IDs or paths of the facts to merge (at least two)
+    pub fact_ids: Vec<String>,
+    /// Path of the merged fact
+    pub path: String,
+    /// Consolidated content
+    pub content: String,
+}
+
 /// Mark a fact as deprecated
 #[derive(Debug, Deserialize, Serialize)]
 pub struct MehDeprecateTool {
@@ -312,6 +421,9 @@ pub struct MehFederatedSearchTool {
     /// Maximum results per KB (default: 10)
     #[serde(default = "default_federated_limit")]
     pub limit_per_kb: i64,
+    /// Maximum results of the merged list (default: 20)
+    #[serde(default = "default_limit")]
+    pub limit: i64,
 }
 
 fn default_federated_limit() -> i64 {
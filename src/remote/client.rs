@@ -288,6 +288,27 @@ impl RemoteClient {
             .map(|r| r.nodes)
     }
 
+    /// One page of the direct children of a path; pass the returned
+    /// `cursor` to get the next page (`None` on the last one)
+    pub async fn browse_page(
+        &self,
+        kb_slug: &str,
+        path: &str,
+        cursor: Option<&str>,
+    ) -> Result<BrowseResponse> {
+        let url = self.url(&format!("/api/v1/kbs/{}/browse/{}", kb_slug, path))?;
+
+        let mut builder = self.auth_header(self.client.get(url));
+
+        if let Some(cursor) = cursor {
+            builder = builder.query(&[("cursor", cursor)]);
+        }
+
+        let resp = builder.send().await.context("Failed to browse")?;
+
+        self.handle_response(resp).await
+    }
+
     // ============== Helpers ==============
 
     /// Handle response and deserialize